@@ -9,12 +9,27 @@
 
 use super::*;
 
+use crate::generators::PhaseResettable;
 use bae_gen::*;
 use bae_mod::*;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-type GeneratorSP = Arc<dyn Generator>;
-type ModifierSP = Arc<dyn Modifier>;
+/// Alias for a [`Generator`] object wrapped in a smart pointer. Like
+/// [`BlockSP`], the object is shared behind a [`Mutex`].
+///
+/// [`Generator`]: ../../generators/trait.Generator.html
+/// [`BlockSP`]: ../type.BlockSP.html
+/// [`Mutex`]: https://doc.rust-lang.org/std/sync/struct.Mutex.html
+pub type GeneratorSP = Arc<Mutex<dyn Generator + Send>>;
+
+/// Alias for a [`Modifier`] object wrapped in a smart pointer. Like
+/// [`BlockSP`], the object is shared behind a [`Mutex`].
+///
+/// [`Modifier`]: ../../modifiers/trait.Modifier.html
+/// [`BlockSP`]: ../type.BlockSP.html
+/// [`Mutex`]: https://doc.rust-lang.org/std/sync/struct.Mutex.html
+pub type ModifierSP = Arc<Mutex<dyn Modifier + Send>>;
 
 /// Type defining the closure that combines inputted SampleT samples from the
 /// outputs of the [`Generator`]s and [`Modifier`]s of the containing
@@ -23,12 +38,64 @@ type ModifierSP = Arc<dyn Modifier>;
 /// [`Generator`]: ../../generators/trait.Generator.html
 /// [`Modifier`]: ../../modifiers/trait.Modifier.html
 /// [`BaeBlock`]: struct.BaeBlock.html
-pub type InterBase = dyn FnMut(SampleT, SampleT) -> SampleT;
+pub type InterBase = dyn FnMut(SampleT, SampleT) -> SampleT + Send;
 
 /// Reference-counted wrapper for the closure [`InterBase`]
 ///
 /// [`InterBase`]: type.InterBase.html
-pub type Inter = Arc<InterBase>;
+pub type Inter = Arc<Mutex<InterBase>>;
+
+/// Type defining a closure applying a control value to a [`Generator`] or
+/// [`Modifier`] of type `T`, typically by calling one of its parameter
+/// setters. See [`BaeBlock::from_controlled_generator`].
+///
+/// [`Generator`]: ../../generators/trait.Generator.html
+/// [`Modifier`]: ../../modifiers/trait.Modifier.html
+/// [`BaeBlock::from_controlled_generator`]: struct.BaeBlock.html#method.from_controlled_generator
+pub type ControlMapping<T> = Box<dyn FnMut(&mut T, SampleT) + Send>;
+
+/// Enum describing the standard interactors a [`BaeBlock`] can be configured
+/// with. Unlike an opaque [`Inter`] closure, an [`InterKind`] can be inspected,
+/// stored, and displayed, which makes it suitable for presets.
+///
+/// [`BaeBlock`]: struct.BaeBlock.html
+/// [`Inter`]: type.Inter.html
+/// [`InterKind`]: enum.InterKind.html
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InterKind {
+    /// Multiplies the generator and modifier samples together.
+    Multiply,
+    /// Adds the generator and modifier samples together.
+    Add,
+    /// Passes the generator sample through.
+    GeneratorOnly,
+    /// Passes the modifier sample through.
+    ModifierOnly,
+    /// Sums the generator and modifier samples scaled by the given weights.
+    Weighted {
+        /// Weight applied to the generator sample.
+        g: SampleT,
+        /// Weight applied to the modifier sample.
+        m: SampleT,
+    },
+}
+
+impl InterKind {
+    /// Constructs the [`Inter`] closure corresponding to this kind.
+    ///
+    /// [`Inter`]: type.Inter.html
+    pub fn to_inter(self) -> Inter {
+        match self {
+            InterKind::Multiply => BaeBlock::default_interactor(),
+            InterKind::Add => BaeBlock::sum_interactor(),
+            InterKind::GeneratorOnly => BaeBlock::generator_passthrough(),
+            InterKind::ModifierOnly => BaeBlock::modifier_passthrough(),
+            InterKind::Weighted { g, m } => {
+                Arc::new(Mutex::new(move |ge: SampleT, mo: SampleT| ge * g + mo * m))
+            }
+        }
+    }
+}
 
 /// Struct used for generalizing the structure of and abstracting the [`Sound`]
 /// struct. This allows us to create complex sounds as a graph of [`BaeBlock`]s,
@@ -37,9 +104,10 @@ pub type Inter = Arc<InterBase>;
 /// [`Generator`] and [`Modifier`] output. See [`Sound`] documentation for more info.
 ///
 /// Internally, the [`Generator`], [`Modifier`], and [`Inter`] are stored wrapped
-/// within an [`Arc`]. This means that when you clone a [`BaeBlock`], the
-/// internal objects are *not* cloned. Rather, their reference count is incremented,
-/// and the wrapped objects stay where they are.
+/// within an [`Arc`] and a [`Mutex`]. This means that when you clone their smart
+/// pointers, the internal objects are *not* cloned. Rather, their reference
+/// count is incremented, the wrapped objects stay where they are, and each is
+/// locked while the [`BaeBlock`] processes it.
 ///
 /// [`Generator`]: ../../generators/trait.Generator.html
 /// [`Modifier`]: ../../modifiers/trait.Modifier.html
@@ -47,10 +115,22 @@ pub type Inter = Arc<InterBase>;
 /// [`Sound`]: struct.Sound.html
 /// [`Inter`]: type.Inter.html
 /// [`Arc`]: https://doc.rust-lang.org/std/sync/struct.Arc.html
+/// [`Mutex`]: https://doc.rust-lang.org/std/sync/struct.Mutex.html
 pub struct BaeBlock {
     g: GeneratorSP,
     m: ModifierSP,
     i: Inter,
+    inter_kind: Option<InterKind>,
+    has_generator: bool,
+    type_name: &'static str,
+    cost_hint: u32,
+    control_rate: bool,
+    bypass: bool,
+    metadata: HashMap<String, String>,
+    params: Option<ParameterizedSP>,
+    phase: Option<Arc<Mutex<dyn PhaseResettable + Send>>>,
+    reset: Option<Arc<Mutex<dyn FnMut() + Send>>>,
+    controls: Vec<Box<dyn FnMut(SampleT) + Send>>,
     input: SampleT,
 }
 
@@ -71,15 +151,16 @@ impl BaeBlock {
     /// [`Inter`]: type.Inter.html
     pub fn new<T, U>(g: T, m: U, i: Inter) -> Self
     where
-        T: 'static + Generator,
-        U: 'static + Modifier,
+        T: 'static + Generator + Send,
+        U: 'static + Modifier + Send,
     {
-        BaeBlock {
-            g: Arc::new(g),
-            m: Arc::new(m),
-            i,
-            input: SampleT::default(),
+        BaeBlockBuilder {
+            i: Some(i),
+            ..Default::default()
         }
+        .generator(g)
+        .modifier(m)
+        .build()
     }
 
     /// Creates a new block from the given [`Generator`]. For the [`BaeBlock`],
@@ -96,14 +177,9 @@ impl BaeBlock {
     /// [`Empty`]: ../../generators/empty/struct.Empty.html
     pub fn from_generator<T>(g: T) -> Self
     where
-        T: 'static + Generator,
+        T: 'static + Generator + Send,
     {
-        BaeBlock {
-            g: Arc::new(g),
-            m: Arc::new(Passthrough::new()),
-            i: Self::generator_passthrough(),
-            input: SampleT::default(),
-        }
+        BaeBlockBuilder::new().generator(g).build()
     }
 
     /// Creates a new block from the given [`Modifier`]. For the [`BaeBlock`],
@@ -120,20 +196,229 @@ impl BaeBlock {
     /// [`Empty`]: ../../modifiers/empty/struct.Empty.html
     pub fn from_modifier<U>(m: U) -> Self
     where
-        U: 'static + Modifier,
+        U: 'static + Modifier + Send,
     {
-        BaeBlock {
-            g: Arc::new(Zero::new()),
-            m: Arc::new(m),
-            i: Self::modifier_passthrough(),
-            input: SampleT::default(),
-        }
+        BaeBlockBuilder::new().modifier(m).build()
+    }
+
+    /// Creates a new block from the given [`Generator`] as with
+    /// [`BaeBlock::from_generator`], additionally exposing the parameters of
+    /// the [`Generator`] through [`Block::parameters`] and
+    /// [`Block::set_parameter`].
+    ///
+    /// # Parameters
+    /// * `g` - The [`Generator`] for the [`BaeBlock`].
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`BaeBlock`]: struct.BaeBlock.html
+    /// [`BaeBlock::from_generator`]: struct.BaeBlock.html#method.from_generator
+    /// [`Block::parameters`]: ../trait.Block.html#method.parameters
+    /// [`Block::set_parameter`]: ../trait.Block.html#method.set_parameter
+    pub fn from_parameterized_generator<T>(g: T) -> Self
+    where
+        T: 'static + Generator + Parameterized + Send,
+    {
+        let g = Arc::new(Mutex::new(g));
+
+        BaeBlockBuilder::new()
+            .shared_generator(g.clone())
+            .parameters(g)
+            .build()
+    }
+
+    /// Creates a new block from the given [`Modifier`] as with
+    /// [`BaeBlock::from_modifier`], additionally exposing the parameters of
+    /// the [`Modifier`] through [`Block::parameters`] and
+    /// [`Block::set_parameter`].
+    ///
+    /// # Parameters
+    /// * `m` - The [`Modifier`] for the [`BaeBlock`].
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`BaeBlock`]: struct.BaeBlock.html
+    /// [`BaeBlock::from_modifier`]: struct.BaeBlock.html#method.from_modifier
+    /// [`Block::parameters`]: ../trait.Block.html#method.parameters
+    /// [`Block::set_parameter`]: ../trait.Block.html#method.set_parameter
+    pub fn from_parameterized_modifier<U>(m: U) -> Self
+    where
+        U: 'static + Modifier + Parameterized + Send,
+    {
+        let m = Arc::new(Mutex::new(m));
+
+        BaeBlockBuilder::new()
+            .shared_modifier(m.clone())
+            .parameters(m)
+            .build()
+    }
+
+    /// Creates a new block from the given [`Modifier`] as with
+    /// [`BaeBlock::from_modifier`], reporting the cost estimated by the
+    /// [`Modifier`] through [`Block::cost_hint`]. Use
+    /// [`BaeBlockBuilder::cost_hint`] to combine the cost with other options,
+    /// such as [`BaeBlockBuilder::parameters`].
+    ///
+    /// # Parameters
+    /// * `m` - The [`Modifier`] for the [`BaeBlock`].
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`BaeBlock`]: struct.BaeBlock.html
+    /// [`BaeBlock::from_modifier`]: struct.BaeBlock.html#method.from_modifier
+    /// [`Block::cost_hint`]: ../trait.Block.html#method.cost_hint
+    /// [`BaeBlockBuilder::cost_hint`]: struct.BaeBlockBuilder.html#method.cost_hint
+    /// [`BaeBlockBuilder::parameters`]: struct.BaeBlockBuilder.html#method.parameters
+    pub fn from_costly_modifier<U>(m: U) -> Self
+    where
+        U: 'static + Modifier + CostHint + Send,
+    {
+        BaeBlockBuilder::new()
+            .cost_hint(m.cost_hint())
+            .modifier(m)
+            .build()
+    }
+
+    /// Creates a new control-rate block from the given LFO, whose phase is
+    /// reset by [`Block::reset_phase`], e.g. through
+    /// [`BaeSound::sync_lfos`]. Otherwise the block behaves as with
+    /// [`BaeBlock::from_generator`].
+    ///
+    /// # Parameters
+    /// * `g` - The LFO for the [`BaeBlock`].
+    ///
+    /// [`BaeBlock`]: struct.BaeBlock.html
+    /// [`BaeBlock::from_generator`]: struct.BaeBlock.html#method.from_generator
+    /// [`Block::reset_phase`]: ../trait.Block.html#method.reset_phase
+    /// [`BaeSound::sync_lfos`]: ../bae_sound/struct.BaeSound.html#method.sync_lfos
+    pub fn from_lfo<T>(g: T) -> Self
+    where
+        T: 'static + Generator + PhaseResettable + Send,
+    {
+        let g = Arc::new(Mutex::new(g));
+
+        BaeBlockBuilder::new()
+            .shared_generator(g.clone())
+            .phase_resettable(g)
+            .control_rate(true)
+            .build()
+    }
+
+    /// Creates a new block from the [`Generator`] returned by the given
+    /// closure, as with [`BaeBlock::from_generator`]. [`Block::reset`]
+    /// replaces the [`Generator`] in place with a new one from the closure,
+    /// so any clones of its smart pointer see the reset as well.
+    ///
+    /// # Parameters
+    /// * `f` - The closure constructing the [`Generator`] for the
+    /// [`BaeBlock`].
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`BaeBlock`]: struct.BaeBlock.html
+    /// [`BaeBlock::from_generator`]: struct.BaeBlock.html#method.from_generator
+    /// [`Block::reset`]: ../trait.Block.html#method.reset
+    pub fn from_generator_fn<F, T>(mut f: F) -> Self
+    where
+        F: 'static + FnMut() -> T + Send,
+        T: 'static + Generator + Send,
+    {
+        let g = Arc::new(Mutex::new(f()));
+        let target = g.clone();
+
+        BaeBlockBuilder::new()
+            .shared_generator(g)
+            .reset(move || {
+                if let Ok(mut g) = target.lock() {
+                    *g = f();
+                }
+            })
+            .build()
+    }
+
+    /// Creates a new block from the [`Modifier`] returned by the given
+    /// closure, as with [`BaeBlock::from_modifier`]. [`Block::reset`]
+    /// replaces the [`Modifier`] in place with a new one from the closure,
+    /// so any clones of its smart pointer see the reset as well.
+    ///
+    /// # Parameters
+    /// * `f` - The closure constructing the [`Modifier`] for the
+    /// [`BaeBlock`].
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`BaeBlock`]: struct.BaeBlock.html
+    /// [`BaeBlock::from_modifier`]: struct.BaeBlock.html#method.from_modifier
+    /// [`Block::reset`]: ../trait.Block.html#method.reset
+    pub fn from_modifier_fn<F, U>(mut f: F) -> Self
+    where
+        F: 'static + FnMut() -> U + Send,
+        U: 'static + Modifier + Send,
+    {
+        let m = Arc::new(Mutex::new(f()));
+        let target = m.clone();
+
+        BaeBlockBuilder::new()
+            .shared_modifier(m)
+            .reset(move || {
+                if let Ok(mut m) = target.lock() {
+                    *m = f();
+                }
+            })
+            .build()
+    }
+
+    /// Creates a new block from the given [`Generator`] as with
+    /// [`BaeBlock::from_generator`], with control inputs applied to the
+    /// [`Generator`] by the given mappings.
+    ///
+    /// Control lanes are indexed by the position of their mapping in
+    /// `controls`, so [`Block::prime_control`] with an index of `0` calls the
+    /// first mapping with the [`Generator`] and the control value, and so on.
+    /// Indices without a mapping are ignored.
+    ///
+    /// # Parameters
+    /// * `g` - The [`Generator`] for the [`BaeBlock`].
+    /// * `controls` - The mappings applying each control lane to `g`.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`BaeBlock`]: struct.BaeBlock.html
+    /// [`BaeBlock::from_generator`]: struct.BaeBlock.html#method.from_generator
+    /// [`Block::prime_control`]: ../trait.Block.html#method.prime_control
+    pub fn from_controlled_generator<T>(g: T, controls: Vec<ControlMapping<T>>) -> Self
+    where
+        T: 'static + Generator + Send,
+    {
+        let g = Arc::new(Mutex::new(g));
+        let mut builder = BaeBlockBuilder::new().shared_generator(g.clone());
+        builder.controls = Self::bind_controls(&g, controls);
+
+        builder.build()
+    }
+
+    /// Creates a new block from the given [`Modifier`] as with
+    /// [`BaeBlock::from_modifier`], with control inputs applied to the
+    /// [`Modifier`] by the given mappings. Control lanes are indexed as with
+    /// [`BaeBlock::from_controlled_generator`].
+    ///
+    /// # Parameters
+    /// * `m` - The [`Modifier`] for the [`BaeBlock`].
+    /// * `controls` - The mappings applying each control lane to `m`.
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`BaeBlock`]: struct.BaeBlock.html
+    /// [`BaeBlock::from_modifier`]: struct.BaeBlock.html#method.from_modifier
+    /// [`BaeBlock::from_controlled_generator`]: struct.BaeBlock.html#method.from_controlled_generator
+    pub fn from_controlled_modifier<U>(m: U, controls: Vec<ControlMapping<U>>) -> Self
+    where
+        U: 'static + Modifier + Send,
+    {
+        let m = Arc::new(Mutex::new(m));
+        let mut builder = BaeBlockBuilder::new().shared_modifier(m.clone());
+        builder.controls = Self::bind_controls(&m, controls);
+
+        builder.build()
     }
 
     /// Creates the default interactor which simply multiplies the two passed
     /// samples together.
     pub fn default_interactor() -> Inter {
-        Arc::new(|ge, mo| ge * mo)
+        Arc::new(Mutex::new(|ge: SampleT, mo: SampleT| ge * mo))
     }
 
     /// Creates a passthrough interactor which passes the [`Generator`] sample
@@ -141,7 +426,7 @@ impl BaeBlock {
     ///
     /// [`Generator`]: ../../generators/trait.Generator.html
     pub fn generator_passthrough() -> Inter {
-        Arc::new(|ge, _| ge)
+        Arc::new(Mutex::new(|ge: SampleT, _: SampleT| ge))
     }
 
     /// Creates a passthrough interactor which passes the [`Modifier`] sample
@@ -149,7 +434,69 @@ impl BaeBlock {
     ///
     /// [`Modifier`]: ../../modifiers/trait.Modifier.html
     pub fn modifier_passthrough() -> Inter {
-        Arc::new(|_, mo| mo)
+        Arc::new(Mutex::new(|_: SampleT, mo: SampleT| mo))
+    }
+
+    /// Creates an interactor which sums the two passed samples.
+    pub fn sum_interactor() -> Inter {
+        Arc::new(Mutex::new(|ge: SampleT, mo: SampleT| ge + mo))
+    }
+
+    /// Creates an interactor which passes through the smaller of the two
+    /// passed samples.
+    pub fn min_interactor() -> Inter {
+        Arc::new(Mutex::new(|ge: SampleT, mo: SampleT| ge.min(mo)))
+    }
+
+    /// Creates an interactor which passes through the larger of the two passed
+    /// samples.
+    pub fn max_interactor() -> Inter {
+        Arc::new(Mutex::new(|ge: SampleT, mo: SampleT| ge.max(mo)))
+    }
+
+    /// Creates a ring modulation interactor, multiplying the [`Generator`]
+    /// sample by the [`Modifier`] sample. This returns
+    /// [`BaeBlock::default_interactor`], and is named for when one of the two
+    /// is used as a modulator, e.g. an oscillator amplitude modulating the
+    /// [`Modifier`] output.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`BaeBlock::default_interactor`]: struct.BaeBlock.html#method.default_interactor
+    pub fn ring_mod_interactor() -> Inter {
+        Self::default_interactor()
+    }
+
+    /// Creates an interactor which multiplies the two passed samples, as with
+    /// [`BaeBlock::default_interactor`], then hard-clips the result to
+    /// `[-threshold, threshold]`. Negative thresholds are treated as their
+    /// magnitude.
+    ///
+    /// [`BaeBlock::default_interactor`]: struct.BaeBlock.html#method.default_interactor
+    pub fn clip_interactor(threshold: SampleT) -> Inter {
+        let threshold = threshold.abs();
+
+        Arc::new(Mutex::new(move |ge: SampleT, mo: SampleT| {
+            (ge * mo).max(-threshold).min(threshold)
+        }))
+    }
+
+    /// Creates an interactor which crossfades from the [`Generator`] sample to
+    /// the [`Modifier`] sample by the given mix, clamped to `[0, 1]`. A mix of
+    /// `0` passes the [`Generator`] sample through, and a mix of `1` the
+    /// [`Modifier`] sample.
+    ///
+    /// The mix is captured when the interactor is created and can't be
+    /// changed afterwards; create a new interactor to change it.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn crossfade_interactor(mix: SampleT) -> Inter {
+        let mix = mix.max(0.0).min(1.0);
+
+        Arc::new(Mutex::new(move |ge: SampleT, mo: SampleT| {
+            ge * (1.0 - mix) + mo * mix
+        }))
     }
 
     /// Returns a reference to the [`Generator`] wrapped in a smart pointer.
@@ -179,6 +526,160 @@ impl BaeBlock {
     pub fn get_m_mut(&mut self) -> &mut ModifierSP {
         &mut self.m
     }
+
+    /// Sets the value returned by [`Block::cost_hint`]. Blocks default to a
+    /// cost of `1`, or to the estimate of a [`CostHint`] object given to
+    /// [`from_costly_modifier`]; other expensive [`Generator`]s and
+    /// [`Modifier`]s should be given a proportionally higher cost.
+    ///
+    /// [`Block::cost_hint`]: ../trait.Block.html#method.cost_hint
+    /// [`CostHint`]: ../trait.CostHint.html
+    /// [`from_costly_modifier`]: struct.BaeBlock.html#method.from_costly_modifier
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn set_cost_hint(&mut self, cost: u32) {
+        self.cost_hint = cost;
+    }
+
+    /// Sets the value returned by [`Block::is_control_rate`]. Mark blocks
+    /// producing control signals, such as LFOs and envelopes, so they can be
+    /// updated below the audio rate. Blocks default to audio rate.
+    ///
+    /// [`Block::is_control_rate`]: ../trait.Block.html#method.is_control_rate
+    pub fn set_control_rate(&mut self, control_rate: bool) {
+        self.control_rate = control_rate;
+    }
+
+    /// Returns whether the block is bypassed.
+    pub fn is_bypassed(&self) -> bool {
+        self.bypass
+    }
+
+    /// Sets whether the block is bypassed, e.g. to A/B test an effect without
+    /// removing it. A bypassed block skips its [`Modifier`] and [`Inter`]
+    /// entirely. Blocks built without a [`Generator`], such as those created
+    /// with [`BaeBlock::from_modifier`], pass their input through, even after
+    /// [`set_inter`], while all other blocks output their [`Generator`]
+    /// sample.
+    ///
+    /// The [`Generator`] keeps being processed while bypassed, so its phase
+    /// continues uninterrupted when the bypass is lifted. The [`Modifier`] is
+    /// not processed, so any state it holds, e.g. a filter's history, is
+    /// left from before the bypass.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`Inter`]: type.Inter.html
+    /// [`BaeBlock::from_modifier`]: struct.BaeBlock.html#method.from_modifier
+    /// [`set_inter`]: struct.BaeBlock.html#method.set_inter
+    pub fn set_bypass(&mut self, b: bool) {
+        self.bypass = b;
+    }
+
+    /// Returns a reference to the [`Inter`] combining the [`Generator`] and
+    /// [`Modifier`] samples.
+    ///
+    /// [`Inter`]: type.Inter.html
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn get_inter(&self) -> &Inter {
+        &self.i
+    }
+
+    /// Sets the [`Inter`] combining the [`Generator`] and [`Modifier`]
+    /// samples. As the given closure is opaque, the block no longer reports an
+    /// [`InterKind`] afterwards.
+    ///
+    /// [`Inter`]: type.Inter.html
+    /// [`InterKind`]: enum.InterKind.html
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn set_inter(&mut self, i: Inter) {
+        self.i = i;
+        self.inter_kind = None;
+    }
+
+    /// Returns the [`InterKind`] the block's interactor was constructed from,
+    /// or `None` if the interactor is a user-supplied closure.
+    ///
+    /// [`InterKind`]: enum.InterKind.html
+    pub fn get_inter_kind(&self) -> Option<InterKind> {
+        self.inter_kind
+    }
+
+    /// Sets the interactor to the closure corresponding to the given
+    /// [`InterKind`], remembering the kind for later queries.
+    ///
+    /// [`InterKind`]: enum.InterKind.html
+    pub fn set_inter_kind(&mut self, kind: InterKind) {
+        self.i = kind.to_inter();
+        self.inter_kind = Some(kind);
+    }
+
+    /// Returns the metadata value stored under the given key.
+    pub fn get_meta(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(|v| v.as_str())
+    }
+
+    /// Stores a metadata value, such as a display name or color, under the
+    /// given key. Metadata has no effect on processing.
+    pub fn set_meta(&mut self, key: &str, value: &str) {
+        self.metadata.insert(key.to_owned(), value.to_owned());
+    }
+
+    /// Creates a block from its already wrapped parts, with every other
+    /// setting at its default. All constructors go through
+    /// [`BaeBlockBuilder::build`] and from there through here, so each field
+    /// only needs a default in one place.
+    ///
+    /// [`BaeBlockBuilder::build`]: struct.BaeBlockBuilder.html#method.build
+    fn from_parts(
+        g: GeneratorSP,
+        m: ModifierSP,
+        i: Inter,
+        inter_kind: Option<InterKind>,
+        type_name: &'static str,
+    ) -> Self {
+        BaeBlock {
+            g,
+            m,
+            i,
+            inter_kind,
+            has_generator: true,
+            type_name,
+            cost_hint: 1,
+            control_rate: false,
+            bypass: false,
+            metadata: HashMap::new(),
+            params: None,
+            phase: None,
+            reset: None,
+            controls: Vec::new(),
+            input: SampleT::default(),
+        }
+    }
+
+    /// Binds each control mapping to the given object, so it can be called
+    /// with just the control value.
+    fn bind_controls<T>(
+        target: &Arc<Mutex<T>>,
+        controls: Vec<ControlMapping<T>>,
+    ) -> Vec<Box<dyn FnMut(SampleT) + Send>>
+    where
+        T: 'static + Send,
+    {
+        controls
+            .into_iter()
+            .map(|mut f| {
+                let target = target.clone();
+                Box::new(move |x: SampleT| {
+                    if let Ok(mut t) = target.lock() {
+                        f(&mut *t, x);
+                    }
+                }) as Box<dyn FnMut(SampleT) + Send>
+            })
+            .collect()
+    }
 }
 
 impl Block for BaeBlock {
@@ -187,20 +688,402 @@ impl Block for BaeBlock {
     }
 
     fn process(&mut self) -> SampleT {
-        let y = Inter::get_mut(&mut self.i).unwrap()(
-            GeneratorSP::get_mut(&mut self.g).unwrap().process(),
-            ModifierSP::get_mut(&mut self.m)
-                .unwrap()
-                .process(self.input),
-        );
+        let input = self.input;
+
+        let g = self.g.lock().map(|mut g| g.process()).unwrap_or_default();
+
+        if self.bypass {
+            self.input = SampleT::default();
+
+            return if self.has_generator { g } else { input };
+        }
+
+        let m = self
+            .m
+            .lock()
+            .map(|mut m| m.process(input))
+            .unwrap_or_default();
+        let y = self.i.lock().map(|mut i| (*i)(g, m)).unwrap_or_default();
 
         self.input = SampleT::default();
 
         y
     }
+
+    /// Applies the control value through the mapping at the given index for
+    /// blocks created with [`BaeBlock::from_controlled_generator`],
+    /// [`BaeBlock::from_controlled_modifier`], or
+    /// [`BaeBlockBuilder::control`], and does nothing otherwise.
+    ///
+    /// [`BaeBlock::from_controlled_generator`]: struct.BaeBlock.html#method.from_controlled_generator
+    /// [`BaeBlock::from_controlled_modifier`]: struct.BaeBlock.html#method.from_controlled_modifier
+    /// [`BaeBlockBuilder::control`]: struct.BaeBlockBuilder.html#method.control
+    fn prime_control(&mut self, idx: usize, value: SampleT) {
+        if let Some(f) = self.controls.get_mut(idx) {
+            f(value);
+        }
+    }
+
+    /// Locks the [`Generator`], [`Modifier`], and [`Inter`] once for the whole
+    /// buffer rather than once per sample, producing the same output as
+    /// repeated calls to [`process`].
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`Inter`]: type.Inter.html
+    /// [`process`]: ../trait.Block.html#tymethod.process
+    fn process_buffer(&mut self, out: &mut [SampleT]) {
+        let mut input = self.input;
+        self.input = SampleT::default();
+
+        let mut g = self.g.lock().ok();
+        let mut next_g = || g.as_mut().map(|g| g.process()).unwrap_or_default();
+
+        if self.bypass {
+            let has_generator = self.has_generator;
+
+            for y in out.iter_mut() {
+                let ge = next_g();
+                *y = if has_generator { ge } else { input };
+                input = SampleT::default();
+            }
+            return;
+        }
+
+        let mut m = self.m.lock().ok();
+        let mut i = self.i.lock().ok();
+
+        for y in out.iter_mut() {
+            let ge = next_g();
+            let mo = m.as_mut().map(|m| m.process(input)).unwrap_or_default();
+            *y = i.as_mut().map(|i| (**i)(ge, mo)).unwrap_or_default();
+            input = SampleT::default();
+        }
+    }
+
+    /// Returns the type name of the wrapped [`Generator`], or of the wrapped
+    /// [`Modifier`] for blocks created with [`BaeBlock::from_modifier`].
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`BaeBlock::from_modifier`]: struct.BaeBlock.html#method.from_modifier
+    fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    fn cost_hint(&self) -> u32 {
+        self.cost_hint
+    }
+
+    fn is_control_rate(&self) -> bool {
+        self.control_rate
+    }
+
+    /// Returns the parameters of the wrapped object for blocks created with
+    /// [`BaeBlock::from_parameterized_generator`],
+    /// [`BaeBlock::from_parameterized_modifier`], or
+    /// [`BaeBlockBuilder::parameters`], and no parameters otherwise.
+    ///
+    /// [`BaeBlock::from_parameterized_generator`]: struct.BaeBlock.html#method.from_parameterized_generator
+    /// [`BaeBlock::from_parameterized_modifier`]: struct.BaeBlock.html#method.from_parameterized_modifier
+    /// [`BaeBlockBuilder::parameters`]: struct.BaeBlockBuilder.html#method.parameters
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        self.params
+            .as_ref()
+            .and_then(|p| p.lock().ok())
+            .map(|p| p.parameters())
+            .unwrap_or_default()
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        self.params
+            .as_ref()
+            .and_then(|p| p.lock().ok())
+            .map(|mut p| p.set_parameter(name, value))
+            .unwrap_or(false)
+    }
+
+    fn reset_phase(&mut self) {
+        if let Some(Ok(mut g)) = self.phase.as_ref().map(|g| g.lock()) {
+            g.reset_phase();
+        }
+    }
+
+    /// Clears any primed input. For blocks created with
+    /// [`BaeBlock::from_generator_fn`] or [`BaeBlock::from_modifier_fn`], the
+    /// wrapped object is also replaced with a newly constructed one, while
+    /// blocks created with [`BaeBlock::from_lfo`] have their phase reset.
+    /// Blocks built with [`BaeBlockBuilder::reset`] and
+    /// [`BaeBlockBuilder::phase_resettable`] do both, in that order. The state
+    /// of other wrapped objects can't be reset, as [`Generator`] and
+    /// [`Modifier`] provide no way to do so.
+    ///
+    /// [`BaeBlock::from_generator_fn`]: struct.BaeBlock.html#method.from_generator_fn
+    /// [`BaeBlock::from_modifier_fn`]: struct.BaeBlock.html#method.from_modifier_fn
+    /// [`BaeBlock::from_lfo`]: struct.BaeBlock.html#method.from_lfo
+    /// [`BaeBlockBuilder::reset`]: struct.BaeBlockBuilder.html#method.reset
+    /// [`BaeBlockBuilder::phase_resettable`]: struct.BaeBlockBuilder.html#method.phase_resettable
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    fn reset(&mut self) {
+        self.input = SampleT::default();
+
+        if let Some(Ok(mut f)) = self.reset.as_ref().map(|f| f.lock()) {
+            (*f)();
+        }
+        self.reset_phase();
+    }
+}
+
+impl FrameBlock for BaeBlock {
+    type Frame = SampleT;
+
+    fn prime_frame(&mut self, x: SampleT) {
+        self.prime_input(x);
+    }
+
+    fn process_frame(&mut self) -> SampleT {
+        self.process()
+    }
 }
 
 /// Alias for a [`BaeBlock`] object wrapped in a smart pointer.
 ///
 /// [`BaeBlock`]: struct.BaeBlock.html
-pub type BaeBlockSP = Arc<BaeBlock>;
+pub type BaeBlockSP = Arc<Mutex<BaeBlock>>;
+
+/// Builder for a [`BaeBlock`], which wraps the [`Generator`], [`Modifier`],
+/// and interactor closure in their smart pointers internally.
+///
+/// Any part that isn't given falls back to the defaults of
+/// [`BaeBlock::from_generator`] and [`BaeBlock::from_modifier`]: a missing
+/// [`Generator`] is replaced with [`Zero`], and a missing [`Modifier`] with
+/// [`Passthrough`]. Without an interactor, a block with only a [`Generator`]
+/// passes it through, a block with only a [`Modifier`] passes that through,
+/// and a block with both multiplies them as with
+/// [`BaeBlock::default_interactor`].
+///
+/// Every `from_*` constructor of [`BaeBlock`] is a shorthand for a
+/// combination of the builder's options, which can also be combined freely,
+/// e.g. for a [`Modifier`] that is both parameterized and resettable.
+///
+/// [`BaeBlock`]: struct.BaeBlock.html
+/// [`BaeBlock::from_generator`]: struct.BaeBlock.html#method.from_generator
+/// [`BaeBlock::from_modifier`]: struct.BaeBlock.html#method.from_modifier
+/// [`BaeBlock::default_interactor`]: struct.BaeBlock.html#method.default_interactor
+/// [`Generator`]: ../../generators/trait.Generator.html
+/// [`Modifier`]: ../../modifiers/trait.Modifier.html
+/// [`Zero`]: ../../generators/zero/struct.Zero.html
+/// [`Passthrough`]: ../../modifiers/passthrough/struct.Passthrough.html
+#[derive(Default)]
+pub struct BaeBlockBuilder {
+    g: Option<(GeneratorSP, &'static str)>,
+    m: Option<(ModifierSP, &'static str)>,
+    i: Option<Inter>,
+    params: Option<ParameterizedSP>,
+    phase: Option<Arc<Mutex<dyn PhaseResettable + Send>>>,
+    reset: Option<Arc<Mutex<dyn FnMut() + Send>>>,
+    control_rate: bool,
+    cost_hint: Option<u32>,
+    controls: Vec<Box<dyn FnMut(SampleT) + Send>>,
+}
+
+impl BaeBlockBuilder {
+    /// Creates a new builder with no [`Generator`], [`Modifier`], or
+    /// interactor set.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the [`Generator`] of the block.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    pub fn generator<T>(self, g: T) -> Self
+    where
+        T: 'static + Generator + Send,
+    {
+        self.shared_generator(Arc::new(Mutex::new(g)))
+    }
+
+    /// Sets the [`Generator`] of the block to an object that is already
+    /// wrapped in a smart pointer, so the same object can also be given to
+    /// [`parameters`] or [`phase_resettable`], or captured by a [`reset`]
+    /// closure.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`parameters`]: struct.BaeBlockBuilder.html#method.parameters
+    /// [`phase_resettable`]: struct.BaeBlockBuilder.html#method.phase_resettable
+    /// [`reset`]: struct.BaeBlockBuilder.html#method.reset
+    pub fn shared_generator<T>(mut self, g: Arc<Mutex<T>>) -> Self
+    where
+        T: 'static + Generator + Send,
+    {
+        self.g = Some((g, std::any::type_name::<T>()));
+        self
+    }
+
+    /// Sets the [`Modifier`] of the block.
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn modifier<U>(self, m: U) -> Self
+    where
+        U: 'static + Modifier + Send,
+    {
+        self.shared_modifier(Arc::new(Mutex::new(m)))
+    }
+
+    /// Sets the [`Modifier`] of the block to an object that is already
+    /// wrapped in a smart pointer, as with [`shared_generator`].
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`shared_generator`]: struct.BaeBlockBuilder.html#method.shared_generator
+    pub fn shared_modifier<U>(mut self, m: Arc<Mutex<U>>) -> Self
+    where
+        U: 'static + Modifier + Send,
+    {
+        self.m = Some((m, std::any::type_name::<U>()));
+        self
+    }
+
+    /// Sets the closure combining the [`Generator`] and [`Modifier`] samples,
+    /// called with the [`Generator`] sample first.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn interactor<F>(mut self, i: F) -> Self
+    where
+        F: 'static + FnMut(SampleT, SampleT) -> SampleT + Send,
+    {
+        self.i = Some(Arc::new(Mutex::new(i)));
+        self
+    }
+
+    /// Sets the object whose parameters the block exposes through
+    /// [`Block::parameters`] and [`Block::set_parameter`], typically the
+    /// [`Generator`] or [`Modifier`] given to [`shared_generator`] or
+    /// [`shared_modifier`].
+    ///
+    /// [`Block::parameters`]: ../trait.Block.html#method.parameters
+    /// [`Block::set_parameter`]: ../trait.Block.html#method.set_parameter
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`shared_generator`]: struct.BaeBlockBuilder.html#method.shared_generator
+    /// [`shared_modifier`]: struct.BaeBlockBuilder.html#method.shared_modifier
+    pub fn parameters(mut self, p: ParameterizedSP) -> Self {
+        self.params = Some(p);
+        self
+    }
+
+    /// Sets the oscillator whose phase is reset by [`Block::reset_phase`] and
+    /// [`Block::reset`], e.g. an LFO given to [`shared_generator`].
+    ///
+    /// [`Block::reset_phase`]: ../trait.Block.html#method.reset_phase
+    /// [`Block::reset`]: ../trait.Block.html#method.reset
+    /// [`shared_generator`]: struct.BaeBlockBuilder.html#method.shared_generator
+    pub fn phase_resettable(mut self, p: Arc<Mutex<dyn PhaseResettable + Send>>) -> Self {
+        self.phase = Some(p);
+        self
+    }
+
+    /// Sets the closure called by [`Block::reset`] to reset the state of the
+    /// wrapped objects, e.g. by replacing them with newly constructed ones.
+    ///
+    /// [`Block::reset`]: ../trait.Block.html#method.reset
+    pub fn reset<F>(mut self, f: F) -> Self
+    where
+        F: 'static + FnMut() + Send,
+    {
+        self.reset = Some(Arc::new(Mutex::new(f)));
+        self
+    }
+
+    /// Sets the value returned by [`Block::is_control_rate`]. See
+    /// [`BaeBlock::set_control_rate`].
+    ///
+    /// [`Block::is_control_rate`]: ../trait.Block.html#method.is_control_rate
+    /// [`BaeBlock::set_control_rate`]: struct.BaeBlock.html#method.set_control_rate
+    pub fn control_rate(mut self, control_rate: bool) -> Self {
+        self.control_rate = control_rate;
+        self
+    }
+
+    /// Sets the value returned by [`Block::cost_hint`], typically the
+    /// estimate of a [`CostHint`] object. See [`BaeBlock::set_cost_hint`].
+    ///
+    /// [`Block::cost_hint`]: ../trait.Block.html#method.cost_hint
+    /// [`CostHint`]: ../trait.CostHint.html
+    /// [`BaeBlock::set_cost_hint`]: struct.BaeBlock.html#method.set_cost_hint
+    pub fn cost_hint(mut self, cost: u32) -> Self {
+        self.cost_hint = Some(cost);
+        self
+    }
+
+    /// Adds a control lane applying its values with the given closure,
+    /// typically by calling a parameter setter on an object given to
+    /// [`shared_generator`] or [`shared_modifier`]. Lanes are indexed in the
+    /// order they are added, as with [`BaeBlock::from_controlled_generator`].
+    ///
+    /// [`shared_generator`]: struct.BaeBlockBuilder.html#method.shared_generator
+    /// [`shared_modifier`]: struct.BaeBlockBuilder.html#method.shared_modifier
+    /// [`BaeBlock::from_controlled_generator`]: struct.BaeBlock.html#method.from_controlled_generator
+    pub fn control<F>(mut self, f: F) -> Self
+    where
+        F: 'static + FnMut(SampleT) + Send,
+    {
+        self.controls.push(Box::new(f));
+        self
+    }
+
+    /// Builds the [`BaeBlock`]. The block reports the type name of its
+    /// [`Generator`] if one was given, and of its [`Modifier`] otherwise.
+    ///
+    /// [`BaeBlock`]: struct.BaeBlock.html
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn build(self) -> BaeBlock {
+        let (i, inter_kind) = match (self.i, &self.g, &self.m) {
+            (Some(i), _, _) => (i, None),
+            (None, Some(_), Some(_)) => (BaeBlock::default_interactor(), Some(InterKind::Multiply)),
+            (None, None, Some(_)) => (
+                BaeBlock::modifier_passthrough(),
+                Some(InterKind::ModifierOnly),
+            ),
+            (None, _, None) => (
+                BaeBlock::generator_passthrough(),
+                Some(InterKind::GeneratorOnly),
+            ),
+        };
+
+        let has_generator = self.g.is_some();
+        let type_name = match (&self.g, &self.m) {
+            (Some((_, name)), _) | (None, Some((_, name))) => *name,
+            (None, None) => std::any::type_name::<Zero>(),
+        };
+
+        let mut block = BaeBlock::from_parts(
+            self.g
+                .map(|(g, _)| g)
+                .unwrap_or_else(|| Arc::new(Mutex::new(Zero::new()))),
+            self.m
+                .map(|(m, _)| m)
+                .unwrap_or_else(|| Arc::new(Mutex::new(Passthrough::new()))),
+            i,
+            inter_kind,
+            type_name,
+        );
+        block.has_generator = has_generator;
+        block.control_rate = self.control_rate;
+        if let Some(cost) = self.cost_hint {
+            block.cost_hint = cost;
+        }
+        block.params = self.params;
+        block.phase = self.phase;
+        block.reset = self.reset;
+        block.controls = self.controls;
+
+        block
+    }
+}
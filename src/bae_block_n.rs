@@ -0,0 +1,194 @@
+//! # BaeBlockN
+//!
+//! A multichannel counterpart to [`BaeBlock`], producing a frame of `N`
+//! samples per call, e.g. for stereo panning or spatialization.
+//!
+//! [`BaeBlock`]: ../bae_block/struct.BaeBlock.html
+
+use super::*;
+
+use bae_gen::*;
+use bae_mod::*;
+use std::sync::{Arc, Mutex};
+
+/// Type defining the closure that combines the output sample of the
+/// [`Generator`] of a [`BaeBlockN`] with the frame of outputs of its
+/// [`Modifier`]s into the output frame. This is the multichannel counterpart
+/// to [`InterBase`].
+///
+/// [`Generator`]: ../../generators/trait.Generator.html
+/// [`Modifier`]: ../../modifiers/trait.Modifier.html
+/// [`BaeBlockN`]: struct.BaeBlockN.html
+/// [`InterBase`]: ../bae_block/type.InterBase.html
+pub type InterBaseN<const N: usize> = dyn FnMut(SampleT, [SampleT; N]) -> [SampleT; N] + Send;
+
+/// Reference-counted wrapper for the closure [`InterBaseN`]
+///
+/// [`InterBaseN`]: type.InterBaseN.html
+pub type InterN<const N: usize> = Arc<Mutex<InterBaseN<N>>>;
+
+/// Struct implementing a [`BaeBlock`] with `N` output channels. The block
+/// runs a single [`Generator`] and one [`Modifier`] per channel, each fed the
+/// corresponding channel of the input frame, and combines their outputs with
+/// an [`InterN`].
+///
+/// A `BaeBlockN<1>` constructed from the same objects and an equivalent
+/// interactor produces the same output as a [`BaeBlock`].
+///
+/// [`BaeBlock`]: ../bae_block/struct.BaeBlock.html
+/// [`Generator`]: ../../generators/trait.Generator.html
+/// [`Modifier`]: ../../modifiers/trait.Modifier.html
+/// [`InterN`]: type.InterN.html
+pub struct BaeBlockN<const N: usize> {
+    g: GeneratorSP,
+    m: Vec<ModifierSP>,
+    i: InterN<N>,
+    input: [SampleT; N],
+}
+
+impl<const N: usize> BaeBlockN<N> {
+    /// Creates a new block from the given [`Generator`], per-channel
+    /// [`Modifier`]s, and [`InterN`].
+    ///
+    /// # Parameters
+    ///
+    /// * `g` - The [`Generator`] for the block.
+    /// * `m` - The [`Modifier`] processing each channel of the input frame.
+    /// * `i` - The interactor combining the output of `g` with the frame of
+    /// outputs of `m`.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`InterN`]: type.InterN.html
+    pub fn new<T>(g: T, m: [ModifierSP; N], i: InterN<N>) -> Self
+    where
+        T: 'static + Generator + Send,
+    {
+        BaeBlockN {
+            g: Arc::new(Mutex::new(g)),
+            m: m.iter().cloned().collect(),
+            i,
+            input: [SampleT::default(); N],
+        }
+    }
+
+    /// Creates a new block from the given [`Generator`], whose output is
+    /// copied to every channel of the frame. Use [`set_inter`] to weight each
+    /// channel, e.g. for panning.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`set_inter`]: struct.BaeBlockN.html#method.set_inter
+    pub fn from_generator<T>(g: T) -> Self
+    where
+        T: 'static + Generator + Send,
+    {
+        BaeBlockN {
+            g: Arc::new(Mutex::new(g)),
+            m: Self::passthroughs(),
+            i: Self::generator_passthrough(),
+            input: [SampleT::default(); N],
+        }
+    }
+
+    /// Creates a new block from the given per-channel [`Modifier`]s, whose
+    /// output frame is passed through.
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn from_modifiers(m: [ModifierSP; N]) -> Self {
+        BaeBlockN {
+            g: Arc::new(Mutex::new(Zero::new())),
+            m: m.iter().cloned().collect(),
+            i: Self::modifier_passthrough(),
+            input: [SampleT::default(); N],
+        }
+    }
+
+    /// Creates the default interactor which multiplies each channel of the
+    /// [`Modifier`] frame by the [`Generator`] sample.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn default_interactor() -> InterN<N> {
+        Arc::new(Mutex::new(|ge: SampleT, mut mo: [SampleT; N]| {
+            for y in &mut mo {
+                *y *= ge;
+            }
+            mo
+        }))
+    }
+
+    /// Creates a passthrough interactor which copies the [`Generator`] sample
+    /// to every channel.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    pub fn generator_passthrough() -> InterN<N> {
+        Arc::new(Mutex::new(|ge: SampleT, _: [SampleT; N]| [ge; N]))
+    }
+
+    /// Creates a passthrough interactor which passes the [`Modifier`] frame
+    /// through.
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn modifier_passthrough() -> InterN<N> {
+        Arc::new(Mutex::new(|_: SampleT, mo: [SampleT; N]| mo))
+    }
+
+    /// Returns a reference to the [`Generator`] wrapped in a smart pointer.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    pub fn get_g(&self) -> &GeneratorSP {
+        &self.g
+    }
+
+    /// Returns the [`Modifier`] of each channel, wrapped in smart pointers.
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn get_m(&self) -> &[ModifierSP] {
+        &self.m
+    }
+
+    /// Sets the [`InterN`] combining the [`Generator`] and [`Modifier`]
+    /// outputs.
+    ///
+    /// [`InterN`]: type.InterN.html
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn set_inter(&mut self, i: InterN<N>) {
+        self.i = i;
+    }
+
+    fn passthroughs() -> Vec<ModifierSP> {
+        (0..N)
+            .map(|_| Arc::new(Mutex::new(Passthrough::new())) as ModifierSP)
+            .collect()
+    }
+}
+
+impl<const N: usize> FrameBlock for BaeBlockN<N> {
+    type Frame = [SampleT; N];
+
+    fn prime_frame(&mut self, x: [SampleT; N]) {
+        for (input, x) in self.input.iter_mut().zip(x.iter()) {
+            *input += x;
+        }
+    }
+
+    fn process_frame(&mut self) -> [SampleT; N] {
+        let g = self.g.lock().map(|mut g| g.process()).unwrap_or_default();
+
+        let mut frame = [SampleT::default(); N];
+        for ((y, m), x) in frame.iter_mut().zip(&self.m).zip(self.input.iter()) {
+            *y = m.lock().map(|mut m| m.process(*x)).unwrap_or_default();
+        }
+
+        let y = self
+            .i
+            .lock()
+            .map(|mut i| (*i)(g, frame))
+            .unwrap_or([SampleT::default(); N]);
+
+        self.input = [SampleT::default(); N];
+
+        y
+    }
+}
@@ -10,6 +10,11 @@
 
 use super::*;
 
+use bae_mod::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
 /// Struct implementing the ability to run a single [`Generator`] through a
 /// given list of [`Modifier`]s operated in series. This allows for simple and
 /// fast processing of the structure's elements while still allowing for a wide
@@ -21,8 +26,23 @@ use super::*;
 pub struct BaeSound {
     generator: BlockSP,
     modifier_list: Vec<BlockSP>,
+    parallel_list: Vec<(BlockSP, SampleT)>,
+    parallel_position: ParallelPosition,
+    stereo_list: Vec<FrameBlockSP<2>>,
     input_gain: SampleT,
     output_gain: SampleT,
+    input_envelope: Option<GainEnvelope>,
+    output_envelope: Option<GainEnvelope>,
+    mod_matrix: ModMatrix,
+    control_rate_divisor: usize,
+    control_counter: usize,
+    control_values: Vec<SampleT>,
+    metadata: HashMap<String, String>,
+    lfo_sync_on_trigger: bool,
+    dc_offset: SampleT,
+    mute_ramp: MuteRamp,
+    duration: Option<usize>,
+    elapsed: usize,
     id: Option<usize>,
     is_muted: bool,
     is_paused: bool,
@@ -42,22 +62,67 @@ impl BaeSound {
         BaeSound {
             generator,
             modifier_list: Vec::new(),
+            parallel_list: Vec::new(),
+            parallel_position: ParallelPosition::BeforeChain,
+            stereo_list: Vec::new(),
             input_gain: input_gain as SampleT,
             output_gain: output_gain as SampleT,
+            input_envelope: None,
+            output_envelope: None,
+            mod_matrix: ModMatrix::new(),
+            control_rate_divisor: 1,
+            control_counter: 0,
+            control_values: Vec::new(),
+            metadata: HashMap::new(),
+            lfo_sync_on_trigger: false,
+            dc_offset: 0.0,
+            mute_ramp: MuteRamp::new(),
+            duration: None,
+            elapsed: 0,
             id: None,
             is_muted: false,
             is_paused: false,
         }
     }
 
+    /// Constructs a new [`BaeSound`] object from the given [`Patch`], with the
+    /// patch's generator and modifiers created for the given sample rate.
+    /// The mute ramp is set to [`DEFAULT_RAMP`] at the given sample rate, and
+    /// everything else not described by the [`Patch`] is left at the defaults
+    /// of [`new`].
+    ///
+    /// [`BaeSound`]: struct.BaeSound.html
+    /// [`Patch`]: ../patch/struct.Patch.html
+    /// [`DEFAULT_RAMP`]: ../constant.DEFAULT_RAMP.html
+    /// [`new`]: struct.BaeSound.html#method.new
+    #[cfg(feature = "serde")]
+    pub fn from_patch(patch: &Patch, sample_rate: MathT) -> Self {
+        let mut sound = BaeSound::new(
+            patch.input_gain,
+            patch.output_gain,
+            patch.generator.to_block(sample_rate),
+        );
+
+        sound.extend_modifiers(
+            patch
+                .modifiers
+                .iter()
+                .map(|m| m.to_block(sample_rate))
+                .collect(),
+        );
+        for (key, value) in &patch.metadata {
+            sound.set_meta(key, value);
+        }
+        sound.set_mute_ramp(DEFAULT_RAMP, sample_rate);
+
+        sound
+    }
+
     /// Adds a single modifier to the internal [`Vec`] of [`Modifier`]s.
     ///
     /// [`Vec`]: https://doc.rust-lang.org/std/vec/struct.Vec.html
     /// [`Modifier`]: ../../modifiers/trait.Modifier.html
-    pub fn add_modifier<M>(&mut self, m: BlockSP)
-    where
-        M: 'static + Clone,
-    {
+    pub fn add_modifier(&mut self, m: BlockSP) {
         self.modifier_list.push(m);
     }
 
@@ -69,6 +134,192 @@ impl BaeSound {
         self.modifier_list.extend(m_list);
     }
 
+    /// Inserts a modifier into the internal [`Vec`] of [`Modifier`]s at the
+    /// given index, shifting the following modifiers back. An index past the
+    /// end of the chain appends the modifier. Routes in the [`ModMatrix`] keep
+    /// modulating the same blocks.
+    ///
+    /// [`Vec`]: https://doc.rust-lang.org/std/vec/struct.Vec.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`ModMatrix`]: ../mod_matrix/struct.ModMatrix.html
+    pub fn insert_modifier(&mut self, index: usize, m: BlockSP) {
+        let index = index.min(self.modifier_list.len());
+
+        self.modifier_list.insert(index, m);
+        self.mod_matrix.insert_block(index + 1);
+        if index < self.control_values.len() {
+            self.control_values.insert(index + 1, Default::default());
+        }
+    }
+
+    /// Removes and returns the modifier at the given index, or returns `None`
+    /// if there is no modifier at that index.
+    ///
+    /// Routes in the [`ModMatrix`] to the removed modifier's parameters are
+    /// removed, shifting the handles of later routes down, while the others
+    /// keep modulating the same blocks.
+    ///
+    /// [`ModMatrix`]: ../mod_matrix/struct.ModMatrix.html
+    pub fn remove_modifier(&mut self, index: usize) -> Option<BlockSP> {
+        if index >= self.modifier_list.len() {
+            return None;
+        }
+
+        self.mod_matrix.remove_blocks(index + 1..index + 2);
+
+        if index + 1 < self.control_values.len() {
+            self.control_values.remove(index + 1);
+        }
+        Some(self.modifier_list.remove(index))
+    }
+
+    /// Returns the number of modifiers in the chain.
+    pub fn modifier_count(&self) -> usize {
+        self.modifier_list.len()
+    }
+
+    /// Adds a modifier to the parallel stage of the sound, mixed in at the
+    /// given wet level.
+    ///
+    /// Every parallel modifier is fed the same dry signal, and their outputs,
+    /// scaled by their wet levels, are summed with the dry signal. By default
+    /// the parallel stage runs between the generator and the series chain of
+    /// [`Modifier`]s, so the dry signal is the output of the generator and the
+    /// mix continues through the series chain. Use [`set_parallel_position`]
+    /// to run it after the series chain instead, e.g. for a reverb send.
+    /// Parallel modifiers are processed every sample, regardless of
+    /// [`Block::is_control_rate`].
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`set_parallel_position`]: struct.BaeSound.html#method.set_parallel_position
+    /// [`Block::is_control_rate`]: ../trait.Block.html#method.is_control_rate
+    pub fn add_parallel_modifier(&mut self, m: BlockSP, wet: MathT) {
+        self.parallel_list.push((m, wet as SampleT));
+    }
+
+    /// Returns the number of modifiers in the parallel stage.
+    pub fn parallel_modifier_count(&self) -> usize {
+        self.parallel_list.len()
+    }
+
+    /// Returns where the parallel stage runs relative to the series chain.
+    pub fn get_parallel_position(&self) -> ParallelPosition {
+        self.parallel_position
+    }
+
+    /// Sets where the parallel stage runs relative to the series chain of
+    /// [`Modifier`]s. Defaults to [`ParallelPosition::BeforeChain`].
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`ParallelPosition::BeforeChain`]: enum.ParallelPosition.html#variant.BeforeChain
+    pub fn set_parallel_position(&mut self, position: ParallelPosition) {
+        self.parallel_position = position;
+    }
+
+    /// Adds a stereo [`FrameBlock`], such as a panning [`BaeBlockN`], to the
+    /// end of the sound. The stereo stage runs after the output gain: the
+    /// mono output of the sound is copied to both channels of the frame,
+    /// which then passes through each stereo block in turn.
+    ///
+    /// Use [`process_stereo`] to get the stereo frame. While the stereo stage
+    /// is not empty, [`Sound::process`] returns the average of its two
+    /// channels.
+    ///
+    /// [`FrameBlock`]: ../trait.FrameBlock.html
+    /// [`BaeBlockN`]: ../bae_block_n/struct.BaeBlockN.html
+    /// [`process_stereo`]: struct.BaeSound.html#method.process_stereo
+    /// [`Sound::process`]: ../trait.Sound.html#tymethod.process
+    pub fn add_stereo_modifier(&mut self, m: FrameBlockSP<2>) {
+        self.stereo_list.push(m);
+    }
+
+    /// Returns the number of blocks in the stereo stage.
+    pub fn stereo_modifier_count(&self) -> usize {
+        self.stereo_list.len()
+    }
+
+    /// Processes the sound like [`Sound::process`], then runs the result
+    /// through the stereo stage added with [`add_stereo_modifier`], returning
+    /// the left and right samples. Paused and finished sounds return silence
+    /// without processing the stereo stage.
+    ///
+    /// [`Sound::process`]: ../trait.Sound.html#tymethod.process
+    /// [`add_stereo_modifier`]: struct.BaeSound.html#method.add_stereo_modifier
+    pub fn process_stereo(&mut self, input: SampleT) -> [SampleT; 2] {
+        if self.is_paused || self.is_finished() {
+            return Default::default();
+        }
+
+        let y = self.process_mono(input);
+        let mut frame = [y; 2];
+        for block in &self.stereo_list {
+            if let Ok(mut block) = block.lock() {
+                block.prime_frame(frame);
+                frame = block.process_frame();
+            }
+        }
+
+        frame
+    }
+
+    /// Splits the [`Modifier`] chain at the given index, returning a new
+    /// [`BaeSound`] containing the [`Modifier`]s from `index` onwards. This
+    /// sound keeps the [`Modifier`]s before `index`.
+    ///
+    /// The returned sound uses a passthrough [`BaeBlock`] as its generator, so
+    /// feeding it the output of this sound reproduces the output of the
+    /// original chain. To preserve the overall gain structure, this sound keeps
+    /// its input gain and has its output gain set to unity, while the returned
+    /// sound has a unity input gain and takes over the original output gain.
+    /// A parallel stage running after the chain moves to the returned sound,
+    /// while one running before it stays with this sound. The stereo stage
+    /// always moves to the returned sound.
+    ///
+    /// The [`ModMatrix`] stays with this sound, as sharing its sources would
+    /// process them twice per sample. Its routes to blocks that move are
+    /// removed, so the returned sound starts unmodulated, while routes to the
+    /// output gain keep modulating the output of this sound, now ahead of the
+    /// moved [`Modifier`]s.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of [`Modifier`]s.
+    ///
+    /// [`BaeSound`]: struct.BaeSound.html
+    /// [`BaeBlock`]: ../bae_block/struct.BaeBlock.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`ModMatrix`]: ../mod_matrix/struct.ModMatrix.html
+    pub fn split_at(&mut self, index: usize) -> BaeSound {
+        let moved_parallel = match self.parallel_position {
+            ParallelPosition::AfterChain => self.parallel_list.len(),
+            ParallelPosition::BeforeChain => 0,
+        };
+        self.mod_matrix
+            .remove_blocks(index + 1..self.modifier_list.len() + 1 + moved_parallel);
+
+        let tail = self.modifier_list.split_off(index);
+
+        let mut sound = BaeSound::new(
+            1.0,
+            self.get_output_gain(),
+            Arc::new(Mutex::new(BaeBlock::from_modifier(Passthrough::new()))),
+        );
+        sound.extend_modifiers(tail);
+        if self.parallel_position == ParallelPosition::AfterChain {
+            sound.parallel_list = std::mem::take(&mut self.parallel_list);
+            sound.parallel_position = ParallelPosition::AfterChain;
+        }
+        sound.stereo_list = std::mem::take(&mut self.stereo_list);
+        sound.dc_offset = self.dc_offset;
+        sound.output_envelope = self.output_envelope.take();
+        sound.mute_ramp.set_len(self.mute_ramp.len());
+
+        self.output_gain = 1.0;
+        self.dc_offset = 0.0;
+
+        sound
+    }
+
     /// Returns the linear gain applied to the input during processing.
     pub fn get_input_gain(&self) -> MathT {
         self.input_gain as MathT
@@ -88,11 +339,538 @@ impl BaeSound {
     pub fn set_output_gain(&mut self, g: MathT) {
         self.output_gain = g as SampleT;
     }
+
+    /// Sets an envelope automating the input gain, given as a list of
+    /// breakpoints pairing a time since the envelope was set with a linear
+    /// gain. The gain is interpolated linearly between breakpoints, holding
+    /// the first and last values before and after them, and replaces the
+    /// static input gain until the envelope is removed by passing an empty
+    /// list.
+    ///
+    /// The breakpoint times are converted to samples at the given sample rate,
+    /// and the envelope advances by one sample each time the sound is
+    /// processed while unpaused.
+    pub fn set_input_gain_envelope(
+        &mut self,
+        breakpoints: Vec<(Duration, MathT)>,
+        sample_rate: MathT,
+    ) {
+        self.input_envelope = GainEnvelope::new(breakpoints, sample_rate);
+    }
+
+    /// Sets an envelope automating the output gain, as with
+    /// [`set_input_gain_envelope`].
+    ///
+    /// [`set_input_gain_envelope`]: struct.BaeSound.html#method.set_input_gain_envelope
+    pub fn set_output_gain_envelope(
+        &mut self,
+        breakpoints: Vec<(Duration, MathT)>,
+        sample_rate: MathT,
+    ) {
+        self.output_envelope = GainEnvelope::new(breakpoints, sample_rate);
+    }
+
+    /// Returns a reference to the [`ModMatrix`] modulating the sound's
+    /// parameters.
+    ///
+    /// [`ModMatrix`]: ../mod_matrix/struct.ModMatrix.html
+    pub fn get_mod_matrix(&self) -> &ModMatrix {
+        &self.mod_matrix
+    }
+
+    /// Returns a mutable reference to the [`ModMatrix`] modulating the sound's
+    /// parameters. The matrix is applied each sample before the chain is
+    /// processed.
+    ///
+    /// [`ModMatrix`]: ../mod_matrix/struct.ModMatrix.html
+    pub fn get_mod_matrix_mut(&mut self) -> &mut ModMatrix {
+        &mut self.mod_matrix
+    }
+
+    /// Returns the number of audio samples between updates of control-rate
+    /// blocks.
+    pub fn get_control_rate_divisor(&self) -> usize {
+        self.control_rate_divisor
+    }
+
+    /// Sets the number of audio samples between updates of control-rate
+    /// blocks. Blocks reporting [`Block::is_control_rate`] are processed once
+    /// every `n` samples and hold their output in between, while all other
+    /// blocks are processed every sample. A divisor of `0` is treated as `1`.
+    ///
+    /// [`Block::is_control_rate`]: ../trait.Block.html#method.is_control_rate
+    pub fn set_control_rate_divisor(&mut self, n: usize) {
+        self.control_rate_divisor = n.max(1);
+        self.control_counter = 0;
+    }
+
+    /// Returns the gain in decibels applied to the input during processing.
+    pub fn get_input_gain_db(&self) -> MathT {
+        linear_to_db(self.get_input_gain())
+    }
+
+    /// Returns the gain in decibels applied to the output during processing.
+    pub fn get_output_gain_db(&self) -> MathT {
+        linear_to_db(self.get_output_gain())
+    }
+
+    /// Sets the input gain in decibels that is applied during processing.
+    pub fn set_input_gain_db(&mut self, db: MathT) {
+        self.set_input_gain(db_to_linear(db));
+    }
+
+    /// Sets the output gain in decibels that is applied during processing.
+    pub fn set_output_gain_db(&mut self, db: MathT) {
+        self.set_output_gain(db_to_linear(db));
+    }
+
+    /// Returns the metadata value stored under the given key.
+    pub fn get_meta(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(|v| v.as_str())
+    }
+
+    /// Stores a metadata value, such as a display name or color, under the
+    /// given key. Metadata has no effect on processing.
+    pub fn set_meta(&mut self, key: &str, value: &str) {
+        self.metadata.insert(key.to_owned(), value.to_owned());
+    }
+
+    /// Returns the parameters of every block in the sound, with each name
+    /// prefixed by the index of its block, e.g. `"1.frequency"`. The
+    /// generator block has index `0`, the series modifiers are numbered from
+    /// `1` in processing order, and the parallel modifiers follow them in the
+    /// order they were added, wherever the parallel stage runs.
+    pub fn all_parameters(&self) -> Vec<(String, MathT)> {
+        self.blocks()
+            .enumerate()
+            .filter_map(|(i, b)| b.lock().ok().map(|b| (i, b.parameters())))
+            .flat_map(|(i, params)| {
+                params
+                    .into_iter()
+                    .map(move |(name, value)| (format!("{}.{}", i, name), value))
+            })
+            .collect()
+    }
+
+    /// Sets the parameter with the given block-prefixed name, as returned by
+    /// [`all_parameters`]. Returns `false` if there is no such parameter.
+    ///
+    /// [`all_parameters`]: struct.BaeSound.html#method.all_parameters
+    pub fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        let (index, name) = match name.find('.') {
+            Some(p) => (&name[..p], &name[p + 1..]),
+            None => return false,
+        };
+
+        let index = match index.parse::<usize>() {
+            Ok(i) => i,
+            Err(_) => return false,
+        };
+
+        let set = self
+            .block_at(index)
+            .and_then(|b| b.lock().ok())
+            .map(|mut b| b.set_parameter(name, value))
+            .unwrap_or(false);
+        if set && self.mod_matrix.get_base(index, name).is_some() {
+            self.mod_matrix.set_base(index, name, value);
+        }
+
+        set
+    }
+
+    /// Resets the phase of every LFO in the sound, including those in the
+    /// parallel stage, such as blocks created with [`BaeBlock::from_lfo`], so
+    /// they restart from a common origin and stay phase-locked while running
+    /// at the same rate.
+    ///
+    /// [`BaeBlock::from_lfo`]: ../bae_block/struct.BaeBlock.html#method.from_lfo
+    pub fn sync_lfos(&mut self) {
+        for block in self.blocks() {
+            if let Ok(mut b) = block.lock() {
+                b.reset_phase();
+            }
+        }
+    }
+
+    /// Returns whether the LFOs of the sound are synchronized each time the
+    /// sound is triggered.
+    pub fn get_lfo_sync_on_trigger(&self) -> bool {
+        self.lfo_sync_on_trigger
+    }
+
+    /// Sets whether [`sync_lfos`] is called each time the sound is triggered,
+    /// i.e. when it is unpaused with [`Sound::toggle_pause`]. Defaults to
+    /// `false`.
+    ///
+    /// [`sync_lfos`]: struct.BaeSound.html#method.sync_lfos
+    /// [`Sound::toggle_pause`]: ../trait.Sound.html#tymethod.toggle_pause
+    pub fn set_lfo_sync_on_trigger(&mut self, sync: bool) {
+        self.lfo_sync_on_trigger = sync;
+    }
+
+    /// Measures the DC offset of the output of the chain over the given number
+    /// of samples, processing it with a silent input, and removes the
+    /// measured offset from all later output. The offset is measured before
+    /// the output gain, so it stays correct when the gain changes.
+    ///
+    /// The measurement processes the chain regardless of the pause and mute
+    /// states, advancing it by `measure_samples` samples. Calling this again
+    /// replaces the previous offset, and `0` samples removes it.
+    pub fn auto_dc_compensate(&mut self, measure_samples: usize) {
+        self.dc_offset = 0.0;
+
+        if measure_samples == 0 {
+            return;
+        }
+
+        let sum: MathT = (0..measure_samples)
+            .map(|_| self.process_chain(0.0).0 as MathT)
+            .sum();
+
+        self.dc_offset = (sum / measure_samples as MathT) as SampleT;
+    }
+
+    /// Returns the DC offset removed from the output, as measured by
+    /// [`auto_dc_compensate`].
+    ///
+    /// [`auto_dc_compensate`]: struct.BaeSound.html#method.auto_dc_compensate
+    pub fn get_dc_offset(&self) -> SampleT {
+        self.dc_offset
+    }
+
+    /// Returns the length in samples of the fade applied when the sound is
+    /// muted or unmuted.
+    pub fn get_mute_ramp(&self) -> usize {
+        self.mute_ramp.len()
+    }
+
+    /// Sets the length of the linear fade applied to the output when the
+    /// sound is muted or unmuted with [`Sound::toggle_mute`], avoiding the
+    /// click of an instant cut. The duration is converted to samples at the
+    /// given sample rate, and a duration of zero mutes instantly. Defaults to
+    /// [`DEFAULT_RAMP`] converted at [`DEFAULT_RAMP_SAMPLE_RATE`], as
+    /// [`new`] takes no sample rate.
+    ///
+    /// Muting a paused sound, or one that has not been processed yet, takes
+    /// effect instantly, as there is no running signal to click.
+    ///
+    /// [`Sound::toggle_mute`]: ../trait.Sound.html#tymethod.toggle_mute
+    /// [`DEFAULT_RAMP`]: ../constant.DEFAULT_RAMP.html
+    /// [`DEFAULT_RAMP_SAMPLE_RATE`]: ../constant.DEFAULT_RAMP_SAMPLE_RATE.html
+    /// [`new`]: struct.BaeSound.html#method.new
+    pub fn set_mute_ramp(&mut self, d: Duration, sample_rate: MathT) {
+        self.mute_ramp.set_len(ramp_samples(d, sample_rate));
+    }
+
+    /// Limits playback to the given duration from now, rounded to the nearest
+    /// sample at the given sample rate. Once that many samples have been
+    /// processed while unpaused, the sound outputs silence without processing
+    /// its chain and [`Sound::is_finished`] returns `true`, so a
+    /// [`BaeChannel`] removes the sound.
+    ///
+    /// [`Sound::is_finished`]: ../trait.Sound.html#method.is_finished
+    /// [`BaeChannel`]: ../channel/bae_channel/struct.BaeChannel.html
+    pub fn set_duration(&mut self, d: Duration, sample_rate: MathT) {
+        self.duration = Some(ramp_samples(d, sample_rate));
+        self.elapsed = 0;
+    }
+
+    /// Removes the duration limit set by [`set_duration`], letting the sound
+    /// play indefinitely.
+    ///
+    /// [`set_duration`]: struct.BaeSound.html#method.set_duration
+    pub fn clear_duration(&mut self) {
+        self.duration = None;
+    }
+
+    /// Processes the generator and modifier chain, returning its output along
+    /// with the modulated output gain to apply to it.
+    fn process_chain(&mut self, input: SampleT) -> (SampleT, SampleT) {
+        self.mod_matrix.process();
+        let input_gain = self
+            .input_envelope
+            .as_mut()
+            .map_or(self.input_gain, |e| e.next_gain());
+        let output_gain = self
+            .output_envelope
+            .as_mut()
+            .map_or(self.output_gain, |e| e.next_gain());
+        let input_gain =
+            input_gain * (1.0 + self.mod_matrix.modulation(&ModDestination::InputGain));
+        let output_gain =
+            output_gain * (1.0 + self.mod_matrix.modulation(&ModDestination::OutputGain));
+        self.apply_parameter_modulation();
+
+        self.control_values
+            .resize(self.modifier_list.len() + 1, Default::default());
+        let update_control = self.control_counter == 0;
+        self.control_counter = (self.control_counter + 1) % self.control_rate_divisor;
+
+        let mut out = Self::process_block(
+            &self.generator,
+            &mut self.control_values[0],
+            update_control,
+            input * input_gain,
+        )
+        .unwrap_or_default();
+
+        if self.parallel_position == ParallelPosition::BeforeChain {
+            out = Self::process_parallel(&self.parallel_list, out);
+        }
+
+        for (m, held) in self
+            .modifier_list
+            .iter()
+            .zip(self.control_values[1..].iter_mut())
+        {
+            out = Self::process_block(m, held, update_control, out).unwrap_or(out);
+        }
+
+        if self.parallel_position == ParallelPosition::AfterChain {
+            out = Self::process_parallel(&self.parallel_list, out);
+        }
+
+        (out, output_gain)
+    }
+
+    /// Feeds the dry signal to every parallel modifier, returning it summed
+    /// with their outputs scaled by their wet levels.
+    fn process_parallel(parallel: &[(BlockSP, SampleT)], dry: SampleT) -> SampleT {
+        let mut out = dry;
+
+        for (m, wet) in parallel {
+            if let Ok(mut m) = m.lock() {
+                m.prime_input(dry);
+                out += *wet * m.process();
+            }
+        }
+
+        out
+    }
+
+    /// Returns the block with the given index, as numbered by
+    /// [`all_parameters`].
+    ///
+    /// [`all_parameters`]: struct.BaeSound.html#method.all_parameters
+    fn block_at(&self, index: usize) -> Option<&BlockSP> {
+        self.blocks().nth(index)
+    }
+
+    /// Returns every block of the sound, numbered as by [`all_parameters`].
+    ///
+    /// [`all_parameters`]: struct.BaeSound.html#method.all_parameters
+    fn blocks(&self) -> impl Iterator<Item = &BlockSP> {
+        std::iter::once(&self.generator)
+            .chain(self.modifier_list.iter())
+            .chain(self.parallel_list.iter().map(|(b, _)| b))
+    }
+
+    /// Sets every parameter routed in the [`ModMatrix`] to its modulated
+    /// value for the current sample, recording the base value of parameters
+    /// modulated for the first time.
+    ///
+    /// [`ModMatrix`]: ../mod_matrix/struct.ModMatrix.html
+    fn apply_parameter_modulation(&mut self) {
+        let mut new_bases = Vec::new();
+
+        for (index, name, m) in self.mod_matrix.parameter_modulations() {
+            let mut b = match self.block_at(index).map(|b| b.lock()) {
+                Some(Ok(b)) => b,
+                _ => continue,
+            };
+
+            let base = match self.mod_matrix.get_base(index, name) {
+                Some(v) => v,
+                None => match b.parameters().into_iter().find(|(n, _)| n == name) {
+                    Some((_, v)) => {
+                        new_bases.push((index, name.to_owned(), v));
+                        v
+                    }
+                    None => continue,
+                },
+            };
+
+            b.set_parameter(name, base * (1.0 + m as MathT));
+        }
+
+        for (index, name, v) in new_bases {
+            self.mod_matrix.set_base(index, &name, v);
+        }
+    }
+
+    fn process_block(
+        block: &BlockSP,
+        held: &mut SampleT,
+        update_control: bool,
+        input: SampleT,
+    ) -> Option<SampleT> {
+        let mut b = block.lock().ok()?;
+
+        if !b.is_control_rate() || update_control {
+            b.prime_input(input);
+            *held = b.process();
+        }
+
+        Some(*held)
+    }
+
+    /// Processes the mono output of the sound, before the stereo stage.
+    fn process_mono(&mut self, input: SampleT) -> SampleT {
+        if self.is_paused || self.is_finished() {
+            return Default::default();
+        }
+        self.elapsed += 1;
+
+        let (out, output_gain) = self.process_chain(input);
+
+        let mute_gain = self.mute_ramp.next_gain(self.is_muted);
+        if mute_gain == 0.0 {
+            Default::default()
+        } else {
+            (out - self.dc_offset) * output_gain * mute_gain
+        }
+    }
+}
+
+/// Enum describing where the parallel stage of a [`BaeSound`] runs relative
+/// to its series chain of modifiers. See
+/// [`BaeSound::add_parallel_modifier`].
+///
+/// [`BaeSound`]: struct.BaeSound.html
+/// [`BaeSound::add_parallel_modifier`]: struct.BaeSound.html#method.add_parallel_modifier
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParallelPosition {
+    /// The parallel stage processes the output of the generator, and its mix
+    /// is fed to the series chain.
+    BeforeChain,
+    /// The parallel stage processes the output of the series chain, e.g. for a
+    /// reverb send mixed into the final output.
+    AfterChain,
+}
+
+/// Breakpoint envelope automating one of the gains of a [`BaeSound`].
+///
+/// [`BaeSound`]: struct.BaeSound.html
+#[derive(Clone)]
+struct GainEnvelope {
+    breakpoints: Vec<(MathT, SampleT)>,
+    position: usize,
+}
+
+impl GainEnvelope {
+    /// Converts the breakpoint times to samples, returning `None` if there
+    /// are no breakpoints.
+    fn new(breakpoints: Vec<(Duration, MathT)>, sample_rate: MathT) -> Option<Self> {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        if breakpoints.is_empty() {
+            return None;
+        }
+
+        let mut breakpoints: Vec<(MathT, SampleT)> = breakpoints
+            .into_iter()
+            .map(|(t, g)| (t.as_secs_f64() * sample_rate, g as SampleT))
+            .collect();
+        breakpoints.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        Some(GainEnvelope {
+            breakpoints,
+            position: 0,
+        })
+    }
+
+    /// Returns the gain at the current position and advances by one sample.
+    fn next_gain(&mut self) -> SampleT {
+        let n = self.position as MathT;
+        self.position += 1;
+
+        let next = self.breakpoints.iter().position(|(t, _)| *t > n);
+
+        match next {
+            Some(0) => self.breakpoints[0].1,
+            Some(i) => {
+                let (t0, g0) = self.breakpoints[i - 1];
+                let (t1, g1) = self.breakpoints[i];
+
+                g0 + (g1 - g0) * ((n - t0) / (t1 - t0)) as SampleT
+            }
+            None => self.breakpoints[self.breakpoints.len() - 1].1,
+        }
+    }
+}
+
+/// Linear fade applied to the output of a sound as it is muted or unmuted,
+/// shared by [`BaeSound`] and [`BlockSound`].
+///
+/// [`BaeSound`]: struct.BaeSound.html
+/// [`BlockSound`]: ../block_sound/struct.BlockSound.html
+#[derive(Copy, Clone)]
+pub(crate) struct MuteRamp {
+    len: usize,
+    gain: SampleT,
+    running: bool,
+}
+
+impl MuteRamp {
+    /// Creates a ramp of [`DEFAULT_RAMP`] at [`DEFAULT_RAMP_SAMPLE_RATE`],
+    /// starting unmuted.
+    ///
+    /// [`DEFAULT_RAMP`]: ../constant.DEFAULT_RAMP.html
+    /// [`DEFAULT_RAMP_SAMPLE_RATE`]: ../constant.DEFAULT_RAMP_SAMPLE_RATE.html
+    pub(crate) fn new() -> Self {
+        MuteRamp {
+            len: ramp_samples(DEFAULT_RAMP, DEFAULT_RAMP_SAMPLE_RATE),
+            gain: 1.0,
+            running: false,
+        }
+    }
+
+    /// Returns the length of the ramp in samples.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Sets the length of the ramp in samples, where `0` mutes instantly.
+    pub(crate) fn set_len(&mut self, len: usize) {
+        self.len = len;
+    }
+
+    /// Updates the ramp for a change of mute state. The change takes effect
+    /// instantly if the sound is paused or has not been processed yet.
+    pub(crate) fn toggle(&mut self, muted: bool, paused: bool) {
+        if paused || !self.running {
+            self.gain = if muted { 0.0 } else { 1.0 };
+        }
+    }
+
+    /// Advances the ramp by one processed sample towards the given mute
+    /// state, returning the gain to apply to that sample.
+    pub(crate) fn next_gain(&mut self, muted: bool) -> SampleT {
+        self.running = true;
+
+        let target = if muted { 0.0 } else { 1.0 };
+        if self.len == 0 {
+            self.gain = target;
+        } else {
+            let step = 1.0 / self.len as SampleT;
+            self.gain = if self.gain < target {
+                (self.gain + step).min(target)
+            } else {
+                (self.gain - step).max(target)
+            };
+        }
+
+        self.gain
+    }
 }
 
 impl Sound for BaeSound {
     fn toggle_pause(&mut self) {
         self.is_paused = !self.is_paused;
+
+        if !self.is_paused && self.lfo_sync_on_trigger {
+            self.sync_lfos();
+        }
     }
 
     fn is_paused(&self) -> bool {
@@ -100,13 +878,33 @@ impl Sound for BaeSound {
     }
 
     fn toggle_mute(&mut self) {
-        self.is_muted = !self.is_muted
+        self.is_muted = !self.is_muted;
+        self.mute_ramp.toggle(self.is_muted, self.is_paused);
     }
 
     fn is_muted(&self) -> bool {
         self.is_muted
     }
 
+    /// Sets the output gain, as with [`set_output_gain`].
+    ///
+    /// [`set_output_gain`]: struct.BaeSound.html#method.set_output_gain
+    fn set_gain(&mut self, g: MathT) {
+        self.set_output_gain(g);
+    }
+
+    fn get_gain(&self) -> MathT {
+        self.get_output_gain()
+    }
+
+    /// Returns whether the sound has played for the duration given to
+    /// [`set_duration`]. Sounds without a duration never finish.
+    ///
+    /// [`set_duration`]: struct.BaeSound.html#method.set_duration
+    fn is_finished(&self) -> bool {
+        matches!(self.duration, Some(d) if self.elapsed >= d)
+    }
+
     fn register(&mut self, id: usize) {
         self.id = Some(id);
     }
@@ -116,28 +914,11 @@ impl Sound for BaeSound {
     }
 
     fn process(&mut self, input: SampleT) -> SampleT {
-        if self.is_paused {
-            return Default::default();
-        }
-
-        let mut out = if let Some(b) = BlockSP::get_mut(&mut self.generator) {
-            b.prime_input(input * self.input_gain);
-            b.process()
-        } else {
-            Default::default()
-        };
-
-        for m in &mut self.modifier_list {
-            if let Some(m) = BlockSP::get_mut(m) {
-                m.prime_input(out);
-                out = m.process();
-            }
-        }
-
-        if self.is_muted {
-            Default::default()
+        if self.stereo_list.is_empty() {
+            self.process_mono(input)
         } else {
-            out * self.output_gain
+            let [l, r] = self.process_stereo(input);
+            (l + r) * 0.5
         }
     }
 
@@ -0,0 +1,104 @@
+//! # Block Iterators
+//!
+//! Adapters exposing the output of a [`Block`] as an [`Iterator`] of samples,
+//! replacing manual processing loops.
+//!
+//! [`Block`]: ../trait.Block.html
+//! [`Iterator`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html
+
+use super::*;
+
+/// [`Iterator`] yielding the output of [`Block::process`] on each call to
+/// `next`. The iterator never ends; limit it with [`Iterator::take`].
+///
+/// Create one with [`Block::samples`], or with [`BlockIter::new`] for
+/// unsized blocks such as the contents of a locked [`BlockSP`].
+///
+/// [`Iterator`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html
+/// [`Iterator::take`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.take
+/// [`Block::process`]: ../trait.Block.html#tymethod.process
+/// [`Block::samples`]: ../trait.Block.html#method.samples
+/// [`BlockIter::new`]: struct.BlockIter.html#method.new
+/// [`BlockSP`]: ../type.BlockSP.html
+pub struct BlockIter<'a, B>
+where
+    B: Block + ?Sized,
+{
+    block: &'a mut B,
+}
+
+impl<'a, B> BlockIter<'a, B>
+where
+    B: Block + ?Sized,
+{
+    /// Creates a new [`BlockIter`] processing the given [`Block`].
+    ///
+    /// [`BlockIter`]: struct.BlockIter.html
+    /// [`Block`]: ../trait.Block.html
+    pub fn new(block: &'a mut B) -> Self {
+        BlockIter { block }
+    }
+
+    /// Converts the iterator into one that primes the [`Block`] with a sample
+    /// pulled from the given input before each call to [`Block::process`].
+    /// The returned iterator ends when the input does.
+    ///
+    /// [`Block`]: ../trait.Block.html
+    /// [`Block::process`]: ../trait.Block.html#tymethod.process
+    pub fn with_input<I>(self, input: I) -> BlockInputIter<'a, B, I::IntoIter>
+    where
+        I: IntoIterator<Item = SampleT>,
+    {
+        BlockInputIter {
+            block: self.block,
+            input: input.into_iter(),
+        }
+    }
+}
+
+impl<'a, B> Iterator for BlockIter<'a, B>
+where
+    B: Block + ?Sized,
+{
+    type Item = SampleT;
+
+    fn next(&mut self) -> Option<SampleT> {
+        Some(self.block.process())
+    }
+}
+
+/// [`Iterator`] priming a [`Block`] with each sample of an input iterator and
+/// yielding the result of [`Block::process`]. Created by
+/// [`BlockIter::with_input`].
+///
+/// [`Iterator`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html
+/// [`Block`]: ../trait.Block.html
+/// [`Block::process`]: ../trait.Block.html#tymethod.process
+/// [`BlockIter::with_input`]: struct.BlockIter.html#method.with_input
+pub struct BlockInputIter<'a, B, I>
+where
+    B: Block + ?Sized,
+    I: Iterator<Item = SampleT>,
+{
+    block: &'a mut B,
+    input: I,
+}
+
+impl<'a, B, I> Iterator for BlockInputIter<'a, B, I>
+where
+    B: Block + ?Sized,
+    I: Iterator<Item = SampleT>,
+{
+    type Item = SampleT;
+
+    fn next(&mut self) -> Option<SampleT> {
+        let x = self.input.next()?;
+
+        self.block.prime_input(x);
+        Some(self.block.process())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
@@ -0,0 +1,132 @@
+//! # Block Sound
+//!
+//! Module containing the simplest [`Sound`], playing a single [`Block`].
+//!
+//! [`Sound`]: ../trait.Sound.html
+//! [`Block`]: ../trait.Block.html
+
+use super::*;
+
+use std::time::Duration;
+
+/// Struct implementing [`Sound`] for a single [`Block`], for blocks that
+/// don't fit the generator-plus-modifiers shape of a [`BaeSound`], such as
+/// custom graph blocks. Each call to [`process`] primes the [`Block`] with
+/// the input and returns its output, scaled by the gain set with
+/// [`Sound::set_gain`]. Muting fades the output as with
+/// [`BaeSound::set_mute_ramp`].
+///
+/// [`Sound`]: ../trait.Sound.html
+/// [`Block`]: ../trait.Block.html
+/// [`BaeSound`]: ../bae_sound/struct.BaeSound.html
+/// [`process`]: ../trait.Sound.html#tymethod.process
+/// [`Sound::set_gain`]: ../trait.Sound.html#tymethod.set_gain
+/// [`BaeSound::set_mute_ramp`]: ../bae_sound/struct.BaeSound.html#method.set_mute_ramp
+#[derive(Clone)]
+pub struct BlockSound {
+    block: BlockSP,
+    gain: SampleT,
+    mute_ramp: MuteRamp,
+    id: Option<usize>,
+    is_muted: bool,
+    is_paused: bool,
+}
+
+impl BlockSound {
+    /// Constructs a new [`BlockSound`] playing the given [`Block`].
+    ///
+    /// [`BlockSound`]: struct.BlockSound.html
+    /// [`Block`]: ../trait.Block.html
+    pub fn new(block: BlockSP) -> Self {
+        BlockSound {
+            block,
+            gain: 1.0,
+            mute_ramp: MuteRamp::new(),
+            id: None,
+            is_muted: false,
+            is_paused: false,
+        }
+    }
+
+    /// Returns a reference to the wrapped [`Block`].
+    ///
+    /// [`Block`]: ../trait.Block.html
+    pub fn get_block(&self) -> &BlockSP {
+        &self.block
+    }
+
+    /// Returns the length in samples of the fade applied when the sound is
+    /// muted or unmuted.
+    pub fn get_mute_ramp(&self) -> usize {
+        self.mute_ramp.len()
+    }
+
+    /// Sets the length of the fade applied when the sound is muted or
+    /// unmuted, as with [`BaeSound::set_mute_ramp`].
+    ///
+    /// [`BaeSound::set_mute_ramp`]: ../bae_sound/struct.BaeSound.html#method.set_mute_ramp
+    pub fn set_mute_ramp(&mut self, d: Duration, sample_rate: MathT) {
+        self.mute_ramp.set_len(ramp_samples(d, sample_rate));
+    }
+}
+
+impl Sound for BlockSound {
+    fn toggle_pause(&mut self) {
+        self.is_paused = !self.is_paused;
+    }
+
+    fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    fn toggle_mute(&mut self) {
+        self.is_muted = !self.is_muted;
+        self.mute_ramp.toggle(self.is_muted, self.is_paused);
+    }
+
+    fn is_muted(&self) -> bool {
+        self.is_muted
+    }
+
+    fn set_gain(&mut self, g: MathT) {
+        self.gain = g as SampleT;
+    }
+
+    fn get_gain(&self) -> MathT {
+        self.gain as MathT
+    }
+
+    fn register(&mut self, id: usize) {
+        self.id = Some(id);
+    }
+
+    fn unregister(&mut self) {
+        self.id = None;
+    }
+
+    fn process(&mut self, input: SampleT) -> SampleT {
+        if self.is_paused {
+            return Default::default();
+        }
+
+        let out = self
+            .block
+            .lock()
+            .map(|mut b| {
+                b.prime_input(input);
+                b.process()
+            })
+            .unwrap_or_default();
+
+        let mute_gain = self.mute_ramp.next_gain(self.is_muted);
+        if mute_gain == 0.0 {
+            Default::default()
+        } else {
+            out * self.gain * mute_gain
+        }
+    }
+
+    fn get_id(&self) -> Option<usize> {
+        self.id
+    }
+}
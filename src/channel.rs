@@ -8,13 +8,65 @@
 use super::*;
 
 use bae_sf::*;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 pub mod bae_channel;
+pub mod master_bus;
+#[cfg(feature = "realtime")]
+pub mod realtime_channel;
+pub mod voice_manager;
+
 pub use bae_channel::*;
+pub use master_bus::*;
+#[cfg(feature = "realtime")]
+pub use realtime_channel::*;
+pub use voice_manager::*;
+
+/// Alias for a [`Sound`] object wrapped in a smart pointer.
+///
+/// As with [`BlockSP`], the [`Sound`] is shared behind a [`Mutex`], so the same
+/// [`Sound`] can be added to several [`Channel`]s, each of which locks it while
+/// processing. Every [`Channel`] processing a shared [`Sound`] advances it, so
+/// its output is split between them rather than duplicated.
+///
+/// [`Sound`]: ../trait.Sound.html
+/// [`BlockSP`]: ../type.BlockSP.html
+/// [`Channel`]: trait.Channel.html
+/// [`Mutex`]: https://doc.rust-lang.org/std/sync/struct.Mutex.html
+pub type SoundSP = Arc<Mutex<dyn Sound + Send>>;
+
+/// Errors returned by [`Channel`] operations.
+///
+/// [`Channel`]: trait.Channel.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelError {
+    /// The [`Channel`] already holds its maximum number of [`Sound`]s.
+    ///
+    /// [`Channel`]: trait.Channel.html
+    /// [`Sound`]: ../trait.Sound.html
+    ChannelFull,
+    /// The outputs of the [`Channel`]s mixed by a [`MasterBus`] differ in
+    /// length. Holds the index of the first [`Channel`] whose output length
+    /// differs from the first [`Channel`]'s.
+    ///
+    /// [`Channel`]: trait.Channel.html
+    /// [`MasterBus`]: master_bus/struct.MasterBus.html
+    OutputLengthMismatch(usize),
+}
+
+impl std::fmt::Display for ChannelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ChannelError::ChannelFull => write!(f, "channel is full"),
+            ChannelError::OutputLengthMismatch(i) => {
+                write!(f, "output length of channel {} does not match", i)
+            }
+        }
+    }
+}
 
-type SoundSP = Arc<dyn Sound>;
+impl std::error::Error for ChannelError {}
 
 /// Trait defining the simplest possible interface for a channel, with the
 /// ability to process a batch of samples at a time.
@@ -23,7 +75,9 @@ where
     SF: SampleFormat,
 {
     /// Sets the amount of time [`process`] should calculate samples for. The
-    /// given duration is truncated to a integer sample value.
+    /// given duration is rounded to the nearest integer sample value, so
+    /// durations computed from a sample count, which may be off by a
+    /// nanosecond, still process exactly that many samples.
     ///
     /// [`process`]: trait.Channel.html#tymethod.process
     fn set_process_time(&mut self, d: Duration);
@@ -38,11 +92,13 @@ where
     /// internal track of samples.
     fn process(&mut self);
 
-    /// Adds a [`Sound`] to the [`Channel`] for processing.
+    /// Adds a [`Sound`] to the [`Channel`] for processing, returning the id
+    /// it was registered with, or an error if the [`Sound`] could not be
+    /// added.
     ///
     /// [`Channel`]: trait.Channel.html
     /// [`Sound`]: https://docs.rs/bae_sounds/0.1.0/trait.Sound.html
-    fn add_sound(&mut self, sound: &mut SoundSP);
+    fn add_sound(&mut self, sound: &mut SoundSP) -> Result<usize, ChannelError>;
 
     /// Removes a [`Sound`] from the [`Channel`].
     ///
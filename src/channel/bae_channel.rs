@@ -0,0 +1,548 @@
+//! # Standard Channel
+
+use super::*;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Standard implementation of the [`Channel`] trait.
+///
+/// All processing advances one sample at a time, and sounds are always mixed
+/// in the order they were added, so the output is bit-identical regardless of
+/// the process time used to render it.
+///
+/// Alongside the mono output, the channel renders a stereo pair of outputs,
+/// available through [`get_output_stereo`]. Sounds added with
+/// [`add_sound_panned`] are placed with an equal-power pan law, while all
+/// other sounds are centered. The mono output always contains every sound at
+/// full level, regardless of pan.
+///
+/// The channel also meters its mono output: [`get_peak`] holds the highest
+/// absolute sample since the last call to [`reset_meters`], while [`get_rms`]
+/// returns the RMS of the most recently processed block. Both read the mix
+/// as a [`SampleT`] just before it is converted to the output format, so
+/// they still report levels above full scale, which the outputs clip to
+/// `[-1, 1]`.
+///
+/// Sounds can be soloed with [`set_solo`]. While any sound is soloed, the
+/// others are skipped entirely, so they neither contribute to the outputs
+/// nor advance until the solo is lifted.
+///
+/// After each call to [`process`] or [`process_into`], sounds reporting
+/// [`Sound::is_finished`] are unregistered and removed from the channel.
+///
+/// [`Channel`]: ../trait.Channel.html
+/// [`set_solo`]: struct.BaeChannel.html#method.set_solo
+/// [`process`]: ../trait.Channel.html#tymethod.process
+/// [`process_into`]: struct.BaeChannel.html#method.process_into
+/// [`Sound::is_finished`]: ../../trait.Sound.html#method.is_finished
+/// [`get_output_stereo`]: struct.BaeChannel.html#method.get_output_stereo
+/// [`add_sound_panned`]: struct.BaeChannel.html#method.add_sound_panned
+/// [`get_peak`]: struct.BaeChannel.html#method.get_peak
+/// [`get_rms`]: struct.BaeChannel.html#method.get_rms
+/// [`reset_meters`]: struct.BaeChannel.html#method.reset_meters
+#[derive(Clone)]
+pub struct BaeChannel<SF>
+where
+    SF: SampleFormat,
+{
+    sample_rate: MathT,
+    output: Vec<SF>,
+    output_left: Vec<SF>,
+    output_right: Vec<SF>,
+    process_samples: usize,
+    sounds: BTreeMap<usize, SoundSP>,
+    pans: BTreeMap<usize, (SampleT, SampleT)>,
+    soloed: BTreeSet<usize>,
+    gain: SampleT,
+    inserts: Vec<BlockSP>,
+    max_sounds: Option<usize>,
+    peak: SampleT,
+    rms: SampleT,
+    id_counter: usize,
+}
+
+impl<SF> BaeChannel<SF>
+where
+    SF: SampleFormat,
+{
+    /// Creates a new channel with the given gain.
+    ///
+    /// The internal track is initialized for 10ms' worth of samples. Call
+    /// [`set_process_time`] to change this.
+    ///
+    /// Debug builds panic if `sample_rate` is not positive, as it would
+    /// otherwise produce silent NaNs during processing.
+    ///
+    /// [`set_process_time`]: ../trait.Channel.html#tymethod.set_process_time
+    pub fn new(gain: MathT, sample_rate: MathT) -> Self {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        let process_samples = (0.01 * sample_rate as MathT) as usize;
+
+        BaeChannel {
+            sample_rate,
+            output: Vec::with_capacity(process_samples),
+            output_left: Vec::with_capacity(process_samples),
+            output_right: Vec::with_capacity(process_samples),
+            process_samples,
+            sounds: BTreeMap::new(),
+            pans: BTreeMap::new(),
+            soloed: BTreeSet::new(),
+            gain: gain as SampleT,
+            inserts: Vec::new(),
+            max_sounds: None,
+            peak: SampleT::default(),
+            rms: SampleT::default(),
+            id_counter: 0,
+        }
+    }
+
+    /// Returns the gain of the output of the channel in decibels.
+    pub fn get_gain_db(&self) -> MathT {
+        linear_to_db(self.gain as MathT)
+    }
+
+    /// Sets the gain of the output of the channel in decibels.
+    pub fn set_gain_db(&mut self, db: MathT) {
+        self.set_gain(db_to_linear(db));
+    }
+
+    /// Adds a [`Sound`] to the channel as with [`Channel::add_sound`], placing
+    /// it in the stereo outputs at the given pan, clamped to `[-1, 1]`, where
+    /// `-1` is hard left and `1` hard right.
+    ///
+    /// [`Sound`]: ../../trait.Sound.html
+    /// [`Channel::add_sound`]: ../trait.Channel.html#tymethod.add_sound
+    pub fn add_sound_panned(
+        &mut self,
+        sound: &mut SoundSP,
+        pan: MathT,
+    ) -> Result<usize, ChannelError> {
+        let id = self.add_sound(sound)?;
+
+        let theta = (pan.max(-1.0).min(1.0) + 1.0) * std::f64::consts::FRAC_PI_4;
+        self.pans
+            .insert(id, (theta.cos() as SampleT, theta.sin() as SampleT));
+
+        Ok(id)
+    }
+
+    /// Returns the number of [`Sound`]s in the channel.
+    ///
+    /// [`Sound`]: ../../trait.Sound.html
+    pub fn sound_count(&self) -> usize {
+        self.sounds.len()
+    }
+
+    /// Returns the ids of the [`Sound`]s in the channel, in the order they
+    /// were added.
+    ///
+    /// [`Sound`]: ../../trait.Sound.html
+    pub fn sound_ids(&self) -> Vec<usize> {
+        self.sounds.keys().copied().collect()
+    }
+
+    /// Returns the gain of the [`Sound`] with the given id, or `None` if it
+    /// is not in the channel.
+    ///
+    /// [`Sound`]: ../../trait.Sound.html
+    pub fn get_sound_gain(&self, id: usize) -> Option<MathT> {
+        self.sounds
+            .get(&id)
+            .and_then(|s| s.lock().ok())
+            .map(|s| s.get_gain())
+    }
+
+    /// Sets the gain of the [`Sound`] with the given id through
+    /// [`Sound::set_gain`], e.g. for a per-sound fader. Returns `false` if
+    /// the sound is not in the channel.
+    ///
+    /// [`Sound`]: ../../trait.Sound.html
+    /// [`Sound::set_gain`]: ../../trait.Sound.html#tymethod.set_gain
+    pub fn set_sound_gain(&mut self, id: usize, g: MathT) -> bool {
+        match self.sounds.get(&id).map(|s| s.lock()) {
+            Some(Ok(mut s)) => {
+                s.set_gain(g);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Solos or unsolos the [`Sound`] with the given id. While at least one
+    /// sound is soloed, only soloed sounds are processed. Ids that are not in
+    /// the channel are ignored.
+    ///
+    /// [`Sound`]: ../../trait.Sound.html
+    pub fn set_solo(&mut self, id: usize, solo: bool) {
+        if solo && self.sounds.contains_key(&id) {
+            self.soloed.insert(id);
+        } else {
+            self.soloed.remove(&id);
+        }
+    }
+
+    /// Returns whether the [`Sound`] with the given id is soloed.
+    ///
+    /// [`Sound`]: ../../trait.Sound.html
+    pub fn is_soloed(&self, id: usize) -> bool {
+        self.soloed.contains(&id)
+    }
+
+    /// Returns the maximum number of [`Sound`]s the channel accepts, or `None`
+    /// if it is unlimited.
+    ///
+    /// [`Sound`]: ../../trait.Sound.html
+    pub fn get_max_sounds(&self) -> Option<usize> {
+        self.max_sounds
+    }
+
+    /// Limits the number of [`Sound`]s the channel accepts. Once the limit is
+    /// reached, [`Channel::add_sound`] returns [`ChannelError::ChannelFull`].
+    /// Sounds already in the channel are kept if there are more than `n`.
+    /// Channels are unlimited by default.
+    ///
+    /// [`Sound`]: ../../trait.Sound.html
+    /// [`Channel::add_sound`]: ../trait.Channel.html#tymethod.add_sound
+    /// [`ChannelError::ChannelFull`]: ../enum.ChannelError.html#variant.ChannelFull
+    pub fn set_max_sounds(&mut self, n: usize) {
+        self.max_sounds = Some(n);
+    }
+
+    /// Returns references to the left and right stereo outputs, rendered
+    /// alongside the mono output by each call to [`process`]. The stereo
+    /// outputs include the channel gain but not the inserts, which only
+    /// process the mono output.
+    ///
+    /// [`process`]: ../trait.Channel.html#tymethod.process
+    pub fn get_output_stereo(&self) -> (&Vec<SF>, &Vec<SF>) {
+        (&self.output_left, &self.output_right)
+    }
+
+    /// Clears the mono and stereo outputs, keeping their capacity so the next
+    /// call to [`process`] doesn't need to allocate.
+    ///
+    /// [`process`]: ../trait.Channel.html#tymethod.process
+    pub fn clear_output(&mut self) {
+        self.output.clear();
+        self.output_left.clear();
+        self.output_right.clear();
+    }
+
+    /// Returns the highest absolute sample of the mono output since the
+    /// channel was created or [`reset_meters`] was last called.
+    ///
+    /// [`reset_meters`]: struct.BaeChannel.html#method.reset_meters
+    pub fn get_peak(&self) -> SampleT {
+        self.peak
+    }
+
+    /// Returns the RMS of the mono output over the block rendered by the most
+    /// recent call to [`process`].
+    ///
+    /// [`process`]: ../trait.Channel.html#tymethod.process
+    pub fn get_rms(&self) -> SampleT {
+        self.rms
+    }
+
+    /// Resets the peak-hold and RMS meters to zero.
+    pub fn reset_meters(&mut self) {
+        self.peak = SampleT::default();
+        self.rms = SampleT::default();
+    }
+
+    /// Appends a [`Block`] to the list of inserts processing the output of
+    /// the channel. Inserts run in the order they were added on the mix of
+    /// all sounds after the channel gain, e.g. for master EQ.
+    ///
+    /// [`Block`]: ../../trait.Block.html
+    pub fn add_insert(&mut self, m: BlockSP) {
+        self.inserts.push(m);
+    }
+
+    /// Removes and returns the insert at the given index, or returns `None`
+    /// if there is no insert at that index.
+    pub fn remove_insert(&mut self, index: usize) -> Option<BlockSP> {
+        if index < self.inserts.len() {
+            Some(self.inserts.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of inserts on the output of the channel.
+    pub fn insert_count(&self) -> usize {
+        self.inserts.len()
+    }
+
+    /// Returns whether the channel has no sounds to process, either because
+    /// none have been added or because all of them are paused. An idle
+    /// channel skips processing its sounds, and outputs silence unless it
+    /// has inserts, which keep running so effect tails are not cut off.
+    pub fn is_idle(&self) -> bool {
+        self.sounds
+            .values()
+            .all(|s| s.lock().map(|s| s.is_paused()).unwrap_or(true))
+    }
+
+    /// Calls the given closure with mutable access to each [`Sound`] in the
+    /// channel, e.g. to mute every sound or change their gains at once.
+    ///
+    /// Each [`Sound`] is locked while the closure runs, so the changes are
+    /// visible to every other [`SoundSP`] sharing it.
+    ///
+    /// [`Sound`]: ../../trait.Sound.html
+    /// [`SoundSP`]: ../type.SoundSP.html
+    pub fn for_each_sound<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut dyn Sound),
+    {
+        for sound in self.sounds.values() {
+            if let Ok(mut s) = sound.lock() {
+                f(&mut *s);
+            }
+        }
+    }
+
+    /// Renders the given number of samples offline, as fast as possible,
+    /// returning the full buffer. The channel is processed in blocks of its
+    /// process time, and `progress` is called after each block with the
+    /// fraction of the render completed, reaching `1.0` once it is done.
+    ///
+    /// The rendered samples are moved out of the channel, so its output is
+    /// left empty until the next call to [`process`].
+    ///
+    /// [`process`]: ../trait.Channel.html#tymethod.process
+    pub fn render_offline<F>(&mut self, total_samples: usize, mut progress: F) -> Vec<SF>
+    where
+        F: FnMut(f32),
+    {
+        let block = self.process_samples.max(1);
+        let mut rendered = Vec::with_capacity(total_samples);
+
+        while rendered.len() < total_samples {
+            self.process_samples = block.min(total_samples - rendered.len());
+            self.process();
+            rendered.append(&mut self.output);
+
+            progress(rendered.len() as f32 / total_samples as f32);
+        }
+
+        if total_samples == 0 {
+            progress(1.0);
+        }
+
+        self.process_samples = block;
+
+        rendered
+    }
+
+    /// Sets the number of samples rendered by [`process`], reserving space
+    /// for them. The outputs never shrink, so once they have grown to the
+    /// largest block processed this does not allocate.
+    ///
+    /// [`process`]: ../trait.Channel.html#tymethod.process
+    #[cfg(feature = "realtime")]
+    pub(crate) fn set_process_samples(&mut self, n: usize) {
+        self.process_samples = n;
+
+        self.output.reserve(n.saturating_sub(self.output.len()));
+        self.output_left
+            .reserve(n.saturating_sub(self.output_left.len()));
+        self.output_right
+            .reserve(n.saturating_sub(self.output_right.len()));
+    }
+
+    /// Mixes the given buffer's worth of samples directly into `out`, as with
+    /// [`process`], without touching the channel's own outputs. This avoids
+    /// copying out of [`get_output`] and lets callers reuse their buffers.
+    /// Only the mono output is rendered, after the channel gain and inserts,
+    /// and the meters are updated as with [`process`].
+    ///
+    /// [`process`]: ../trait.Channel.html#tymethod.process
+    /// [`get_output`]: ../trait.Channel.html#tymethod.get_output
+    pub fn process_into(&mut self, out: &mut [SF]) {
+        let idle = self.is_idle();
+        self.rms = SampleT::default();
+
+        if idle && self.inserts.is_empty() {
+            for sample in out.iter_mut() {
+                *sample = SF::default();
+            }
+            return;
+        }
+
+        let mut sum_squares = SampleT::default();
+
+        for sample in out.iter_mut() {
+            let (mix, _, _) = self.mix_sample(idle);
+            sum_squares += mix * mix;
+
+            *sample = Self::to_format(mix);
+        }
+
+        if !out.is_empty() {
+            self.rms = (sum_squares / out.len() as SampleT).sqrt();
+        }
+
+        self.remove_finished();
+    }
+
+    /// Processes every sound for a single sample, returning the mono mix after
+    /// the channel gain and inserts along with the left and right mixes after
+    /// the channel gain, and updating the peak meter.
+    fn mix_sample(&mut self, idle: bool) -> (SampleT, SampleT, SampleT) {
+        let center = std::f64::consts::FRAC_1_SQRT_2 as SampleT;
+        let mut mix = SampleT::default();
+        let (mut l, mut r) = (SampleT::default(), SampleT::default());
+
+        if !idle {
+            for (id, sound) in &self.sounds {
+                if !self.soloed.is_empty() && !self.soloed.contains(id) {
+                    continue;
+                }
+
+                if let Ok(mut s) = sound.lock() {
+                    let y = s.process(Default::default());
+                    let (gl, gr) = self.pans.get(id).copied().unwrap_or((center, center));
+
+                    mix += y;
+                    l += y * gl;
+                    r += y * gr;
+                }
+            }
+        }
+
+        mix *= self.gain;
+
+        for m in &mut self.inserts {
+            if let Ok(mut m) = m.lock() {
+                m.prime_input(mix);
+                mix = m.process();
+            }
+        }
+
+        self.peak = self.peak.max(mix.abs());
+
+        (mix, l * self.gain, r * self.gain)
+    }
+
+    /// Converts a mixed sample into the output format, hard-clipping it to
+    /// `[-1, 1]` first so out-of-range mixes saturate instead of wrapping in
+    /// integer formats.
+    fn to_format(x: SampleT) -> SF {
+        SF::from_sample(x.max(-1.0).min(1.0))
+    }
+
+    /// Unregisters and removes every sound that has finished playing.
+    fn remove_finished(&mut self) {
+        let finished: Vec<usize> = self
+            .sounds
+            .iter()
+            .filter(|(_, s)| s.lock().map(|s| s.is_finished()).unwrap_or(false))
+            .map(|(id, _)| *id)
+            .collect();
+
+        for id in finished {
+            if let Some(Ok(mut s)) = self.sounds.get(&id).map(|s| s.lock()) {
+                s.unregister();
+            }
+            self.remove_sound(id);
+        }
+    }
+
+    fn get_id(&mut self) -> usize {
+        let old = self.id_counter;
+
+        self.id_counter += 1;
+
+        old
+    }
+}
+
+impl<SF> Channel<SF> for BaeChannel<SF>
+where
+    SF: SampleFormat,
+{
+    fn set_process_time(&mut self, d: Duration) {
+        let n = (d.as_secs_f64() * self.sample_rate as MathT).round() as usize;
+
+        self.process_samples = n;
+        self.clear_output();
+        self.output.reserve(n);
+        self.output_left.reserve(n);
+        self.output_right.reserve(n);
+    }
+
+    fn get_output(&self) -> &Vec<SF> {
+        &self.output
+    }
+
+    fn set_gain(&mut self, gain: MathT) {
+        self.gain = gain as SampleT;
+    }
+
+    fn process(&mut self) {
+        self.clear_output();
+        self.output.resize_with(self.process_samples, SF::default);
+        self.output_left
+            .resize_with(self.process_samples, SF::default);
+        self.output_right
+            .resize_with(self.process_samples, SF::default);
+
+        let idle = self.is_idle();
+        self.rms = SampleT::default();
+
+        if idle && self.inserts.is_empty() {
+            return;
+        }
+
+        let mut sum_squares = SampleT::default();
+
+        let mut output = std::mem::take(&mut self.output);
+        let mut output_left = std::mem::take(&mut self.output_left);
+        let mut output_right = std::mem::take(&mut self.output_right);
+
+        for ((sample, left), right) in output
+            .iter_mut()
+            .zip(output_left.iter_mut())
+            .zip(output_right.iter_mut())
+        {
+            let (mix, l, r) = self.mix_sample(idle);
+            sum_squares += mix * mix;
+
+            *sample = Self::to_format(mix);
+            *left = Self::to_format(l);
+            *right = Self::to_format(r);
+        }
+
+        self.output = output;
+        self.output_left = output_left;
+        self.output_right = output_right;
+
+        if self.process_samples > 0 {
+            self.rms = (sum_squares / self.process_samples as SampleT).sqrt();
+        }
+
+        self.remove_finished();
+    }
+
+    fn add_sound(&mut self, sound: &mut SoundSP) -> Result<usize, ChannelError> {
+        if matches!(self.max_sounds, Some(max) if self.sounds.len() >= max) {
+            return Err(ChannelError::ChannelFull);
+        }
+
+        let id = self.get_id();
+        if let Ok(mut s) = sound.lock() {
+            s.register(id);
+        }
+        self.sounds.insert(id, sound.clone());
+
+        Ok(id)
+    }
+
+    fn remove_sound(&mut self, id: usize) {
+        self.sounds.remove(&id);
+        self.pans.remove(&id);
+        self.soloed.remove(&id);
+    }
+}
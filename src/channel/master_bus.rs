@@ -0,0 +1,171 @@
+//! # Master Bus
+//!
+//! Mixing of several [`Channel`]s into a single master output.
+//!
+//! [`Channel`]: ../trait.Channel.html
+
+use super::*;
+
+/// Trait for sample formats a [`MasterBus`] can mix. [`SampleFormat`] only
+/// converts samples into the format, so mixing needs the reverse conversion
+/// to sum the outputs of each [`Channel`].
+///
+/// A [`BaeChannel`] converts its mono mix into every channel of its output
+/// format, so multichannel formats convert back by averaging their channels.
+///
+/// [`MasterBus`]: struct.MasterBus.html
+/// [`SampleFormat`]: https://docs.rs/bae_sf/latest/bae_sf/trait.SampleFormat.html
+/// [`Channel`]: ../trait.Channel.html
+/// [`BaeChannel`]: ../bae_channel/struct.BaeChannel.html
+pub trait MixFormat: SampleFormat {
+    /// Converts the sample back into a [`SampleT`].
+    ///
+    /// [`SampleT`]: https://docs.rs/bae_types/latest/bae_types/type.SampleT.html
+    fn to_sample(&self) -> SampleT;
+}
+
+impl MixFormat for Mono {
+    fn to_sample(&self) -> SampleT {
+        self.mono
+    }
+}
+
+impl MixFormat for Stereo {
+    fn to_sample(&self) -> SampleT {
+        (self.left + self.right) * 0.5
+    }
+}
+
+/// Struct summing the outputs of several [`Channel`]s into a single master
+/// output.
+///
+/// Each call to [`process`] processes every channel and mixes their outputs,
+/// applying the master gain and hard-clipping the mix to `[-1, 1]` as
+/// [`BaeChannel`] does. The channels must all render the same number of
+/// samples per call, so they should be given the same process time.
+///
+/// [`Channel`]: ../trait.Channel.html
+/// [`BaeChannel`]: ../bae_channel/struct.BaeChannel.html
+/// [`process`]: struct.MasterBus.html#method.process
+pub struct MasterBus<SF>
+where
+    SF: MixFormat,
+{
+    channels: Vec<Box<dyn Channel<SF>>>,
+    gain: SampleT,
+    output: Vec<SF>,
+}
+
+impl<SF> MasterBus<SF>
+where
+    SF: MixFormat,
+{
+    /// Creates a new, empty [`MasterBus`] with the given master gain.
+    ///
+    /// [`MasterBus`]: struct.MasterBus.html
+    pub fn new(gain: MathT) -> Self {
+        MasterBus {
+            channels: Vec::new(),
+            gain: gain as SampleT,
+            output: Vec::new(),
+        }
+    }
+
+    /// Adds a [`Channel`] to the bus, returning its index.
+    ///
+    /// [`Channel`]: ../trait.Channel.html
+    pub fn add_channel(&mut self, channel: Box<dyn Channel<SF>>) -> usize {
+        self.channels.push(channel);
+        self.channels.len() - 1
+    }
+
+    /// Removes and returns the [`Channel`] at the given index, or returns
+    /// `None` if there is no channel at that index. The indices of later
+    /// channels shift down by one.
+    ///
+    /// [`Channel`]: ../trait.Channel.html
+    pub fn remove_channel(&mut self, index: usize) -> Option<Box<dyn Channel<SF>>> {
+        if index < self.channels.len() {
+            Some(self.channels.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of [`Channel`]s on the bus.
+    ///
+    /// [`Channel`]: ../trait.Channel.html
+    pub fn channel_count(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Returns a mutable reference to the [`Channel`] at the given index.
+    ///
+    /// [`Channel`]: ../trait.Channel.html
+    pub fn get_channel_mut(&mut self, index: usize) -> Option<&mut Box<dyn Channel<SF>>> {
+        self.channels.get_mut(index)
+    }
+
+    /// Returns the master gain.
+    pub fn get_gain(&self) -> MathT {
+        self.gain as MathT
+    }
+
+    /// Sets the master gain.
+    pub fn set_gain(&mut self, gain: MathT) {
+        self.gain = gain as SampleT;
+    }
+
+    /// Returns a reference to the master output rendered by the last call to
+    /// [`process`].
+    ///
+    /// [`process`]: struct.MasterBus.html#method.process
+    pub fn get_output(&self) -> &Vec<SF> {
+        &self.output
+    }
+
+    /// Processes every [`Channel`] and mixes their outputs into the master
+    /// output, which is as long as the output of each channel, or empty if
+    /// there are no channels.
+    ///
+    /// If any channel's output differs in length from the first channel's,
+    /// the master output is left empty and
+    /// [`ChannelError::OutputLengthMismatch`] is returned with the index of
+    /// the offending channel. Every channel is processed regardless.
+    ///
+    /// [`Channel`]: ../trait.Channel.html
+    /// [`ChannelError::OutputLengthMismatch`]: ../enum.ChannelError.html#variant.OutputLengthMismatch
+    pub fn process(&mut self) -> Result<(), ChannelError> {
+        self.output.clear();
+
+        for c in &mut self.channels {
+            c.process();
+        }
+
+        let len = match self.channels.first() {
+            Some(c) => c.get_output().len(),
+            None => return Ok(()),
+        };
+        if let Some(i) = self
+            .channels
+            .iter()
+            .position(|c| c.get_output().len() != len)
+        {
+            return Err(ChannelError::OutputLengthMismatch(i));
+        }
+
+        self.output.reserve(len);
+        for i in 0..len {
+            let mix: SampleT = self
+                .channels
+                .iter()
+                .map(|c| c.get_output()[i].to_sample())
+                .sum();
+
+            self.output
+                .push(SF::from_sample((mix * self.gain).max(-1.0).min(1.0)));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+//! # Real-time Channel
+//!
+//! A wrapper around [`BaeChannel`] for rendering directly from the callback
+//! of an audio output, such as those provided by `cpal`.
+//!
+//! [`BaeChannel`]: ../bae_channel/struct.BaeChannel.html
+
+use super::*;
+
+/// Wrapper around a [`BaeChannel`] filling interleaved `f32` buffers on
+/// demand, instead of rendering a fixed amount of samples per call to
+/// [`process`].
+///
+/// # Real-time safety
+///
+/// [`fill`] never allocates, provided the buffers passed to it hold no more
+/// than the number of frames given to [`new`]; larger buffers are rendered
+/// in several chunks. It does lock each [`Sound`] in the channel, so any
+/// other thread sharing a [`SoundSP`] must only hold it briefly, and the
+/// sounds themselves must not allocate or block while processing. Changes
+/// made through [`get_channel_mut`], such as adding sounds or changing the
+/// process time, may allocate and belong outside the audio callback.
+///
+/// [`BaeChannel`]: ../bae_channel/struct.BaeChannel.html
+/// [`process`]: ../trait.Channel.html#tymethod.process
+/// [`fill`]: struct.RealtimeChannel.html#method.fill
+/// [`new`]: struct.RealtimeChannel.html#method.new
+/// [`get_channel_mut`]: struct.RealtimeChannel.html#method.get_channel_mut
+/// [`Sound`]: ../../trait.Sound.html
+/// [`SoundSP`]: ../type.SoundSP.html
+pub struct RealtimeChannel {
+    channel: BaeChannel<Mono>,
+    max_frames: usize,
+}
+
+impl RealtimeChannel {
+    /// Wraps the given channel, reserving space to render up to `max_frames`
+    /// frames per chunk. A `max_frames` of `0` is treated as `1`.
+    pub fn new(mut channel: BaeChannel<Mono>, max_frames: usize) -> Self {
+        let max_frames = max_frames.max(1);
+        channel.set_process_samples(max_frames);
+
+        RealtimeChannel {
+            channel,
+            max_frames,
+        }
+    }
+
+    /// Returns a reference to the wrapped channel.
+    pub fn get_channel(&self) -> &BaeChannel<Mono> {
+        &self.channel
+    }
+
+    /// Returns a mutable reference to the wrapped channel.
+    pub fn get_channel_mut(&mut self) -> &mut BaeChannel<Mono> {
+        &mut self.channel
+    }
+
+    /// Fills the given buffer with interleaved frames of `channels` samples
+    /// each, rendering as many frames as fit. With one channel the mono output
+    /// of the [`BaeChannel`] is written; with more, the first two channels of
+    /// each frame receive its stereo outputs and any others are silent. A
+    /// trailing partial frame and buffers with zero channels are filled with
+    /// silence.
+    ///
+    /// [`BaeChannel`]: ../bae_channel/struct.BaeChannel.html
+    pub fn fill(&mut self, buffer: &mut [f32], channels: usize) {
+        for s in buffer.iter_mut() {
+            *s = 0.0;
+        }
+
+        if channels == 0 {
+            return;
+        }
+
+        for chunk in buffer.chunks_mut(self.max_frames * channels) {
+            let frames = chunk.len() / channels;
+
+            self.channel.set_process_samples(frames);
+            self.channel.process();
+
+            let mono = self.channel.get_output();
+            let (left, right) = self.channel.get_output_stereo();
+
+            for (i, frame) in chunk.chunks_exact_mut(channels).enumerate() {
+                if channels == 1 {
+                    frame[0] = mono[i].mono;
+                } else {
+                    frame[0] = left[i].mono;
+                    frame[1] = right[i].mono;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! # Voice Manager
+//!
+//! Polyphonic voice allocation on top of a [`BaeChannel`], for playing the
+//! same patch at several pitches at once.
+//!
+//! [`BaeChannel`]: ../bae_channel/struct.BaeChannel.html
+
+use super::*;
+
+use std::collections::VecDeque;
+
+/// Alias for the closure creating a new voice for a [`VoiceManager`] playing
+/// the given frequency.
+///
+/// [`VoiceManager`]: struct.VoiceManager.html
+pub type VoiceFactory = Box<dyn Fn(MathT) -> SoundSP + Send>;
+
+/// Struct allocating voices within a [`BaeChannel`].
+///
+/// Each call to [`note_on`] creates a new voice from the factory and adds it
+/// to the channel. Once the maximum number of voices is playing, the oldest
+/// voice is stolen to make room: it is removed from the channel before the
+/// new voice is added, so stealing also works in a channel limited by
+/// [`BaeChannel::set_max_sounds`]. Voices ended by [`note_off`] are muted, so
+/// [`BaeSound`]s fade out over their mute ramp, and are removed from the
+/// channel once the release time set by [`set_release`] has been processed.
+/// Released voices still occupy a slot in the channel until then, so if the
+/// channel is full when a voice starts, the oldest released voice is cut short
+/// to make room.
+///
+/// [`BaeChannel`]: ../bae_channel/struct.BaeChannel.html
+/// [`BaeChannel::set_max_sounds`]: ../bae_channel/struct.BaeChannel.html#method.set_max_sounds
+/// [`BaeSound`]: ../../bae_sound/struct.BaeSound.html
+/// [`note_on`]: struct.VoiceManager.html#method.note_on
+/// [`note_off`]: struct.VoiceManager.html#method.note_off
+/// [`set_release`]: struct.VoiceManager.html#method.set_release
+pub struct VoiceManager<SF>
+where
+    SF: SampleFormat,
+{
+    channel: BaeChannel<SF>,
+    factory: VoiceFactory,
+    max_voices: usize,
+    voices: VecDeque<(usize, MathT, SoundSP)>,
+    releasing: Vec<(usize, usize, SoundSP)>,
+    release: usize,
+    sample_rate: MathT,
+}
+
+impl<SF> VoiceManager<SF>
+where
+    SF: SampleFormat,
+{
+    /// Creates a new [`VoiceManager`] over a new [`BaeChannel`] with the given
+    /// gain and sample rate, playing at most `max_voices` voices at a time,
+    /// treating `0` as `1`. The release time defaults to [`DEFAULT_RAMP`],
+    /// matching the default mute ramp of [`BaeSound`].
+    ///
+    /// [`VoiceManager`]: struct.VoiceManager.html
+    /// [`BaeChannel`]: ../bae_channel/struct.BaeChannel.html
+    /// [`DEFAULT_RAMP`]: ../../constant.DEFAULT_RAMP.html
+    /// [`BaeSound`]: ../../bae_sound/struct.BaeSound.html
+    pub fn new<F>(gain: MathT, sample_rate: MathT, max_voices: usize, factory: F) -> Self
+    where
+        F: 'static + Fn(MathT) -> SoundSP + Send,
+    {
+        VoiceManager {
+            channel: BaeChannel::new(gain, sample_rate),
+            factory: Box::new(factory),
+            max_voices: max_voices.max(1),
+            voices: VecDeque::new(),
+            releasing: Vec::new(),
+            release: ramp_samples(DEFAULT_RAMP, sample_rate),
+            sample_rate,
+        }
+    }
+
+    /// Returns a reference to the channel the voices are played through.
+    pub fn get_channel(&self) -> &BaeChannel<SF> {
+        &self.channel
+    }
+
+    /// Returns a mutable reference to the channel the voices are played
+    /// through, e.g. to add inserts or change the process time.
+    pub fn get_channel_mut(&mut self) -> &mut BaeChannel<SF> {
+        &mut self.channel
+    }
+
+    /// Returns the maximum number of voices played at a time.
+    pub fn get_max_voices(&self) -> usize {
+        self.max_voices
+    }
+
+    /// Sets the time released voices keep playing before they are removed
+    /// from the channel. This should cover the fade of the voices, such as
+    /// the mute ramp of a [`BaeSound`].
+    ///
+    /// [`BaeSound`]: ../../bae_sound/struct.BaeSound.html
+    pub fn set_release(&mut self, d: Duration) {
+        self.release = ramp_samples(d, self.sample_rate);
+    }
+
+    /// Returns the number of voices playing, excluding released voices.
+    pub fn voice_count(&self) -> usize {
+        self.voices.len()
+    }
+
+    /// Returns the frequencies of the playing voices, from oldest to newest.
+    pub fn active_frequencies(&self) -> Vec<MathT> {
+        self.voices.iter().map(|(_, f, _)| *f).collect()
+    }
+
+    /// Starts a new voice at the given frequency, stealing the oldest voice if
+    /// the maximum number of voices is already playing. The stolen voice is
+    /// removed from the channel at once, before the new voice is added, and
+    /// if the channel is still full, released voices are removed from oldest
+    /// to newest until the new voice fits. Returns the id of the new voice
+    /// within the channel.
+    pub fn note_on(&mut self, freq: MathT) -> Result<usize, ChannelError> {
+        if self.voices.len() >= self.max_voices {
+            if let Some((id, _, sound)) = self.voices.pop_front() {
+                Self::remove_voice(&mut self.channel, id, &sound);
+            }
+        }
+
+        let mut sound = (self.factory)(freq);
+        let id = loop {
+            match self.channel.add_sound(&mut sound) {
+                Err(ChannelError::ChannelFull) if !self.releasing.is_empty() => {
+                    let (id, _, released) = self.releasing.remove(0);
+                    Self::remove_voice(&mut self.channel, id, &released);
+                }
+                result => break result?,
+            }
+        };
+        self.voices.push_back((id, freq, sound));
+
+        Ok(id)
+    }
+
+    /// Releases the oldest voice playing the given frequency. Returns `false`
+    /// if no voice is playing it.
+    pub fn note_off(&mut self, freq: MathT) -> bool {
+        match self.voices.iter().position(|(_, f, _)| *f == freq) {
+            Some(i) => {
+                if let Some((id, _, sound)) = self.voices.remove(i) {
+                    self.release_voice(id, sound);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Processes the channel as with [`Channel::process`], then removes any
+    /// released voices whose release time has elapsed.
+    ///
+    /// [`Channel::process`]: ../trait.Channel.html#tymethod.process
+    pub fn process(&mut self) {
+        self.channel.process();
+        let processed = self.channel.get_output().len();
+
+        for (_, remaining, _) in &mut self.releasing {
+            *remaining = remaining.saturating_sub(processed);
+        }
+
+        let channel = &mut self.channel;
+        self.releasing.retain(|(id, remaining, sound)| {
+            if *remaining == 0 {
+                Self::remove_voice(channel, *id, sound);
+            }
+            *remaining > 0
+        });
+    }
+
+    fn release_voice(&mut self, id: usize, sound: SoundSP) {
+        if let Ok(mut s) = sound.lock() {
+            if !s.is_muted() {
+                s.toggle_mute();
+            }
+        }
+
+        self.releasing.push((id, self.release, sound));
+    }
+
+    /// Unregisters the given voice and removes it from the channel.
+    fn remove_voice(channel: &mut BaeChannel<SF>, id: usize, sound: &SoundSP) {
+        if let Ok(mut s) = sound.lock() {
+            s.unregister();
+        }
+        channel.remove_sound(id);
+    }
+}
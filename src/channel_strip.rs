@@ -0,0 +1,198 @@
+//! # Channel Strip
+//!
+//! Module containing a [`Sound`] bundling the processing commonly applied to
+//! every channel of a mix: input trim, a three-band equalizer, a compressor,
+//! and output trim.
+//!
+//! [`Sound`]: ../trait.Sound.html
+
+use super::*;
+
+use crate::modifiers::*;
+use bae_mod::Modifier;
+use std::time::Duration;
+
+/// The sections of a [`ChannelStrip`] that can be individually enabled or
+/// bypassed.
+///
+/// [`ChannelStrip`]: struct.ChannelStrip.html
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StripSection {
+    /// The low-shelf band of the equalizer.
+    LowShelf,
+    /// The peaking band of the equalizer.
+    Peak,
+    /// The high-shelf band of the equalizer.
+    HighShelf,
+    /// The compressor.
+    Compressor,
+}
+
+/// Struct implementing a channel strip. The input is processed through the
+/// input trim, the low-shelf, peak, and high-shelf [`Biquad`]s, the
+/// [`Compressor`], then the output trim. Each section other than the trims can
+/// be bypassed with [`set_enabled`].
+///
+/// [`Biquad`]: ../modifiers/biquad/struct.Biquad.html
+/// [`Compressor`]: ../modifiers/compressor/struct.Compressor.html
+/// [`set_enabled`]: struct.ChannelStrip.html#method.set_enabled
+#[derive(Clone)]
+pub struct ChannelStrip {
+    low_shelf: Biquad,
+    peak: Biquad,
+    high_shelf: Biquad,
+    compressor: Compressor,
+    enabled: [bool; 4],
+    input_trim: SampleT,
+    output_trim: SampleT,
+    id: Option<usize>,
+    is_muted: bool,
+    is_paused: bool,
+}
+
+impl ChannelStrip {
+    /// Constructs a new [`ChannelStrip`] with all sections enabled and set to
+    /// neutral values: flat 100Hz, 1kHz, and 10kHz bands, a 4:1 compressor
+    /// with a threshold of -12dB, and unity trims. Configure the sections
+    /// through the accessors.
+    ///
+    /// [`ChannelStrip`]: struct.ChannelStrip.html
+    pub fn new(sample_rate: MathT) -> Self {
+        ChannelStrip {
+            low_shelf: Biquad::low_shelf(100.0, 0.707, 0.0, sample_rate),
+            peak: Biquad::peak(1000.0, 0.707, 0.0, sample_rate),
+            high_shelf: Biquad::high_shelf(10_000.0, 0.707, 0.0, sample_rate),
+            compressor: Compressor::new(
+                -12.0,
+                4.0,
+                Duration::from_millis(10),
+                Duration::from_millis(100),
+                sample_rate,
+            ),
+            enabled: [true; 4],
+            input_trim: 1.0,
+            output_trim: 1.0,
+            id: None,
+            is_muted: false,
+            is_paused: false,
+        }
+    }
+
+    /// Returns whether the given section is processed.
+    pub fn is_enabled(&self, section: StripSection) -> bool {
+        self.enabled[section as usize]
+    }
+
+    /// Sets whether the given section is processed or bypassed.
+    pub fn set_enabled(&mut self, section: StripSection, enabled: bool) {
+        self.enabled[section as usize] = enabled;
+    }
+
+    /// Returns a mutable reference to the low-shelf band of the equalizer.
+    pub fn get_low_shelf_mut(&mut self) -> &mut Biquad {
+        &mut self.low_shelf
+    }
+
+    /// Returns a mutable reference to the peaking band of the equalizer.
+    pub fn get_peak_mut(&mut self) -> &mut Biquad {
+        &mut self.peak
+    }
+
+    /// Returns a mutable reference to the high-shelf band of the equalizer.
+    pub fn get_high_shelf_mut(&mut self) -> &mut Biquad {
+        &mut self.high_shelf
+    }
+
+    /// Returns a mutable reference to the compressor.
+    pub fn get_compressor_mut(&mut self) -> &mut Compressor {
+        &mut self.compressor
+    }
+
+    /// Returns the linear gain applied to the input.
+    pub fn get_input_trim(&self) -> MathT {
+        self.input_trim as MathT
+    }
+
+    /// Sets the linear gain applied to the input.
+    pub fn set_input_trim(&mut self, g: MathT) {
+        self.input_trim = g as SampleT;
+    }
+
+    /// Returns the linear gain applied to the output.
+    pub fn get_output_trim(&self) -> MathT {
+        self.output_trim as MathT
+    }
+
+    /// Sets the linear gain applied to the output.
+    pub fn set_output_trim(&mut self, g: MathT) {
+        self.output_trim = g as SampleT;
+    }
+}
+
+impl Sound for ChannelStrip {
+    fn toggle_pause(&mut self) {
+        self.is_paused = !self.is_paused;
+    }
+
+    fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    fn toggle_mute(&mut self) {
+        self.is_muted = !self.is_muted;
+    }
+
+    fn is_muted(&self) -> bool {
+        self.is_muted
+    }
+
+    /// Sets the output trim, as with [`set_output_trim`].
+    ///
+    /// [`set_output_trim`]: struct.ChannelStrip.html#method.set_output_trim
+    fn set_gain(&mut self, g: MathT) {
+        self.set_output_trim(g);
+    }
+
+    fn get_gain(&self) -> MathT {
+        self.get_output_trim()
+    }
+
+    fn register(&mut self, id: usize) {
+        self.id = Some(id);
+    }
+
+    fn unregister(&mut self) {
+        self.id = None;
+    }
+
+    fn process(&mut self, input: SampleT) -> SampleT {
+        if self.is_paused {
+            return Default::default();
+        }
+
+        let mut out = input * self.input_trim;
+
+        if self.is_enabled(StripSection::LowShelf) {
+            out = self.low_shelf.process(out);
+        }
+        if self.is_enabled(StripSection::Peak) {
+            out = self.peak.process(out);
+        }
+        if self.is_enabled(StripSection::HighShelf) {
+            out = self.high_shelf.process(out);
+        }
+        if self.is_enabled(StripSection::Compressor) {
+            out = self.compressor.process(out);
+        }
+
+        if self.is_muted {
+            Default::default()
+        } else {
+            out * self.output_trim
+        }
+    }
+
+    fn get_id(&self) -> Option<usize> {
+        self.id
+    }
+}
@@ -12,8 +12,9 @@ use super::*;
 use bae_mod::*;
 
 use petgraph::graph;
-use std::collections::VecDeque;
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// Alias for the graph type used by [`ComplexSound`].
 ///
@@ -25,11 +26,47 @@ pub type Graph = graph::DiGraph<BlockSP, ()>;
 /// [`ComplexSound`]: struct.ComplexSound.html
 pub type GraphNode = graph::NodeIndex;
 
+/// The output magnitude above which the stability guard of a [`ComplexSound`]
+/// considers a block to be unstable. This is 60dB above full scale.
+///
+/// [`ComplexSound`]: struct.ComplexSound.html
+pub const STABILITY_THRESHOLD: SampleT = 1000.0;
+
 /// Alias for the container storing the order of processing [`GraphNode`]s.
 ///
 /// [`GraphNode`]: type.GraphNode.html
 pub type ProcessOrder = VecDeque<GraphNode>;
 
+/// Error returned by [`ComplexSound::from_graph_string`] when the given text
+/// is not a valid graph. Each variant holds the 1-based number of the line
+/// the error was found on.
+///
+/// [`ComplexSound::from_graph_string`]: struct.ComplexSound.html#method.from_graph_string
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// The line is not a recognized record, or a field could not be parsed.
+    InvalidLine(usize),
+    /// The registry returned no block for the named kind.
+    UnknownBlock(usize, String),
+    /// A node is out of order, or an edge refers to a node that does not
+    /// exist.
+    InvalidNode(usize),
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidLine(l) => write!(f, "invalid record on line {}", l),
+            ParseError::UnknownBlock(l, name) => {
+                write!(f, "unknown block kind `{}` on line {}", name, l)
+            }
+            ParseError::InvalidNode(l) => write!(f, "invalid node index on line {}", l),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 /// Type implementing the ability to run multiple [`Generator`]s and
 /// [`Modifier`]s within a single object, granting the ability to create complex
 /// systems like those found in digital synthesizers.
@@ -42,6 +79,18 @@ pub struct ComplexSound {
     process_order: ProcessOrder,
     input_gain: GraphNode,
     output_gain: GraphNode,
+    gains: (MathT, MathT),
+    feed_forward: bool,
+    block_size: usize,
+    block_inputs: Vec<Vec<SampleT>>,
+    metadata: HashMap<String, String>,
+    stability_guard: bool,
+    stability_trips: Vec<GraphNode>,
+    tripped_nodes: Vec<GraphNode>,
+    muted_edges: Vec<graph::EdgeIndex>,
+    edge_ramp: usize,
+    edge_ramps: BTreeMap<(GraphNode, GraphNode), (SampleT, SampleT)>,
+    has_processed: bool,
     id: Option<usize>,
     is_muted: bool,
     is_paused: bool,
@@ -53,19 +102,32 @@ impl ComplexSound {
     ///
     /// [`ComplexSound`]: struct.ComplexSound.html
     pub fn new(input_gain: MathT, output_gain: MathT) -> Self {
+        let gains = (input_gain, output_gain);
         let mut graph = Graph::new();
-        let input_gain = graph.add_node(Arc::new(BaeBlock::from_modifier(
-            Gain::new(input_gain as SampleT),
-        )));
-        let output_gain = graph.add_node(Arc::new(BaeBlock::from_modifier(
-            Gain::new(output_gain as SampleT),
-        )));
+        let input_gain = graph.add_node(Arc::new(Mutex::new(BaeBlock::from_modifier(Gain::new(
+            input_gain as SampleT,
+        )))));
+        let output_gain = graph.add_node(Arc::new(Mutex::new(BaeBlock::from_modifier(Gain::new(
+            output_gain as SampleT,
+        )))));
 
         ComplexSound {
             graph,
             process_order: ProcessOrder::new(),
             input_gain,
             output_gain,
+            gains,
+            feed_forward: true,
+            block_size: 64,
+            block_inputs: Vec::new(),
+            metadata: HashMap::new(),
+            stability_guard: false,
+            stability_trips: Vec::new(),
+            tripped_nodes: Vec::new(),
+            muted_edges: Vec::new(),
+            edge_ramp: ramp_samples(DEFAULT_RAMP, DEFAULT_RAMP_SAMPLE_RATE),
+            edge_ramps: BTreeMap::new(),
+            has_processed: false,
             id: None,
             is_muted: false,
             is_paused: false,
@@ -101,23 +163,500 @@ impl ComplexSound {
     }
 
     /// Adds a new connection (edge) between the two given [`GraphNode`]s.
+    /// Once the sound has been processed, the connection fades in over the
+    /// edge ramp set by [`set_edge_ramp`].
     ///
     /// [`GraphNode`]: type.GraphNode.html
+    /// [`set_edge_ramp`]: struct.ComplexSound.html#method.set_edge_ramp
     pub fn add_connection(&mut self, from: GraphNode, to: GraphNode) {
+        let existed = self.graph.find_edge(from, to).is_some();
         self.graph.update_edge(from, to, ());
 
+        if self.is_ramping() {
+            match self.edge_ramps.get_mut(&(from, to)) {
+                Some(ramp) => ramp.1 = 1.0,
+                None if !existed => {
+                    self.edge_ramps.insert((from, to), (0.0, 1.0));
+                }
+                None => (),
+            }
+        }
+
         self.process_order();
     }
 
-    /// Removes a connection between the two given [`GraphNode`]s.
+    /// Removes a connection between the two given [`GraphNode`]s. Returns
+    /// `false` if there was no such connection.
+    ///
+    /// The connection is removed from the graph immediately, but once the
+    /// sound has been processed, it keeps delivering its signal while fading
+    /// out over the edge ramp set by [`set_edge_ramp`].
     ///
     /// [`GraphNode`]: type.GraphNode.html
-    pub fn remove_connection(&mut self, from: GraphNode, to: GraphNode) {
-        if let Some(e) = self.graph.find_edge(from, to) {
-            self.graph.remove_edge(e);
+    /// [`set_edge_ramp`]: struct.ComplexSound.html#method.set_edge_ramp
+    pub fn remove_connection(&mut self, from: GraphNode, to: GraphNode) -> bool {
+        let removed = match self.graph.find_edge(from, to) {
+            Some(e) => self.graph.remove_edge(e).is_some(),
+            None => false,
+        };
+
+        if removed && self.is_ramping() {
+            self.edge_ramps.entry((from, to)).or_insert((1.0, 0.0)).1 = 0.0;
         }
 
         self.process_order();
+
+        removed
+    }
+
+    /// Returns the length in samples of the fade applied to connections as
+    /// they are added or removed.
+    pub fn get_edge_ramp(&self) -> usize {
+        self.edge_ramp
+    }
+
+    /// Sets the length of the linear fade applied to connections added with
+    /// [`add_connection`] or removed with [`remove_connection`] during
+    /// playback, avoiding the click of an instant switch. The duration is
+    /// converted to samples at the given sample rate, and a duration of zero
+    /// switches instantly. Defaults to [`DEFAULT_RAMP`] converted at
+    /// [`DEFAULT_RAMP_SAMPLE_RATE`], as [`new`] takes no sample rate.
+    ///
+    /// Connections changed before the sound is first processed take effect
+    /// instantly, as there is no running signal to click.
+    ///
+    /// [`add_connection`]: struct.ComplexSound.html#method.add_connection
+    /// [`remove_connection`]: struct.ComplexSound.html#method.remove_connection
+    /// [`DEFAULT_RAMP`]: ../constant.DEFAULT_RAMP.html
+    /// [`DEFAULT_RAMP_SAMPLE_RATE`]: ../constant.DEFAULT_RAMP_SAMPLE_RATE.html
+    /// [`new`]: struct.ComplexSound.html#method.new
+    pub fn set_edge_ramp(&mut self, d: Duration, sample_rate: MathT) {
+        self.edge_ramp = ramp_samples(d, sample_rate);
+
+        if self.edge_ramp == 0 {
+            self.edge_ramps.clear();
+        }
+    }
+
+    /// Returns whether the connections of the graph form at least one
+    /// feedback loop.
+    ///
+    /// Feedback loops are processed deterministically: each connection leading
+    /// back to a block already processed during the current sample delivers
+    /// its value on the next sample instead, adding one sample of delay to the
+    /// loop.
+    pub fn has_cycle(&self) -> bool {
+        petgraph::algo::is_cyclic_directed(&self.graph)
+    }
+
+    /// Returns an iterator over every block in the graph, yielding the index
+    /// of its [`GraphNode`] and its [`Block::type_name`].
+    ///
+    /// [`GraphNode`]: type.GraphNode.html
+    /// [`Block::type_name`]: ../trait.Block.html#method.type_name
+    pub fn blocks(&self) -> impl Iterator<Item = (usize, &'static str)> + '_ {
+        self.graph.node_indices().map(move |n| {
+            let name = self.graph[n].lock().map(|b| b.type_name());
+            (n.index(), name.unwrap_or_default())
+        })
+    }
+
+    /// Returns an iterator over the indices of every [`GraphNode`] in the
+    /// graph, including the input and output gain nodes.
+    ///
+    /// [`GraphNode`]: type.GraphNode.html
+    pub fn nodes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.graph.node_indices().map(|n| n.index())
+    }
+
+    /// Returns an iterator over every connection in the graph, yielding the
+    /// indices of the source and destination [`GraphNode`]s.
+    ///
+    /// [`GraphNode`]: type.GraphNode.html
+    pub fn connections(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.graph
+            .raw_edges()
+            .iter()
+            .map(|e| (e.source().index(), e.target().index()))
+    }
+
+    /// Renders the graph in the Graphviz DOT language, e.g. for visualizing
+    /// it with `dot -Tsvg`. Each node is labelled with its
+    /// [`Block::type_name`], while the input and output gain nodes are
+    /// labelled with their gains and filled to stand out.
+    ///
+    /// [`Block::type_name`]: ../trait.Block.html#method.type_name
+    pub fn to_dot(&self) -> String {
+        let mut s = String::from("digraph {\n");
+
+        for (n, name) in self.blocks() {
+            let label = if n == self.input_gain.index() {
+                format!("input gain ({})", self.gains.0)
+            } else if n == self.output_gain.index() {
+                format!("output gain ({})", self.gains.1)
+            } else {
+                name.replace('\\', "\\\\").replace('"', "\\\"")
+            };
+
+            s += &format!("    {} [label=\"{}\"", n, label);
+            if n == self.input_gain.index() || n == self.output_gain.index() {
+                s += ", style=filled, fillcolor=lightblue";
+            }
+            s += "];\n";
+        }
+
+        for (from, to) in self.connections() {
+            s += &format!("    {} -> {};\n", from, to);
+        }
+
+        s + "}\n"
+    }
+
+    /// Returns an advisory estimate of the cost of processing a single sample
+    /// through the graph, summing the [`Block::cost_hint`] of every block that
+    /// is processed.
+    ///
+    /// [`Block::cost_hint`]: ../trait.Block.html#method.cost_hint
+    pub fn estimated_cost(&self) -> u32 {
+        self.process_order
+            .iter()
+            .filter_map(|n| self.graph[*n].lock().map(|b| b.cost_hint()).ok())
+            .sum()
+    }
+
+    /// Converts the sound into an equivalent [`BaeSound`] if the graph is a
+    /// single series path from the input gain to the output gain, which avoids
+    /// the overhead of traversing the graph during processing.
+    ///
+    /// The input gain node becomes the generator of the [`BaeSound`], and the
+    /// following blocks on the path become its [`Modifier`]s, ending with the
+    /// output gain node. Since the gains stay in the chain, the [`BaeSound`]
+    /// itself is created with unity gains. The pause and mute states and the
+    /// metadata are carried over.
+    ///
+    /// If the graph branches, merges, contains a cycle, or contains blocks not
+    /// on the path, the [`ComplexSound`] is returned unchanged as the error.
+    ///
+    /// [`BaeSound`]: ../bae_sound/struct.BaeSound.html
+    /// [`ComplexSound`]: struct.ComplexSound.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn try_into_bae_sound(self) -> Result<BaeSound, ComplexSound> {
+        let path = match self.linear_path() {
+            Some(p) => p,
+            None => return Err(self),
+        };
+
+        let mut blocks = path.iter().map(|n| self.graph[*n].clone());
+        let mut sound = BaeSound::new(1.0, 1.0, blocks.next().unwrap());
+        sound.extend_modifiers(blocks.collect());
+
+        for (key, value) in &self.metadata {
+            sound.set_meta(key, value);
+        }
+
+        if self.is_muted {
+            sound.toggle_mute();
+        }
+        if self.is_paused {
+            sound.toggle_pause();
+        }
+
+        Ok(sound)
+    }
+
+    /// Serializes the graph into a line-based text format readable by
+    /// [`from_graph_string`].
+    ///
+    /// Each line is a tab-separated record. The `input` and `output` records
+    /// hold the index and gain of the input and output gain nodes. Each
+    /// `node` record holds the index of a block, its [`Block::type_name`], and
+    /// its [`Block::parameters`] as `name=value` fields. Each `edge` record
+    /// holds the indices of the source and destination of a connection.
+    /// Metadata is not included.
+    ///
+    /// Blocks whose settings are not exposed through [`Block::parameters`],
+    /// such as those created with [`BaeBlock::from_modifier`], are written
+    /// without any fields. [`from_graph_string`] then leaves them as the
+    /// registry constructs them, so their settings are lost in the round
+    /// trip. Use [`BaeBlock::from_parameterized_modifier`] or similar for
+    /// blocks whose settings should be kept.
+    ///
+    /// [`from_graph_string`]: struct.ComplexSound.html#method.from_graph_string
+    /// [`Block::type_name`]: ../trait.Block.html#method.type_name
+    /// [`Block::parameters`]: ../trait.Block.html#method.parameters
+    /// [`BaeBlock::from_modifier`]: ../bae_block/struct.BaeBlock.html#method.from_modifier
+    /// [`BaeBlock::from_parameterized_modifier`]: ../bae_block/struct.BaeBlock.html#method.from_parameterized_modifier
+    pub fn to_graph_string(&self) -> String {
+        let mut s = format!(
+            "input\t{}\t{}\noutput\t{}\t{}\n",
+            self.input_gain.index(),
+            self.gains.0,
+            self.output_gain.index(),
+            self.gains.1
+        );
+
+        for n in self.graph.node_indices() {
+            if n == self.input_gain || n == self.output_gain {
+                continue;
+            }
+
+            if let Ok(block) = self.graph[n].lock() {
+                s += &format!("node\t{}\t{}", n.index(), block.type_name());
+                for (name, value) in block.parameters() {
+                    s += &format!("\t{}={}", name, value);
+                }
+                s += "\n";
+            }
+        }
+
+        for (from, to) in self.connections() {
+            s += &format!("edge\t{}\t{}\n", from, to);
+        }
+
+        s
+    }
+
+    /// Parses a graph written by [`to_graph_string`].
+    ///
+    /// Blocks can't be reconstructed from their type name alone, so each
+    /// `node` record is passed to `registry`, which returns a new [`Block`]
+    /// for the given type name, or `None` if it is unknown. The stored
+    /// parameters are then applied with [`Block::set_parameter`]. Nodes keep
+    /// their indices, so they must be listed in order. Empty lines and lines
+    /// starting with `#` are ignored, and missing `input` or `output` records
+    /// default to unity gain.
+    ///
+    /// [`to_graph_string`]: struct.ComplexSound.html#method.to_graph_string
+    /// [`Block`]: ../trait.Block.html
+    /// [`Block::set_parameter`]: ../trait.Block.html#method.set_parameter
+    pub fn from_graph_string<F>(s: &str, mut registry: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(&str) -> Option<BlockSP>,
+    {
+        let mut gains = (1.0, 1.0);
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split('\t').collect();
+            let index = |f: usize| {
+                fields
+                    .get(f)
+                    .and_then(|x| x.parse::<usize>().ok())
+                    .ok_or(ParseError::InvalidLine(line_number))
+            };
+            let gain = || {
+                fields
+                    .get(2)
+                    .and_then(|x| x.parse::<MathT>().ok())
+                    .ok_or(ParseError::InvalidLine(line_number))
+            };
+
+            match fields[0] {
+                "input" if fields.len() == 3 => {
+                    if index(1)? != 0 {
+                        return Err(ParseError::InvalidNode(line_number));
+                    }
+                    gains.0 = gain()?;
+                }
+                "output" if fields.len() == 3 => {
+                    if index(1)? != 1 {
+                        return Err(ParseError::InvalidNode(line_number));
+                    }
+                    gains.1 = gain()?;
+                }
+                "node" if fields.len() >= 3 => {
+                    let mut parameters = Vec::new();
+                    for f in &fields[3..] {
+                        let mut kv = f.splitn(2, '=');
+                        let name = kv.next().unwrap_or_default();
+                        let value = kv
+                            .next()
+                            .and_then(|v| v.parse::<MathT>().ok())
+                            .ok_or(ParseError::InvalidLine(line_number))?;
+                        parameters.push((name, value));
+                    }
+                    nodes.push((line_number, index(1)?, fields[2], parameters));
+                }
+                "edge" if fields.len() == 3 => {
+                    edges.push((line_number, index(1)?, index(2)?));
+                }
+                _ => return Err(ParseError::InvalidLine(line_number)),
+            }
+        }
+
+        let mut cs = ComplexSound::new(gains.0, gains.1);
+
+        for (line_number, index, name, parameters) in nodes {
+            if index != cs.graph.node_count() {
+                return Err(ParseError::InvalidNode(line_number));
+            }
+
+            let block = registry(name)
+                .ok_or_else(|| ParseError::UnknownBlock(line_number, name.to_owned()))?;
+            if let Ok(mut b) = block.lock() {
+                for (name, value) in parameters {
+                    b.set_parameter(name, value);
+                }
+            }
+
+            cs.add_block(block);
+        }
+
+        for (line_number, from, to) in edges {
+            if from >= cs.graph.node_count() || to >= cs.graph.node_count() {
+                return Err(ParseError::InvalidNode(line_number));
+            }
+
+            cs.add_connection(GraphNode::new(from), GraphNode::new(to));
+        }
+
+        Ok(cs)
+    }
+
+    /// Returns the number of samples each block processes at a time during
+    /// [`process_block`].
+    ///
+    /// [`process_block`]: struct.ComplexSound.html#method.process_block
+    pub fn get_block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Sets the number of samples each block processes at a time during
+    /// [`process_block`]. Larger sizes reduce the overhead of traversing the
+    /// graph at the cost of memory. A size of `0` is treated as `1`.
+    ///
+    /// [`process_block`]: struct.ComplexSound.html#method.process_block
+    pub fn set_block_size(&mut self, n: usize) {
+        self.block_size = n.max(1);
+    }
+
+    /// Processes the sound with silent input, filling `out` with the result.
+    ///
+    /// Rather than passing each sample through the whole graph in turn, every
+    /// block processes up to [`get_block_size`] samples before moving on to
+    /// the next block, so the graph is traversed once per buffer instead of
+    /// once per sample. The output is identical to repeatedly calling
+    /// [`process`].
+    ///
+    /// Graphs where a block feeds one processed before it, such as feedback
+    /// loops, rely on the single sample delay of [`process`], and are
+    /// processed one sample at a time instead, as are graphs with connections
+    /// still fading in or out and graphs with the [stability guard] enabled.
+    ///
+    /// [`get_block_size`]: struct.ComplexSound.html#method.get_block_size
+    /// [`process`]: ../trait.Sound.html#tymethod.process
+    /// [stability guard]: struct.ComplexSound.html#method.set_stability_guard
+    pub fn process_block(&mut self, out: &mut [SampleT]) {
+        if self.is_paused {
+            for s in out.iter_mut() {
+                *s = Default::default();
+            }
+            return;
+        }
+
+        if !self.feed_forward || !self.edge_ramps.is_empty() || self.stability_guard {
+            for s in out.iter_mut() {
+                *s = self.process(Default::default());
+            }
+            return;
+        }
+        self.has_processed = true;
+
+        self.block_inputs
+            .resize_with(self.graph.node_count(), Vec::new);
+
+        for chunk in out.chunks_mut(self.block_size) {
+            for s in chunk.iter_mut() {
+                *s = Default::default();
+            }
+            for b in &mut self.block_inputs {
+                b.clear();
+                b.resize(chunk.len(), Default::default());
+            }
+
+            for b in &self.process_order {
+                if let Ok(mut block) = self.graph[*b].lock() {
+                    for (y, x) in chunk.iter_mut().zip(&self.block_inputs[b.index()]) {
+                        block.prime_input(*x);
+                        *y = block.process();
+                    }
+                }
+
+                let mut neighbors = self.graph.neighbors(*b).detach();
+
+                while let Some(t) = neighbors.next(&self.graph) {
+                    for (x, y) in self.block_inputs[t.1.index()].iter_mut().zip(chunk.iter()) {
+                        *x += *y;
+                    }
+                }
+            }
+
+            if self.is_muted {
+                for s in chunk.iter_mut() {
+                    *s = Default::default();
+                }
+            }
+        }
+    }
+
+    /// Returns whether the stability guard is enabled.
+    pub fn get_stability_guard(&self) -> bool {
+        self.stability_guard
+    }
+
+    /// Enables or disables the stability guard. While enabled, any block whose
+    /// output exceeds [`STABILITY_THRESHOLD`] or is not finite during
+    /// [`process`] has that output replaced with silence, and every feedback
+    /// connection on a loop through the block is muted so runaway energy can't
+    /// keep building. The first time a block trips is recorded in
+    /// [`stability_trips`]; later trips of the same block are silenced without
+    /// being recorded again.
+    ///
+    /// Muted connections stay muted until [`reset_stability_guard`] is called
+    /// or the connections of the graph change, after which a block tripping
+    /// again is recorded anew.
+    ///
+    /// [`STABILITY_THRESHOLD`]: constant.STABILITY_THRESHOLD.html
+    /// [`process`]: ../trait.Sound.html#tymethod.process
+    /// [`stability_trips`]: struct.ComplexSound.html#method.stability_trips
+    /// [`reset_stability_guard`]: struct.ComplexSound.html#method.reset_stability_guard
+    pub fn set_stability_guard(&mut self, enabled: bool) {
+        self.stability_guard = enabled;
+    }
+
+    /// Returns the [`GraphNode`]s that tripped the stability guard, in the
+    /// order they did so.
+    ///
+    /// [`GraphNode`]: type.GraphNode.html
+    pub fn stability_trips(&self) -> &[GraphNode] {
+        &self.stability_trips
+    }
+
+    /// Unmutes every connection muted by the stability guard and clears the
+    /// recorded trips.
+    pub fn reset_stability_guard(&mut self) {
+        self.stability_trips.clear();
+        self.tripped_nodes.clear();
+        self.muted_edges.clear();
+    }
+
+    /// Returns the metadata value stored under the given key.
+    pub fn get_meta(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(|v| v.as_str())
+    }
+
+    /// Stores a metadata value, such as a display name or color, under the
+    /// given key. Metadata has no effect on processing.
+    pub fn set_meta(&mut self, key: &str, value: &str) {
+        self.metadata.insert(key.to_owned(), value.to_owned());
     }
 
     /// Returns a copy of the list of all nodes of the graph in the order in
@@ -132,6 +671,8 @@ impl ComplexSound {
     /// [`GraphNode`]: type.GraphNode.html
     fn process_order(&mut self) {
         self.process_order.clear();
+        self.tripped_nodes.clear();
+        self.muted_edges.clear();
 
         self.process_order
             .extend(self.graph.externals(petgraph::Direction::Incoming));
@@ -156,6 +697,104 @@ impl ComplexSound {
 
         self.process_order.push_front(self.input_gain);
         self.process_order.push_back(self.output_gain);
+
+        let mut position = vec![None; self.graph.node_count()];
+        for (i, n) in self.process_order.iter().enumerate() {
+            position[n.index()] = Some(i);
+        }
+
+        self.feed_forward = self.graph.raw_edges().iter().all(|e| {
+            match (position[e.source().index()], position[e.target().index()]) {
+                (Some(from), Some(to)) => from < to,
+                _ => true,
+            }
+        });
+    }
+
+    /// Returns whether connection changes should currently be faded.
+    fn is_ramping(&self) -> bool {
+        self.edge_ramp > 0 && self.has_processed
+    }
+
+    /// Advances every connection fade by a sample, dropping those that have
+    /// finished.
+    fn advance_edge_ramps(&mut self) {
+        let step = 1.0 / self.edge_ramp.max(1) as SampleT;
+
+        for (gain, target) in self.edge_ramps.values_mut() {
+            *gain = if *gain < *target {
+                (*gain + step).min(*target)
+            } else {
+                (*gain - step).max(*target)
+            };
+        }
+
+        self.edge_ramps.retain(|_, (gain, target)| gain != target);
+    }
+
+    /// Returns the nodes from the input gain to the output gain if the graph
+    /// consists of only that path, otherwise `None`.
+    fn linear_path(&self) -> Option<Vec<GraphNode>> {
+        let edges = |n, d| self.graph.edges_directed(n, d).count();
+
+        if edges(self.input_gain, petgraph::Direction::Incoming) != 0
+            || edges(self.output_gain, petgraph::Direction::Outgoing) != 0
+        {
+            return None;
+        }
+
+        let mut path = vec![self.input_gain];
+        let mut current = self.input_gain;
+
+        while current != self.output_gain {
+            if edges(current, petgraph::Direction::Outgoing) != 1 {
+                return None;
+            }
+
+            current = self.graph.neighbors(current).next()?;
+
+            if edges(current, petgraph::Direction::Incoming) != 1 || path.contains(&current) {
+                return None;
+            }
+
+            path.push(current);
+        }
+
+        if path.len() == self.graph.node_count() {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Mutes every feedback connection on a loop passing through the given
+    /// [`GraphNode`]. A connection is a feedback connection if it leads to a
+    /// block processed no later than its source.
+    ///
+    /// [`GraphNode`]: type.GraphNode.html
+    fn mute_feedback_through(&mut self, node: GraphNode) {
+        let graph = &self.graph;
+        let order = &self.process_order;
+        let position = |n: GraphNode| order.iter().position(|p| *p == n);
+
+        let feedback = graph.edge_indices().filter(|e| {
+            let (from, to) = graph.edge_endpoints(*e).unwrap();
+
+            let is_feedback = match (position(from), position(to)) {
+                (Some(f), Some(t)) => t <= f,
+                _ => false,
+            };
+
+            is_feedback
+                && petgraph::algo::has_path_connecting(graph, to, node, None)
+                && petgraph::algo::has_path_connecting(graph, node, from, None)
+        });
+
+        for e in feedback.collect::<Vec<_>>() {
+            if !self.muted_edges.contains(&e) {
+                self.muted_edges.push(e);
+            }
+        }
     }
 
     /// Removes duplicate [`GraphNode`]s from the process order to prevent parsing cycles
@@ -191,6 +830,20 @@ impl Sound for ComplexSound {
         self.is_muted
     }
 
+    /// Sets the gain of the output gain node. The node's [`Block`] is replaced
+    /// with a new one, so clones of its smart pointer keep the old gain.
+    ///
+    /// [`Block`]: ../trait.Block.html
+    fn set_gain(&mut self, g: MathT) {
+        self.gains.1 = g;
+        self.graph[self.output_gain] =
+            Arc::new(Mutex::new(BaeBlock::from_modifier(Gain::new(g as SampleT))));
+    }
+
+    fn get_gain(&self) -> MathT {
+        self.gains.1
+    }
+
     fn register(&mut self, id: usize) {
         self.id = Some(id);
     }
@@ -205,21 +858,60 @@ impl Sound for ComplexSound {
         }
 
         let mut out = Default::default();
+        let mut tripped = Vec::new();
 
-        BlockSP::get_mut(self.graph.node_weight_mut(self.input_gain).unwrap())
-            .unwrap()
-            .prime_input(input);
+        if let Ok(mut block) = self.graph[self.input_gain].lock() {
+            block.prime_input(input);
+        }
 
         for b in &self.process_order {
-            let block = BlockSP::get_mut(self.graph.node_weight_mut(*b).unwrap()).unwrap();
-            out = block.process();
+            out = self.graph[*b]
+                .lock()
+                .map(|mut block| block.process())
+                .unwrap_or_default();
+
+            if self.stability_guard && (!out.is_finite() || out.abs() > STABILITY_THRESHOLD) {
+                out = Default::default();
+                tripped.push(*b);
+            }
 
             let mut neighbors = self.graph.neighbors(*b).detach();
 
             while let Some(t) = neighbors.next(&self.graph) {
-                BlockSP::get_mut(self.graph.node_weight_mut(t.1).unwrap())
-                    .unwrap()
-                    .prime_input(out);
+                if self.muted_edges.contains(&t.0) {
+                    continue;
+                }
+
+                let gain = self.edge_ramps.get(&(*b, t.1)).map_or(1.0, |r| r.0);
+                if let Ok(mut block) = self.graph[t.1].lock() {
+                    block.prime_input(out * gain);
+                }
+            }
+
+            let fading = self
+                .edge_ramps
+                .range((*b, GraphNode::new(0))..)
+                .take_while(|((from, _), _)| from == b);
+
+            for ((_, to), (gain, _)) in fading {
+                if self.graph.find_edge(*b, *to).is_some() {
+                    continue;
+                }
+
+                if let Ok(mut block) = self.graph[*to].lock() {
+                    block.prime_input(out * *gain);
+                }
+            }
+        }
+
+        self.advance_edge_ramps();
+        self.has_processed = true;
+
+        for n in tripped {
+            if !self.tripped_nodes.contains(&n) {
+                self.tripped_nodes.push(n);
+                self.stability_trips.push(n);
+                self.mute_feedback_through(n);
             }
         }
 
@@ -0,0 +1,103 @@
+//! # Delay Line
+//!
+//! Module containing a feedback delay [`Block`], the building block of
+//! echoes and comb filters within a [`ComplexSound`] graph.
+//!
+//! [`Block`]: ../trait.Block.html
+//! [`ComplexSound`]: ../complex_sound/struct.ComplexSound.html
+
+use super::*;
+
+use std::time::Duration;
+
+/// Struct implementing a [`Block`] delaying its input by a whole number of
+/// samples. Each delayed sample is fed back into the delay line scaled by the
+/// feedback gain, so an impulse repeats every delay period, decaying by the
+/// feedback gain each time. Feedback gains with a magnitude of `1` or more
+/// never decay.
+///
+/// Within a [`ComplexSound`], a single [`DelayLine`] connected to both the
+/// input and output of the graph gives an echo without needing a feedback
+/// connection.
+///
+/// [`Block`]: ../trait.Block.html
+/// [`ComplexSound`]: ../complex_sound/struct.ComplexSound.html
+/// [`DelayLine`]: struct.DelayLine.html
+#[derive(Clone)]
+pub struct DelayLine {
+    buffer: SampleTrackT,
+    position: usize,
+    feedback: SampleT,
+    sample_rate: MathT,
+    input: SampleT,
+}
+
+impl DelayLine {
+    /// Constructs a new [`DelayLine`] with the given delay, truncated to a
+    /// whole number of samples and at least one sample long, and feedback
+    /// gain.
+    ///
+    /// [`DelayLine`]: struct.DelayLine.html
+    pub fn new(delay: Duration, sample_rate: MathT, feedback: SampleT) -> Self {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        let mut dl = DelayLine {
+            buffer: SampleTrackT::new(),
+            position: 0,
+            feedback,
+            sample_rate,
+            input: SampleT::default(),
+        };
+
+        dl.set_delay(delay);
+
+        dl
+    }
+
+    /// Returns the length of the delay in samples.
+    pub fn get_delay_samples(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Sets the delay, truncated to a whole number of samples and at least one
+    /// sample long. Clears the contents of the delay line.
+    pub fn set_delay(&mut self, delay: Duration) {
+        let len = ((delay.as_secs_f64() * self.sample_rate) as usize).max(1);
+
+        self.buffer = vec![SampleT::default(); len];
+        self.position = 0;
+    }
+
+    /// Returns the feedback gain.
+    pub fn get_feedback(&self) -> SampleT {
+        self.feedback
+    }
+
+    /// Sets the feedback gain.
+    pub fn set_feedback(&mut self, feedback: SampleT) {
+        self.feedback = feedback;
+    }
+}
+
+impl Block for DelayLine {
+    fn prime_input(&mut self, x: SampleT) {
+        self.input += x;
+    }
+
+    fn process(&mut self) -> SampleT {
+        let y = self.buffer[self.position];
+
+        self.buffer[self.position] = self.input + self.feedback * y;
+        self.position = (self.position + 1) % self.buffer.len();
+        self.input = SampleT::default();
+
+        y
+    }
+
+    fn reset(&mut self) {
+        for s in &mut self.buffer {
+            *s = SampleT::default();
+        }
+        self.position = 0;
+        self.input = SampleT::default();
+    }
+}
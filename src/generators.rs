@@ -0,0 +1,49 @@
+//! # Generators
+//!
+//! This module includes [`Generator`]s built on top of the Broad Audio Engine
+//! that are usable within a [`BaeBlock`] through
+//! [`BaeBlock::from_generator`].
+//!
+//! [`Generator`]: ../../generators/trait.Generator.html
+//! [`BaeBlock`]: ../bae_block/struct.BaeBlock.html
+//! [`BaeBlock::from_generator`]: ../bae_block/struct.BaeBlock.html#method.from_generator
+
+use super::*;
+
+use bae_gen::*;
+use std::time::Duration;
+
+pub mod formant;
+pub mod glide;
+pub mod harmonics;
+pub mod metronome;
+pub mod morph;
+pub mod quad_osc;
+pub mod sequencer;
+pub mod sweep;
+
+pub use formant::*;
+pub use glide::*;
+pub use harmonics::*;
+pub use metronome::*;
+pub use morph::*;
+pub use quad_osc::*;
+pub use sequencer::*;
+pub use sweep::*;
+
+/// Trait for oscillators whose frequency can be changed while running.
+pub trait FreqControllable {
+    /// Sets the frequency of the oscillator.
+    fn set_frequency(&mut self, f: MathT);
+
+    /// Returns the current frequency of the oscillator.
+    fn get_frequency(&self) -> MathT;
+}
+
+/// Trait for oscillators whose phase can be reset, e.g. to keep several LFOs
+/// phase-locked.
+pub trait PhaseResettable {
+    /// Resets the oscillator to the start of its cycle, as if it had just been
+    /// constructed.
+    fn reset_phase(&mut self);
+}
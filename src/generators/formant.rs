@@ -0,0 +1,172 @@
+//! # Formant
+//!
+//! A [`Generator`] synthesizing vowel-like tones by filtering a sawtooth
+//! through parallel band-pass resonators tuned to the formants of a vowel.
+//!
+//! [`Generator`]: ../../generators/trait.Generator.html
+
+use super::*;
+
+use crate::modifiers::Biquad;
+use bae_mod::Modifier;
+
+/// The vowels a [`Formant`] generator can produce.
+///
+/// [`Formant`]: struct.Formant.html
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Vowel {
+    /// The vowel in "father".
+    A,
+    /// The vowel in "bet".
+    E,
+    /// The vowel in "beet".
+    I,
+    /// The vowel in "boat".
+    O,
+    /// The vowel in "boot".
+    U,
+}
+
+impl Vowel {
+    /// Returns the frequency, amplitude in decibels, and bandwidth of each of
+    /// the vowel's five formants, based on the formant values of a bass voice.
+    pub fn formants(self) -> [(MathT, MathT, MathT); 5] {
+        match self {
+            Vowel::A => [
+                (600.0, 0.0, 60.0),
+                (1040.0, -7.0, 70.0),
+                (2250.0, -9.0, 110.0),
+                (2450.0, -9.0, 120.0),
+                (2750.0, -20.0, 130.0),
+            ],
+            Vowel::E => [
+                (400.0, 0.0, 40.0),
+                (1620.0, -12.0, 80.0),
+                (2400.0, -9.0, 100.0),
+                (2800.0, -12.0, 120.0),
+                (3100.0, -18.0, 120.0),
+            ],
+            Vowel::I => [
+                (250.0, 0.0, 60.0),
+                (1750.0, -30.0, 90.0),
+                (2600.0, -16.0, 100.0),
+                (3050.0, -22.0, 120.0),
+                (3340.0, -28.0, 120.0),
+            ],
+            Vowel::O => [
+                (400.0, 0.0, 40.0),
+                (750.0, -11.0, 80.0),
+                (2400.0, -21.0, 100.0),
+                (2600.0, -20.0, 120.0),
+                (2900.0, -40.0, 120.0),
+            ],
+            Vowel::U => [
+                (350.0, 0.0, 40.0),
+                (600.0, -20.0, 80.0),
+                (2400.0, -32.0, 100.0),
+                (2675.0, -28.0, 120.0),
+                (2950.0, -36.0, 120.0),
+            ],
+        }
+    }
+}
+
+/// Struct implementing a formant synthesizer. A sawtooth at the fundamental
+/// frequency is fed through one band-pass [`Biquad`] per formant of the
+/// selected [`Vowel`], and the filter outputs are summed scaled by each
+/// formant's amplitude.
+///
+/// [`Biquad`]: ../../modifiers/biquad/struct.Biquad.html
+/// [`Vowel`]: enum.Vowel.html
+#[derive(Clone)]
+pub struct Formant {
+    frequency: MathT,
+    sample_rate: MathT,
+    phase: MathT,
+    vowel: Vowel,
+    filters: [Biquad; 5],
+    gains: [MathT; 5],
+}
+
+impl Formant {
+    /// Constructs a new [`Formant`] generator with the given fundamental
+    /// frequency and vowel.
+    ///
+    /// [`Formant`]: struct.Formant.html
+    pub fn new(frequency: MathT, vowel: Vowel, sample_rate: MathT) -> Self {
+        let mut f = Formant {
+            frequency,
+            sample_rate,
+            phase: 0.0,
+            vowel,
+            filters: [Biquad::band_pass(1000.0, 1.0, sample_rate); 5],
+            gains: [0.0; 5],
+        };
+
+        f.set_vowel(vowel);
+
+        f
+    }
+
+    /// Returns the selected vowel.
+    pub fn get_vowel(&self) -> Vowel {
+        self.vowel
+    }
+
+    /// Selects the vowel to synthesize, retuning the resonators to its
+    /// formants.
+    pub fn set_vowel(&mut self, vowel: Vowel) {
+        self.vowel = vowel;
+
+        for (i, (freq, amp_db, bandwidth)) in vowel.formants().iter().enumerate() {
+            self.filters[i].set_frequency(*freq);
+            self.filters[i].set_q(freq / bandwidth);
+            self.gains[i] = db_to_linear(*amp_db);
+        }
+    }
+}
+
+impl FreqControllable for Formant {
+    fn set_frequency(&mut self, f: MathT) {
+        self.frequency = f;
+    }
+
+    fn get_frequency(&self) -> MathT {
+        self.frequency
+    }
+}
+
+impl PhaseResettable for Formant {
+    fn reset_phase(&mut self) {
+        self.phase = 0.0;
+    }
+}
+
+impl Parameterized for Formant {
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        vec![("frequency".to_owned(), self.get_frequency())]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        match name {
+            "frequency" => self.set_frequency(value),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl Generator for Formant {
+    fn process(&mut self) -> SampleT {
+        let saw = (2.0 * self.phase - 1.0) as SampleT;
+        self.phase = (self.phase + self.frequency / self.sample_rate).fract();
+
+        let mut y = 0.0;
+        for (filter, gain) in self.filters.iter_mut().zip(self.gains.iter()) {
+            y += filter.process(saw) as MathT * gain;
+        }
+
+        y as SampleT
+    }
+}
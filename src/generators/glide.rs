@@ -0,0 +1,150 @@
+//! # Glide
+//!
+//! A [`Generator`] wrapper that slides the frequency of an oscillator towards a
+//! target frequency over a configurable time, granting portamento for
+//! monophonic synthesizers.
+//!
+//! [`Generator`]: ../../generators/trait.Generator.html
+
+use super::*;
+
+/// Struct wrapping a [`FreqControllable`] [`Generator`], linearly ramping its
+/// frequency towards the target set with [`set_target_frequency`] over the
+/// configured glide time. A glide time of zero makes frequency changes
+/// instantaneous.
+///
+/// [`FreqControllable`]: ../trait.FreqControllable.html
+/// [`Generator`]: ../../generators/trait.Generator.html
+/// [`set_target_frequency`]: struct.Glide.html#method.set_target_frequency
+#[derive(Clone)]
+pub struct Glide<G>
+where
+    G: Generator + FreqControllable,
+{
+    osc: G,
+    glide_samples: MathT,
+    sample_rate: MathT,
+    start: MathT,
+    target: MathT,
+    position: MathT,
+}
+
+impl<G> Glide<G>
+where
+    G: Generator + FreqControllable,
+{
+    /// Constructs a new [`Glide`] around the given oscillator. The oscillator's
+    /// current frequency is used as the initial target.
+    ///
+    /// [`Glide`]: struct.Glide.html
+    pub fn new(osc: G, glide_time: Duration, sample_rate: MathT) -> Self {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        let f = osc.get_frequency();
+
+        Glide {
+            osc,
+            glide_samples: glide_time.as_secs_f64() * sample_rate,
+            sample_rate,
+            start: f,
+            target: f,
+            position: 0.0,
+        }
+    }
+
+    /// Returns the time taken to glide to a new target frequency.
+    pub fn get_glide_time(&self) -> Duration {
+        Duration::from_secs_f64(self.glide_samples / self.sample_rate)
+    }
+
+    /// Sets the time taken to glide to a new target frequency. A glide that is
+    /// already in progress restarts from the oscillator's current frequency.
+    pub fn set_glide_time(&mut self, d: Duration) {
+        self.glide_samples = d.as_secs_f64() * self.sample_rate;
+
+        let target = self.target;
+        self.set_target_frequency(target);
+    }
+
+    /// Returns the frequency the oscillator is gliding towards.
+    pub fn get_target_frequency(&self) -> MathT {
+        self.target
+    }
+
+    /// Sets the frequency the oscillator glides towards, starting from its
+    /// current frequency.
+    pub fn set_target_frequency(&mut self, f: MathT) {
+        self.start = self.osc.get_frequency();
+        self.target = f;
+        self.position = 0.0;
+
+        if self.glide_samples <= 0.0 {
+            self.osc.set_frequency(f);
+        }
+    }
+
+    /// Returns a reference to the wrapped oscillator.
+    pub fn get_inner(&self) -> &G {
+        &self.osc
+    }
+}
+
+impl<G> FreqControllable for Glide<G>
+where
+    G: Generator + FreqControllable,
+{
+    fn set_frequency(&mut self, f: MathT) {
+        self.set_target_frequency(f);
+    }
+
+    fn get_frequency(&self) -> MathT {
+        self.osc.get_frequency()
+    }
+}
+
+impl<G> PhaseResettable for Glide<G>
+where
+    G: Generator + FreqControllable + PhaseResettable,
+{
+    fn reset_phase(&mut self) {
+        self.osc.reset_phase();
+    }
+}
+
+impl<G> Parameterized for Glide<G>
+where
+    G: Generator + FreqControllable,
+{
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        vec![
+            ("frequency".to_owned(), self.get_target_frequency()),
+            ("glide_time".to_owned(), self.get_glide_time().as_secs_f64()),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        match name {
+            "frequency" => self.set_target_frequency(value),
+            "glide_time" => self.set_glide_time(Duration::from_secs_f64(value.max(0.0))),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl<G> Generator for Glide<G>
+where
+    G: Generator + FreqControllable,
+{
+    fn process(&mut self) -> SampleT {
+        if self.position < self.glide_samples {
+            self.position += 1.0;
+
+            let t = (self.position / self.glide_samples).min(1.0);
+            self.osc
+                .set_frequency(self.start + (self.target - self.start) * t);
+        }
+
+        self.osc.process()
+    }
+}
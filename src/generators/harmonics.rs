@@ -0,0 +1,129 @@
+//! # Harmonics
+//!
+//! An additive [`Generator`] summing sine partials at integer multiples of a
+//! fundamental, suitable for drawbar organ style tones.
+//!
+//! [`Generator`]: ../../generators/trait.Generator.html
+
+use super::*;
+
+/// Struct implementing a harmonic series oscillator. Each amplitude in the
+/// list drives the partial at the corresponding multiple of the fundamental,
+/// the first being the fundamental itself. Partials at or above the Nyquist
+/// frequency are skipped, so the output is free of aliasing.
+#[derive(Clone)]
+pub struct Harmonics {
+    frequency: MathT,
+    amplitudes: Vec<MathT>,
+    sample_rate: MathT,
+    phase: MathT,
+}
+
+impl Harmonics {
+    /// Constructs a new [`Harmonics`] oscillator.
+    ///
+    /// # Parameters
+    ///
+    /// * `frequency` - The fundamental frequency.
+    /// * `amplitudes` - The amplitude of each partial, starting with the
+    /// fundamental.
+    /// * `sample_rate` - The sample rate of the generated audio.
+    ///
+    /// [`Harmonics`]: struct.Harmonics.html
+    pub fn new(frequency: MathT, amplitudes: Vec<MathT>, sample_rate: MathT) -> Self {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        Harmonics {
+            frequency,
+            amplitudes,
+            sample_rate,
+            phase: 0.0,
+        }
+    }
+
+    /// Returns the amplitude of each partial, starting with the fundamental.
+    pub fn get_harmonics(&self) -> &[MathT] {
+        &self.amplitudes
+    }
+
+    /// Sets the amplitude of the `n`th harmonic, where `1` is the fundamental.
+    /// The list of amplitudes is extended with silent partials as needed.
+    /// Harmonic `0` doesn't exist and is ignored.
+    pub fn set_harmonic(&mut self, n: usize, amplitude: MathT) {
+        if n == 0 {
+            return;
+        }
+
+        if self.amplitudes.len() < n {
+            self.amplitudes.resize(n, 0.0);
+        }
+        self.amplitudes[n - 1] = amplitude;
+    }
+}
+
+impl FreqControllable for Harmonics {
+    fn set_frequency(&mut self, f: MathT) {
+        self.frequency = f;
+    }
+
+    fn get_frequency(&self) -> MathT {
+        self.frequency
+    }
+}
+
+impl PhaseResettable for Harmonics {
+    fn reset_phase(&mut self) {
+        self.phase = 0.0;
+    }
+}
+
+/// The fundamental frequency is exposed as `frequency`, and the amplitude of
+/// the `n`th harmonic as `harmonic_n`, numbered from `1` as with
+/// [`Harmonics::set_harmonic`].
+///
+/// [`Harmonics::set_harmonic`]: struct.Harmonics.html#method.set_harmonic
+impl Parameterized for Harmonics {
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        std::iter::once(("frequency".to_owned(), self.frequency))
+            .chain(
+                self.amplitudes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, a)| (format!("harmonic_{}", i + 1), *a)),
+            )
+            .collect()
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        if name == "frequency" {
+            self.frequency = value;
+            return true;
+        }
+
+        match name.strip_prefix("harmonic_").map(|n| n.parse::<usize>()) {
+            Some(Ok(n)) if n > 0 => {
+                self.set_harmonic(n, value);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Generator for Harmonics {
+    fn process(&mut self) -> SampleT {
+        let nyquist = self.sample_rate / 2.0;
+        let w = 2.0 * std::f64::consts::PI * self.phase;
+
+        let y: MathT = self
+            .amplitudes
+            .iter()
+            .enumerate()
+            .take_while(|(i, _)| (*i + 1) as MathT * self.frequency.abs() < nyquist)
+            .map(|(i, a)| a * ((i + 1) as MathT * w).sin())
+            .sum();
+
+        self.phase = (self.phase + self.frequency / self.sample_rate).rem_euclid(1.0);
+
+        y as SampleT
+    }
+}
@@ -0,0 +1,124 @@
+//! # Metronome
+//!
+//! A [`Generator`] producing short tonal clicks on each beat of a given tempo,
+//! useful as a tempo reference during rendering.
+//!
+//! [`Generator`]: ../../generators/trait.Generator.html
+
+use super::*;
+
+/// Struct implementing a metronome. Each beat produces a short sine click that
+/// decays linearly over the click duration. The amplitude of each click is
+/// taken from the accent pattern, which also defines the number of beats in a
+/// bar.
+///
+/// For example, an accent pattern of `vec![1.0, 0.5, 0.5, 0.5]` produces a 4/4
+/// bar with the first beat accented.
+#[derive(Clone)]
+pub struct Metronome {
+    bpm: MathT,
+    samples_per_beat: MathT,
+    click_frequency: MathT,
+    click_samples: MathT,
+    accents: Vec<SampleT>,
+    sample_rate: MathT,
+    phase: MathT,
+    beat: usize,
+}
+
+impl Metronome {
+    /// Constructs a new [`Metronome`].
+    ///
+    /// # Parameters
+    ///
+    /// * `bpm` - The tempo in beats per minute.
+    /// * `click_frequency` - The frequency of the tone used for each click.
+    /// * `click_duration` - The length of each click.
+    /// * `accents` - The amplitude of the click for each beat of the bar. An
+    /// empty pattern is treated as a single unaccented beat per bar.
+    /// * `sample_rate` - The sample rate of the generated audio.
+    ///
+    /// [`Metronome`]: struct.Metronome.html
+    pub fn new(
+        bpm: MathT,
+        click_frequency: MathT,
+        click_duration: Duration,
+        accents: Vec<SampleT>,
+        sample_rate: MathT,
+    ) -> Self {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        let accents = if accents.is_empty() {
+            vec![1.0]
+        } else {
+            accents
+        };
+
+        Metronome {
+            bpm,
+            samples_per_beat: 60.0 * sample_rate / bpm,
+            click_frequency,
+            click_samples: click_duration.as_secs_f64() * sample_rate,
+            accents,
+            sample_rate,
+            phase: 0.0,
+            beat: 0,
+        }
+    }
+
+    /// Returns the tempo in beats per minute.
+    pub fn get_bpm(&self) -> MathT {
+        self.bpm
+    }
+
+    /// Sets the tempo in beats per minute. The position within the current beat
+    /// is kept.
+    pub fn set_bpm(&mut self, bpm: MathT) {
+        self.bpm = bpm;
+        self.samples_per_beat = 60.0 * self.sample_rate / bpm;
+    }
+}
+
+impl PhaseResettable for Metronome {
+    fn reset_phase(&mut self) {
+        self.phase = 0.0;
+        self.beat = 0;
+    }
+}
+
+impl Parameterized for Metronome {
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        vec![("bpm".to_owned(), self.get_bpm())]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        match name {
+            "bpm" => self.set_bpm(value),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl Generator for Metronome {
+    fn process(&mut self) -> SampleT {
+        let y = if self.phase < self.click_samples {
+            let env = 1.0 - self.phase / self.click_samples;
+            let tone =
+                (2.0 * std::f64::consts::PI * self.click_frequency * self.phase / self.sample_rate)
+                    .sin();
+
+            self.accents[self.beat] as MathT * env * tone
+        } else {
+            0.0
+        };
+
+        self.phase += 1.0;
+        if self.phase >= self.samples_per_beat {
+            self.phase -= self.samples_per_beat;
+            self.beat = (self.beat + 1) % self.accents.len();
+        }
+
+        y as SampleT
+    }
+}
@@ -0,0 +1,110 @@
+//! # Morph
+//!
+//! A [`Generator`] blending two other [`Generator`]s by a continuous morph
+//! value, for wavetable-style morphing between timbres.
+//!
+//! [`Generator`]: ../../generators/trait.Generator.html
+
+use super::*;
+
+/// Struct implementing a morph between two [`Generator`]s. A morph of `0`
+/// outputs the first [`Generator`] and a morph of `1` the second. Both
+/// [`Generator`]s are processed every sample regardless of the morph, so they
+/// stay in phase as the morph changes.
+///
+/// By default the two are blended linearly, which keeps correlated sources
+/// such as oscillators at the same frequency at a constant level. Use
+/// [`set_equal_power`] for uncorrelated sources, which would otherwise dip in
+/// level halfway through the morph.
+///
+/// [`Generator`]: ../../generators/trait.Generator.html
+/// [`set_equal_power`]: struct.MorphGenerator.html#method.set_equal_power
+pub struct MorphGenerator {
+    a: GeneratorSP,
+    b: GeneratorSP,
+    morph: MathT,
+    equal_power: bool,
+}
+
+impl MorphGenerator {
+    /// Constructs a new [`MorphGenerator`] blending from `a` to `b` by the
+    /// given morph, clamped to `[0, 1]`.
+    ///
+    /// [`MorphGenerator`]: struct.MorphGenerator.html
+    pub fn new(a: GeneratorSP, b: GeneratorSP, morph: MathT) -> Self {
+        MorphGenerator {
+            a,
+            b,
+            morph: morph.max(0.0).min(1.0),
+            equal_power: false,
+        }
+    }
+
+    /// Returns the morph between the two [`Generator`]s.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    pub fn get_morph(&self) -> MathT {
+        self.morph
+    }
+
+    /// Sets the morph between the two [`Generator`]s, clamped to `[0, 1]`.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    pub fn set_morph(&mut self, morph: MathT) {
+        self.morph = morph.max(0.0).min(1.0);
+    }
+
+    /// Returns whether the [`Generator`]s are blended with an equal-power
+    /// curve rather than linearly.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    pub fn is_equal_power(&self) -> bool {
+        self.equal_power
+    }
+
+    /// Sets whether the [`Generator`]s are blended with an equal-power curve
+    /// rather than linearly.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    pub fn set_equal_power(&mut self, equal_power: bool) {
+        self.equal_power = equal_power;
+    }
+
+    /// Returns the smart pointers to the two [`Generator`]s.
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    pub fn get_generators(&self) -> (&GeneratorSP, &GeneratorSP) {
+        (&self.a, &self.b)
+    }
+}
+
+impl Parameterized for MorphGenerator {
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        vec![("morph".to_owned(), self.morph)]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        match name {
+            "morph" => self.set_morph(value),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl Generator for MorphGenerator {
+    fn process(&mut self) -> SampleT {
+        let a = self.a.lock().map(|mut a| a.process()).unwrap_or_default() as MathT;
+        let b = self.b.lock().map(|mut b| b.process()).unwrap_or_default() as MathT;
+
+        let (ga, gb) = if self.equal_power {
+            let theta = self.morph * std::f64::consts::FRAC_PI_2;
+            (theta.cos(), theta.sin())
+        } else {
+            (1.0 - self.morph, self.morph)
+        };
+
+        (a * ga + b * gb) as SampleT
+    }
+}
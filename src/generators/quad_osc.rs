@@ -0,0 +1,84 @@
+//! # QuadOsc
+//!
+//! A [`Generator`] producing a sine and cosine pair in exact quadrature, as
+//! used by single-sideband modulation and frequency shifting.
+//!
+//! [`Generator`]: ../../generators/trait.Generator.html
+
+use super::*;
+
+/// Struct implementing a quadrature oscillator. Both outputs are computed from
+/// a single shared phase, so they stay exactly 90° apart regardless of
+/// frequency changes.
+///
+/// When used as a [`Generator`], only the sine output is produced. Use
+/// [`process_frame`] to retrieve both outputs.
+///
+/// [`Generator`]: ../../generators/trait.Generator.html
+/// [`process_frame`]: struct.QuadOsc.html#method.process_frame
+#[derive(Copy, Clone)]
+pub struct QuadOsc {
+    frequency: MathT,
+    sample_rate: MathT,
+    phase: MathT,
+}
+
+impl QuadOsc {
+    /// Constructs a new [`QuadOsc`] at the given frequency.
+    ///
+    /// [`QuadOsc`]: struct.QuadOsc.html
+    pub fn new(frequency: MathT, sample_rate: MathT) -> Self {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        QuadOsc {
+            frequency,
+            sample_rate,
+            phase: 0.0,
+        }
+    }
+
+    /// Processes a single frame, returning `[sin, cos]`.
+    pub fn process_frame(&mut self) -> [SampleT; 2] {
+        let (s, c) = (2.0 * std::f64::consts::PI * self.phase).sin_cos();
+
+        self.phase = (self.phase + self.frequency / self.sample_rate).rem_euclid(1.0);
+
+        [s as SampleT, c as SampleT]
+    }
+}
+
+impl FreqControllable for QuadOsc {
+    fn set_frequency(&mut self, f: MathT) {
+        self.frequency = f;
+    }
+
+    fn get_frequency(&self) -> MathT {
+        self.frequency
+    }
+}
+
+impl PhaseResettable for QuadOsc {
+    fn reset_phase(&mut self) {
+        self.phase = 0.0;
+    }
+}
+
+impl Parameterized for QuadOsc {
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        vec![("frequency".to_owned(), self.get_frequency())]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        match name {
+            "frequency" => self.set_frequency(value),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl Generator for QuadOsc {
+    fn process(&mut self) -> SampleT {
+        self.process_frame()[0]
+    }
+}
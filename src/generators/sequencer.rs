@@ -0,0 +1,133 @@
+//! # Sequencer
+//!
+//! A [`Generator`] stepping an oscillator through a looping pattern of notes,
+//! usable as a simple source for generative patterns.
+//!
+//! [`Generator`]: ../../generators/trait.Generator.html
+
+use super::*;
+
+/// Struct implementing a step sequencer. Each step is a `(note, duration)`
+/// pair, where the note is a MIDI note number and the duration is measured in
+/// beats at the sequencer's tempo. The pattern loops once the last step ends.
+///
+/// Steps with the note [`Sequencer::REST`] produce silence. The internal sine
+/// oscillator keeps its phase across steps, so pitch changes are click-free.
+///
+/// [`Sequencer::REST`]: struct.Sequencer.html#associatedconstant.REST
+#[derive(Clone)]
+pub struct Sequencer {
+    steps: Vec<(MathT, MathT)>,
+    tempo: MathT,
+    sample_rate: MathT,
+    osc: QuadOsc,
+    step: usize,
+    position: MathT,
+}
+
+impl Sequencer {
+    /// Note value marking a step as a rest.
+    pub const REST: MathT = MathT::NEG_INFINITY;
+
+    /// Constructs a new [`Sequencer`].
+    ///
+    /// # Parameters
+    ///
+    /// * `steps` - The `(note, duration)` pairs making up the pattern, with
+    /// durations in beats. An empty pattern produces silence.
+    /// * `tempo` - The tempo in beats per minute.
+    /// * `sample_rate` - The sample rate of the generated audio.
+    ///
+    /// [`Sequencer`]: struct.Sequencer.html
+    pub fn new(steps: Vec<(MathT, MathT)>, tempo: MathT, sample_rate: MathT) -> Self {
+        let mut s = Sequencer {
+            steps: Vec::new(),
+            tempo,
+            sample_rate,
+            osc: QuadOsc::new(0.0, sample_rate),
+            step: 0,
+            position: 0.0,
+        };
+        s.set_steps(steps);
+
+        s
+    }
+
+    /// Returns the steps of the pattern.
+    pub fn get_steps(&self) -> &[(MathT, MathT)] {
+        &self.steps
+    }
+
+    /// Sets the steps of the pattern, restarting it from the first step.
+    pub fn set_steps(&mut self, steps: Vec<(MathT, MathT)>) {
+        self.steps = steps;
+        self.step = 0;
+        self.position = 0.0;
+        self.update_frequency();
+    }
+
+    /// Returns the tempo in beats per minute.
+    pub fn get_tempo(&self) -> MathT {
+        self.tempo
+    }
+
+    /// Sets the tempo in beats per minute. The position within the current
+    /// step is kept.
+    pub fn set_tempo(&mut self, tempo: MathT) {
+        self.tempo = tempo;
+    }
+
+    fn update_frequency(&mut self) {
+        if let Some((note, _)) = self.steps.get(self.step) {
+            if *note != Self::REST {
+                self.osc
+                    .set_frequency(440.0 * (2.0 as MathT).powf((note - 69.0) / 12.0));
+            }
+        }
+    }
+}
+
+impl Parameterized for Sequencer {
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        vec![("tempo".to_owned(), self.get_tempo())]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        match name {
+            "tempo" => self.set_tempo(value),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl Generator for Sequencer {
+    fn process(&mut self) -> SampleT {
+        if self.steps.is_empty() {
+            return Default::default();
+        }
+
+        let y = if self.steps[self.step].0 == Self::REST {
+            Default::default()
+        } else {
+            self.osc.process()
+        };
+
+        self.position += self.tempo / (60.0 * self.sample_rate);
+
+        // Bounded so a pattern of zero-length steps can't stall processing.
+        for _ in 0..self.steps.len() {
+            let duration = self.steps[self.step].1;
+            if self.position < duration {
+                break;
+            }
+
+            self.position -= duration;
+            self.step = (self.step + 1) % self.steps.len();
+            self.update_frequency();
+        }
+
+        y
+    }
+}
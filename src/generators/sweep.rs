@@ -0,0 +1,186 @@
+//! # Sweep
+//!
+//! A [`Generator`] producing an exponential sine sweep, used to measure the
+//! frequency and impulse response of a chain of [`Modifier`]s.
+//!
+//! [`Generator`]: ../../generators/trait.Generator.html
+//! [`Modifier`]: ../../modifiers/trait.Modifier.html
+
+use super::*;
+
+/// Struct implementing an exponential (logarithmic) sine sweep.
+///
+/// The frequency rises from the start frequency to the end frequency over the
+/// given duration, spending equal time in each octave. Once the duration has
+/// passed the sweep produces silence until its phase is reset.
+///
+/// To measure a system, record its response to the whole sweep plus a tail
+/// long enough for the response to decay, then pass the recording to
+/// [`deconvolve`] to recover the impulse response. The recovered response is
+/// only valid between the start and end frequencies.
+///
+/// [`deconvolve`]: struct.Sweep.html#method.deconvolve
+#[derive(Clone)]
+pub struct Sweep {
+    f_start: MathT,
+    f_end: MathT,
+    sample_rate: MathT,
+    length: usize,
+    rate: MathT,
+    position: usize,
+}
+
+impl Sweep {
+    /// Constructs a new [`Sweep`].
+    ///
+    /// # Parameters
+    ///
+    /// * `f_start` - The frequency at the start of the sweep.
+    /// * `f_end` - The frequency at the end of the sweep. This may be lower
+    /// than `f_start` for a downwards sweep.
+    /// * `duration` - The length of the sweep.
+    /// * `sample_rate` - The sample rate of the generated audio.
+    ///
+    /// Debug builds panic if either frequency or `sample_rate` is not
+    /// positive.
+    ///
+    /// [`Sweep`]: struct.Sweep.html
+    pub fn new(f_start: MathT, f_end: MathT, duration: Duration, sample_rate: MathT) -> Self {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        debug_assert!(
+            f_start > 0.0 && f_end > 0.0,
+            "sweep frequencies must be positive"
+        );
+        let length = (duration.as_secs_f64() * sample_rate) as usize;
+
+        Sweep {
+            f_start,
+            f_end,
+            sample_rate,
+            length,
+            rate: if length > 0 {
+                (f_end / f_start).ln() / length as MathT
+            } else {
+                0.0
+            },
+            position: 0,
+        }
+    }
+
+    /// Returns the frequency at the start of the sweep.
+    pub fn get_start_frequency(&self) -> MathT {
+        self.f_start
+    }
+
+    /// Returns the frequency at the end of the sweep.
+    pub fn get_end_frequency(&self) -> MathT {
+        self.f_end
+    }
+
+    /// Returns the length of the sweep.
+    pub fn get_duration(&self) -> Duration {
+        Duration::from_secs_f64(self.length as MathT / self.sample_rate)
+    }
+
+    /// Returns the length of the sweep in samples.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if the sweep has a length of zero samples.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns `true` once the whole sweep has been produced.
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.length
+    }
+
+    /// Returns the instantaneous frequency of the next sample to be
+    /// produced. Once the sweep has finished this is the end frequency.
+    pub fn get_frequency(&self) -> MathT {
+        self.frequency_at(self.position.min(self.length))
+    }
+
+    /// Returns the inverse filter of the sweep: the sweep reversed in time,
+    /// with its amplitude falling by 6dB per octave to compensate for the
+    /// extra energy the sweep puts into low frequencies. Convolving the sweep
+    /// with its inverse filter gives a unit impulse delayed by one less than
+    /// the sweep's length.
+    pub fn inverse_filter(&self) -> SampleTrackT {
+        let weighted: Vec<(MathT, MathT)> = (0..self.length)
+            .map(|n| (self.sample_at(n), self.frequency_at(n) / self.f_end))
+            .collect();
+
+        let norm: MathT = weighted.iter().map(|(x, w)| x * x * w).sum();
+        if norm <= 0.0 {
+            return vec![0.0; self.length];
+        }
+
+        weighted
+            .iter()
+            .rev()
+            .map(|(x, w)| (x * w / norm) as SampleT)
+            .collect()
+    }
+
+    /// Recovers the impulse response of a system from its recorded response
+    /// to the sweep, starting from the first sample of the sweep. The
+    /// returned response is as long as the recording.
+    ///
+    /// The recording is convolved directly with the [`inverse_filter`], so
+    /// this takes time proportional to the length of the recording times the
+    /// length of the sweep, and is meant for offline analysis.
+    ///
+    /// [`inverse_filter`]: struct.Sweep.html#method.inverse_filter
+    pub fn deconvolve(&self, recording: &[SampleT]) -> SampleTrackT {
+        let inverse = self.inverse_filter();
+        let n = inverse.len();
+
+        (0..recording.len())
+            .map(|k| {
+                (k..(k + n).min(recording.len()))
+                    .map(|j| recording[j] as MathT * inverse[n - 1 + k - j] as MathT)
+                    .sum::<MathT>() as SampleT
+            })
+            .collect()
+    }
+
+    /// Returns the instantaneous frequency of the sweep at the given sample.
+    fn frequency_at(&self, n: usize) -> MathT {
+        self.f_start * (self.rate * n as MathT).exp()
+    }
+
+    /// Returns the sample of the sweep at the given position, integrating the
+    /// exponential frequency ramp to find its phase.
+    fn sample_at(&self, n: usize) -> MathT {
+        let cycles = if self.rate == 0.0 {
+            n as MathT
+        } else {
+            (self.rate * n as MathT).exp_m1() / self.rate
+        };
+
+        (2.0 * std::f64::consts::PI * self.f_start * cycles / self.sample_rate).sin()
+    }
+}
+
+impl PhaseResettable for Sweep {
+    /// Restarts the sweep from the start frequency.
+    fn reset_phase(&mut self) {
+        self.position = 0;
+    }
+}
+
+impl Generator for Sweep {
+    fn process(&mut self) -> SampleT {
+        if self.is_finished() {
+            return Default::default();
+        }
+
+        let y = self.sample_at(self.position);
+        self.position += 1;
+
+        y as SampleT
+    }
+}
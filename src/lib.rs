@@ -11,15 +11,70 @@
 
 use bae_types::*;
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 pub mod bae_sound;
 pub mod bae_block;
+pub mod bae_block_n;
+pub mod block_iter;
+pub mod block_sound;
+pub mod channel;
+pub mod channel_strip;
 pub mod complex_sound;
+pub mod delay_line;
+pub mod generators;
+pub mod mod_matrix;
+pub mod modifiers;
+pub mod oversampled;
+#[cfg(feature = "serde")]
+pub mod patch;
+pub mod resampler;
 
 pub use bae_sound::*;
 pub use bae_block::*;
+pub use bae_block_n::*;
+pub use block_iter::*;
+pub use block_sound::*;
+pub use channel::*;
+pub use channel_strip::*;
 pub use complex_sound::*;
+pub use delay_line::*;
+pub use mod_matrix::*;
+pub use oversampled::*;
+#[cfg(feature = "serde")]
+pub use patch::*;
+pub use resampler::*;
+
+/// Converts a gain in decibels to a linear gain.
+pub fn db_to_linear(db: MathT) -> MathT {
+    (10.0 as MathT).powf(db / 20.0)
+}
+
+/// Converts a linear gain to decibels.
+pub fn linear_to_db(g: MathT) -> MathT {
+    20.0 * g.log10()
+}
+
+/// Default length of the fades applied to avoid clicks, such as when a
+/// [`BaeSound`] is muted or a connection of a [`ComplexSound`] is switched.
+///
+/// [`BaeSound`]: bae_sound/struct.BaeSound.html
+/// [`ComplexSound`]: complex_sound/struct.ComplexSound.html
+pub const DEFAULT_RAMP: Duration = Duration::from_millis(5);
+
+/// Sample rate at which [`DEFAULT_RAMP`] is converted to samples by sounds
+/// constructed without a sample rate. Sounds running at other rates should
+/// set their ramps explicitly.
+///
+/// [`DEFAULT_RAMP`]: constant.DEFAULT_RAMP.html
+pub const DEFAULT_RAMP_SAMPLE_RATE: MathT = 48_000.0;
+
+/// Converts the length of a fade to a whole number of samples.
+pub(crate) fn ramp_samples(d: Duration, sample_rate: MathT) -> usize {
+    debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+    (d.as_secs_f64() * sample_rate).round() as usize
+}
 
 /// Trait used for generalizing the interface that allows for the processing of
 /// multiple audio objects (be they [`Generator`]s or [`Modifier`]s or both).
@@ -30,6 +85,17 @@ pub trait Block {
     /// Increments the internal input sample by the given sample.
     fn prime_input(&mut self, x: SampleT);
 
+    /// Feeds a value into the control lane with the given index, e.g. to
+    /// modulate a parameter of the [`Block`] with another signal. Unlike the
+    /// audio input, control values take effect immediately and are not
+    /// summed; call this before each [`process`] for per-sample automation.
+    /// How lanes are indexed is defined by each implementor. Defaults to
+    /// doing nothing.
+    ///
+    /// [`Block`]: trait.Block.html
+    /// [`process`]: trait.Block.html#tymethod.process
+    fn prime_control(&mut self, _idx: usize, _value: SampleT) {}
+
     /// Process the [`Block`]. Individually processes the stored [`Generator`]
     /// and [`Modifier`] objects which are both combined using the [`Inter`] and
     /// returned.
@@ -39,12 +105,192 @@ pub trait Block {
     /// [`Modifier`]: ../../modifiers/trait.Modifier.html
     /// [`Inter`]: type.Inter.html
     fn process(&mut self) -> SampleT;
+
+    /// Returns a human-readable name describing the type of the [`Block`],
+    /// e.g. for display in a node editor. Defaults to the name of the
+    /// implementing type.
+    ///
+    /// [`Block`]: trait.Block.html
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Returns an advisory estimate of the relative cost of processing a
+    /// single sample through the [`Block`], where `1` represents a simple
+    /// filter. The estimate is not measured; it exists so hosts can judge
+    /// whether a patch fits within their processing budget.
+    ///
+    /// [`Block`]: trait.Block.html
+    fn cost_hint(&self) -> u32 {
+        1
+    }
+
+    /// Returns whether the [`Block`] produces a control signal, such as an LFO
+    /// or envelope, rather than audio. Control-rate blocks may be processed
+    /// less often than once per sample by containers that support it, such as
+    /// [`BaeSound::set_control_rate_divisor`]. Defaults to `false`.
+    ///
+    /// [`Block`]: trait.Block.html
+    /// [`BaeSound::set_control_rate_divisor`]: bae_sound/struct.BaeSound.html#method.set_control_rate_divisor
+    fn is_control_rate(&self) -> bool {
+        false
+    }
+
+    /// Returns the named parameters of the [`Block`] along with their current
+    /// values. See [`Parameterized::parameters`]. Defaults to no parameters.
+    ///
+    /// [`Block`]: trait.Block.html
+    /// [`Parameterized::parameters`]: trait.Parameterized.html#tymethod.parameters
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        Vec::new()
+    }
+
+    /// Sets the named parameter of the [`Block`]. See
+    /// [`Parameterized::set_parameter`]. Defaults to returning `false`.
+    ///
+    /// [`Block`]: trait.Block.html
+    /// [`Parameterized::set_parameter`]: trait.Parameterized.html#tymethod.set_parameter
+    fn set_parameter(&mut self, _name: &str, _value: MathT) -> bool {
+        false
+    }
+
+    /// Resets the phase of the [`Block`] if it wraps an oscillator, such as an
+    /// LFO. See [`PhaseResettable`]. Defaults to doing nothing.
+    ///
+    /// [`Block`]: trait.Block.html
+    /// [`PhaseResettable`]: generators/trait.PhaseResettable.html
+    fn reset_phase(&mut self) {}
+
+    /// Resets the internal state of the [`Block`], such as filter memory,
+    /// oscillator phase, and any primed input, so it behaves as if it were
+    /// newly constructed. Useful for looped playback and reusing voices.
+    /// Defaults to doing nothing.
+    ///
+    /// [`Block`]: trait.Block.html
+    fn reset(&mut self) {}
+
+    /// Processes the [`Block`] once for each sample of `out`, storing the
+    /// outputs in order. Any primed input is consumed by the first sample, as
+    /// with successive calls to [`process`].
+    ///
+    /// Defaults to calling [`process`] in a loop. Implementors can override it
+    /// to avoid per-sample overhead, such as dynamic dispatch through a
+    /// [`BlockSP`] or locking their internals.
+    ///
+    /// [`Block`]: trait.Block.html
+    /// [`process`]: trait.Block.html#tymethod.process
+    /// [`BlockSP`]: type.BlockSP.html
+    fn process_buffer(&mut self, out: &mut [SampleT]) {
+        for y in out.iter_mut() {
+            *y = self.process();
+        }
+    }
+
+    /// Returns an [`Iterator`] yielding the output of [`process`] on each call
+    /// to `next`, e.g. `block.samples().take(n).collect::<SampleTrackT>()`.
+    /// Use [`BlockIter::with_input`] to prime the [`Block`] from another
+    /// iterator.
+    ///
+    /// [`Iterator`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html
+    /// [`process`]: trait.Block.html#tymethod.process
+    /// [`BlockIter::with_input`]: block_iter/struct.BlockIter.html#method.with_input
+    /// [`Block`]: trait.Block.html
+    fn samples(&mut self) -> BlockIter<'_, Self>
+    where
+        Self: Sized,
+    {
+        BlockIter::new(self)
+    }
+}
+
+/// Trait generalizing [`Block`] to objects processing a whole frame of
+/// samples at a time, such as the stereo `[SampleT; 2]` frames produced by a
+/// [`BaeBlockN`]. Every [`BaeBlock`] is a [`FrameBlock`] with a mono
+/// `SampleT` frame.
+///
+/// [`Block`] keeps returning a single sample, so the series chain of a
+/// [`BaeSound`] stays mono. Stereo [`FrameBlock`]s are chained after it with
+/// [`BaeSound::add_stereo_modifier`].
+///
+/// [`Block`]: trait.Block.html
+/// [`BaeBlock`]: bae_block/struct.BaeBlock.html
+/// [`BaeBlockN`]: bae_block_n/struct.BaeBlockN.html
+/// [`FrameBlock`]: trait.FrameBlock.html
+/// [`BaeSound`]: bae_sound/struct.BaeSound.html
+/// [`BaeSound::add_stereo_modifier`]: bae_sound/struct.BaeSound.html#method.add_stereo_modifier
+pub trait FrameBlock {
+    /// The type of frame consumed and produced by the [`FrameBlock`].
+    ///
+    /// [`FrameBlock`]: trait.FrameBlock.html
+    type Frame;
+
+    /// Increments the internal input frame by the given frame.
+    fn prime_frame(&mut self, x: Self::Frame);
+
+    /// Processes the [`FrameBlock`], returning the resulting frame.
+    ///
+    /// [`FrameBlock`]: trait.FrameBlock.html
+    fn process_frame(&mut self) -> Self::Frame;
 }
 
+/// Alias for a [`FrameBlock`] producing frames of `N` samples, wrapped in a
+/// smart pointer.
+///
+/// [`FrameBlock`]: trait.FrameBlock.html
+pub type FrameBlockSP<const N: usize> =
+    Arc<Mutex<dyn FrameBlock<Frame = [SampleT; N]> + Send>>;
+
 /// Alias for a [`Block`] object wrapped in a smart pointer.
 ///
+/// The [`Block`] is shared behind a [`Mutex`], so clones of the pointer refer to
+/// the same object and may be processed from multiple places, including from
+/// other threads. Each use locks the [`Block`] for the duration of the call;
+/// processing the same [`Block`] from several places advances its state once
+/// per call, so it is usually only meaningful for stateless objects.
+///
 /// [`Block`]: trait.Block.html
-pub type BlockSP = Arc<dyn Block>;
+/// [`Mutex`]: https://doc.rust-lang.org/std/sync/struct.Mutex.html
+pub type BlockSP = Arc<Mutex<dyn Block + Send>>;
+
+/// Trait for objects exposing their settings as a flat list of named numeric
+/// parameters, e.g. for automation, patch diffing, or building a UI without
+/// knowing the concrete type of the object.
+///
+/// Parameters measured in time are given in seconds, and parameters measured
+/// in decibels are suffixed with `_db`.
+pub trait Parameterized {
+    /// Returns the name and current value of every parameter.
+    fn parameters(&self) -> Vec<(String, MathT)>;
+
+    /// Sets the parameter with the given name to the given value. Returns
+    /// `false` if there is no parameter with the given name, in which case
+    /// nothing is changed.
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool;
+}
+
+/// Alias for a [`Parameterized`] object wrapped in a smart pointer. Like
+/// [`BlockSP`], the object is shared behind a [`Mutex`].
+///
+/// [`Parameterized`]: trait.Parameterized.html
+/// [`BlockSP`]: type.BlockSP.html
+/// [`Mutex`]: https://doc.rust-lang.org/std/sync/struct.Mutex.html
+pub type ParameterizedSP = Arc<Mutex<dyn Parameterized + Send>>;
+
+/// Trait for [`Generator`]s and [`Modifier`]s that are more expensive to
+/// process than a simple filter, estimating their own cost in the units of
+/// [`Block::cost_hint`]. Wrap them with [`BaeBlock::from_costly_modifier`] or
+/// [`BaeBlockBuilder::cost_hint`] so their blocks report that cost.
+///
+/// [`Generator`]: generators/trait.Generator.html
+/// [`Modifier`]: modifiers/trait.Modifier.html
+/// [`Block::cost_hint`]: trait.Block.html#method.cost_hint
+/// [`BaeBlock::from_costly_modifier`]: bae_block/struct.BaeBlock.html#method.from_costly_modifier
+/// [`BaeBlockBuilder::cost_hint`]: bae_block/struct.BaeBlockBuilder.html#method.cost_hint
+pub trait CostHint {
+    /// Returns an estimate of the relative cost of processing a single
+    /// sample, where `1` represents a simple filter.
+    fn cost_hint(&self) -> u32;
+}
 
 /// This trait defines the interface that anything producing sound that will be
 /// output to a [`Channel`] must define.
@@ -63,16 +309,35 @@ pub trait Sound {
     fn is_paused(&self) -> bool;
 
     /// Toggles the mute state of the sound. If the sound is muted, the internal
-    /// structures are still processed during a call to [`process`], but
-    /// [`Default::default()`] is returned.
+    /// structures are still processed during a call to [`process`], but the
+    /// output fades to [`Default::default()`]. Implementations may fade over a
+    /// short ramp to avoid clicks, such as [`BaeSound::set_mute_ramp`].
     ///
     /// [`process`]: trait.Sound.html#tymethod.process
     /// [`Default::default()`]: https://doc.rust-lang.org/std/default/trait.Default.html#tymethod.default
+    /// [`BaeSound::set_mute_ramp`]: bae_sound/struct.BaeSound.html#method.set_mute_ramp
     fn toggle_mute(&mut self);
 
     /// Returns the mute state of the sound.
     fn is_muted(&self) -> bool;
 
+    /// Sets the linear gain applied to the output of the sound, e.g. for a
+    /// per-sound fader.
+    fn set_gain(&mut self, g: MathT);
+
+    /// Returns the linear gain applied to the output of the sound.
+    fn get_gain(&self) -> MathT;
+
+    /// Returns whether the sound has finished playing and can be discarded,
+    /// e.g. a one-shot sound that has played for its whole duration.
+    /// [`BaeChannel`] removes finished sounds after each call to its
+    /// `process`. Defaults to `false`.
+    ///
+    /// [`BaeChannel`]: channel/bae_channel/struct.BaeChannel.html
+    fn is_finished(&self) -> bool {
+        false
+    }
+
     /// Processes the sound and its internal structures, returning the resulting
     /// audio sample.
     ///
@@ -80,9 +345,11 @@ pub trait Sound {
     /// performed, instead only [`Default::default()`] is returned.
     ///
     /// If the sound is muted, the internal structures are still processed, but
-    /// [`Default::default()`] is still returned.
+    /// [`Default::default()`] is returned once the mute ramp, if any, has
+    /// faded out. See [`toggle_mute`].
     ///
     /// [`Default::default()`]: https://doc.rust-lang.org/std/default/trait.Default.html#tymethod.default
+    /// [`toggle_mute`]: trait.Sound.html#tymethod.toggle_mute
     fn process(&mut self, input: SampleT) -> SampleT;
 
     /// Sets itself as registered with the given ID.
@@ -0,0 +1,228 @@
+//! # Modulation Matrix
+//!
+//! Module containing the types used to route modulation sources to the
+//! parameters of a [`BaeSound`].
+//!
+//! [`BaeSound`]: ../bae_sound/struct.BaeSound.html
+
+use super::*;
+
+use bae_gen::Generator;
+
+/// The parameters of a [`BaeSound`] that can be modulated through its
+/// [`ModMatrix`].
+///
+/// [`BaeSound`]: ../bae_sound/struct.BaeSound.html
+/// [`ModMatrix`]: struct.ModMatrix.html
+#[derive(Clone, Debug, PartialEq)]
+pub enum ModDestination {
+    /// The linear gain applied to the input of the sound.
+    InputGain,
+    /// The linear gain applied to the output of the sound.
+    OutputGain,
+    /// A named parameter of one of the sound's blocks, e.g. the cutoff of a
+    /// filter, set through [`Block::set_parameter`]. Blocks are indexed as
+    /// with [`BaeSound::all_parameters`].
+    ///
+    /// [`Block::set_parameter`]: ../trait.Block.html#method.set_parameter
+    /// [`BaeSound::all_parameters`]: ../bae_sound/struct.BaeSound.html#method.all_parameters
+    Parameter {
+        /// The index of the block.
+        block: usize,
+        /// The name of the parameter within the block.
+        name: String,
+    },
+}
+
+/// Struct mapping modulation sources, such as LFOs and envelopes, to
+/// [`ModDestination`]s with a per-route depth.
+///
+/// Each sample, every source is processed once and each route adds
+/// `depth * source` to its destination, so a destination with base value `v`
+/// is applied as `v * (1 + modulation)`.
+///
+/// For a [`ModDestination::Parameter`], the base value is the value of the
+/// parameter when it is first modulated, and the parameter is set to its
+/// modulated value before the chain processes each sample. Setting the
+/// parameter through [`BaeSound::set_parameter`] changes the base value.
+///
+/// Sources and routes are addressed by the handles returned from
+/// [`add_source`] and [`add_route`].
+///
+/// [`ModDestination`]: enum.ModDestination.html
+/// [`ModDestination::Parameter`]: enum.ModDestination.html#variant.Parameter
+/// [`BaeSound::set_parameter`]: ../bae_sound/struct.BaeSound.html#method.set_parameter
+/// [`add_source`]: struct.ModMatrix.html#method.add_source
+/// [`add_route`]: struct.ModMatrix.html#method.add_route
+#[derive(Clone, Default)]
+pub struct ModMatrix {
+    sources: Vec<GeneratorSP>,
+    values: Vec<SampleT>,
+    routes: Vec<(usize, ModDestination, SampleT)>,
+    bases: Vec<(usize, String, MathT)>,
+    parameter_sums: Vec<(usize, SampleT)>,
+}
+
+impl ModMatrix {
+    /// Constructs a new, empty [`ModMatrix`].
+    ///
+    /// [`ModMatrix`]: struct.ModMatrix.html
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a modulation source, returning its handle.
+    pub fn add_source(&mut self, source: GeneratorSP) -> usize {
+        self.sources.push(source);
+        self.values.push(SampleT::default());
+
+        self.sources.len() - 1
+    }
+
+    /// Adds a route from the given source handle to the destination with the
+    /// given depth, returning the route's handle.
+    pub fn add_route(
+        &mut self,
+        source: usize,
+        destination: ModDestination,
+        depth: SampleT,
+    ) -> usize {
+        self.routes.push((source, destination, depth));
+
+        self.routes.len() - 1
+    }
+
+    /// Sets the depth of the given route. Returns `false` if no such route
+    /// exists.
+    pub fn set_depth(&mut self, route: usize, depth: SampleT) -> bool {
+        if let Some(r) = self.routes.get_mut(route) {
+            r.2 = depth;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the number of routes in the matrix.
+    pub fn route_count(&self) -> usize {
+        self.routes.len()
+    }
+
+    /// Processes every source once, storing their outputs and the summed
+    /// modulation of each parameter destination for the current sample.
+    pub(crate) fn process(&mut self) {
+        for (source, value) in self.sources.iter_mut().zip(self.values.iter_mut()) {
+            if let Ok(mut s) = source.lock() {
+                *value = s.process();
+            }
+        }
+
+        self.parameter_sums.clear();
+        for (i, (s, d, depth)) in self.routes.iter().enumerate() {
+            if let ModDestination::Parameter { .. } = d {
+                let m = self.values.get(*s).map_or(0.0, |v| v * depth);
+                let routes = &self.routes;
+
+                match self
+                    .parameter_sums
+                    .iter_mut()
+                    .find(|(r, _)| routes[*r].1 == *d)
+                {
+                    Some(sum) => sum.1 += m,
+                    None => self.parameter_sums.push((i, m)),
+                }
+            }
+        }
+    }
+
+    /// Returns the summed modulation for the given destination for the current
+    /// sample.
+    pub(crate) fn modulation(&self, destination: &ModDestination) -> SampleT {
+        self.routes
+            .iter()
+            .filter(|(_, d, _)| d == destination)
+            .filter_map(|(s, _, depth)| self.values.get(*s).map(|v| v * depth))
+            .sum()
+    }
+
+    /// Returns every parameter destination with at least one route, once
+    /// each, along with its summed modulation for the current sample.
+    pub(crate) fn parameter_modulations(&self) -> impl Iterator<Item = (usize, &str, SampleT)> {
+        self.parameter_sums
+            .iter()
+            .filter_map(move |(r, m)| match self.routes.get(*r) {
+                Some((_, ModDestination::Parameter { block, name }, _)) => {
+                    Some((*block, name.as_str(), *m))
+                }
+                _ => None,
+            })
+    }
+
+    /// Returns the base value of the given parameter, if it has been
+    /// modulated.
+    pub(crate) fn get_base(&self, block: usize, name: &str) -> Option<MathT> {
+        self.bases
+            .iter()
+            .find(|(b, n, _)| *b == block && n == name)
+            .map(|(_, _, v)| *v)
+    }
+
+    /// Sets the base value of the given parameter, which modulation is
+    /// applied around.
+    pub(crate) fn set_base(&mut self, block: usize, name: &str, value: MathT) {
+        match self
+            .bases
+            .iter_mut()
+            .find(|(b, n, _)| *b == block && n == name)
+        {
+            Some(base) => base.2 = value,
+            None => self.bases.push((block, name.to_owned(), value)),
+        }
+    }
+
+    /// Shifts the parameter routes and base values of every block from the
+    /// given index onwards up by one, making room for a block inserted there.
+    pub(crate) fn insert_block(&mut self, index: usize) {
+        for (_, d, _) in &mut self.routes {
+            if let ModDestination::Parameter { block, .. } = d {
+                if *block >= index {
+                    *block += 1;
+                }
+            }
+        }
+
+        for (block, _, _) in &mut self.bases {
+            if *block >= index {
+                *block += 1;
+            }
+        }
+    }
+
+    /// Removes the parameter routes and base values of the blocks in the given
+    /// range, shifting those of every later block down to close the gap.
+    /// Handles of later routes shift down with them.
+    pub(crate) fn remove_blocks(&mut self, blocks: std::ops::Range<usize>) {
+        let removed = blocks.len();
+        let shift = |block: &mut usize| {
+            if *block >= blocks.end {
+                *block -= removed;
+            }
+        };
+
+        self.routes.retain(|(_, d, _)| match d {
+            ModDestination::Parameter { block, .. } => !blocks.contains(block),
+            _ => true,
+        });
+        for (_, d, _) in &mut self.routes {
+            if let ModDestination::Parameter { block, .. } = d {
+                shift(block);
+            }
+        }
+
+        self.parameter_sums.clear();
+        self.bases.retain(|(block, _, _)| !blocks.contains(block));
+        for (block, _, _) in &mut self.bases {
+            shift(block);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! # Modifiers
+//!
+//! This module includes [`Modifier`]s built on top of the Broad Audio Engine
+//! that are usable within a [`BaeBlock`] through [`BaeBlock::from_modifier`].
+//!
+//! [`Modifier`]: ../../modifiers/trait.Modifier.html
+//! [`BaeBlock`]: ../bae_block/struct.BaeBlock.html
+//! [`BaeBlock::from_modifier`]: ../bae_block/struct.BaeBlock.html#method.from_modifier
+
+use super::*;
+
+use bae_mod::*;
+use std::time::Duration;
+
+pub mod biquad;
+pub mod compressor;
+pub mod dry_wet;
+pub mod fdn_reverb;
+pub mod fir;
+pub mod frequency_shifter;
+pub mod linearity_probe;
+pub mod mid_side;
+pub mod rms_follower;
+pub mod scale_quantizer;
+pub mod smoothed;
+pub mod transient_shaper;
+
+pub use biquad::*;
+pub use compressor::*;
+pub use dry_wet::*;
+pub use fdn_reverb::*;
+pub use fir::*;
+pub use frequency_shifter::*;
+pub use linearity_probe::*;
+pub use mid_side::*;
+pub use rms_follower::*;
+pub use scale_quantizer::*;
+pub use smoothed::*;
+pub use transient_shaper::*;
+
+/// Converts a time constant into the coefficient of a one-pole smoothing
+/// filter running at the given sample rate. A zero time constant results in no
+/// smoothing.
+fn time_constant(d: Duration, sample_rate: MathT) -> MathT {
+    let samples = d.as_secs_f64() * sample_rate;
+
+    if samples > 0.0 {
+        (-1.0 / samples).exp()
+    } else {
+        0.0
+    }
+}
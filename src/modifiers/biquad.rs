@@ -0,0 +1,319 @@
+//! # Biquad
+//!
+//! A second-order IIR [`Modifier`] implementing the common filter shapes from
+//! Robert Bristow-Johnson's Audio EQ Cookbook.
+//!
+//! [`Modifier`]: ../../modifiers/trait.Modifier.html
+
+use super::*;
+
+use std::f64::consts::PI;
+
+/// The lowest frequency a [`Biquad`] can be set to.
+///
+/// [`Biquad`]: struct.Biquad.html
+const MIN_FREQUENCY: MathT = 0.01;
+
+/// The proportion of the Nyquist frequency a [`Biquad`]'s frequency is capped
+/// at.
+///
+/// [`Biquad`]: struct.Biquad.html
+const MAX_NYQUIST_RATIO: MathT = 0.999;
+
+/// The lowest quality factor a [`Biquad`] can be set to.
+///
+/// [`Biquad`]: struct.Biquad.html
+const MIN_Q: MathT = 0.01;
+
+/// The filter shapes a [`Biquad`] can take.
+///
+/// [`Biquad`]: struct.Biquad.html
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BiquadKind {
+    /// Second-order low-pass filter.
+    LowPass,
+    /// Second-order high-pass filter.
+    HighPass,
+    /// Band-pass filter with a constant 0dB peak gain.
+    BandPass,
+    /// Shelving filter boosting or cutting below the frequency.
+    LowShelf,
+    /// Peaking filter boosting or cutting around the frequency.
+    Peak,
+    /// Shelving filter boosting or cutting above the frequency.
+    HighShelf,
+}
+
+/// Struct implementing a biquad filter. The gain parameter only affects the
+/// shelving and peaking shapes.
+///
+/// To keep the coefficients valid, frequencies are clamped to lie between DC
+/// and just below the Nyquist frequency, and quality factors are clamped to be
+/// positive.
+#[derive(Copy, Clone)]
+pub struct Biquad {
+    kind: BiquadKind,
+    frequency: MathT,
+    q: MathT,
+    gain_db: MathT,
+    sample_rate: MathT,
+    b0: MathT,
+    b1: MathT,
+    b2: MathT,
+    a1: MathT,
+    a2: MathT,
+    x1: MathT,
+    x2: MathT,
+    y1: MathT,
+    y2: MathT,
+}
+
+impl Biquad {
+    /// Constructs a new [`Biquad`] of the given shape.
+    ///
+    /// # Parameters
+    ///
+    /// * `kind` - The shape of the filter.
+    /// * `frequency` - The cutoff, center, or corner frequency of the filter.
+    /// * `q` - The quality factor of the filter.
+    /// * `gain_db` - The boost or cut of the shelving and peaking shapes.
+    /// * `sample_rate` - The sample rate of the filtered audio.
+    ///
+    /// [`Biquad`]: struct.Biquad.html
+    pub fn new(
+        kind: BiquadKind,
+        frequency: MathT,
+        q: MathT,
+        gain_db: MathT,
+        sample_rate: MathT,
+    ) -> Self {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        let mut b = Biquad {
+            kind,
+            frequency: Self::clamp_frequency(frequency, sample_rate),
+            q: Self::clamp_q(q),
+            gain_db,
+            sample_rate,
+            b0: 1.0,
+            b1: 0.0,
+            b2: 0.0,
+            a1: 0.0,
+            a2: 0.0,
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        };
+
+        b.calculate_coefficients();
+
+        b
+    }
+
+    /// Constructs a new low-pass [`Biquad`].
+    ///
+    /// [`Biquad`]: struct.Biquad.html
+    pub fn low_pass(frequency: MathT, q: MathT, sample_rate: MathT) -> Self {
+        Self::new(BiquadKind::LowPass, frequency, q, 0.0, sample_rate)
+    }
+
+    /// Constructs a new high-pass [`Biquad`].
+    ///
+    /// [`Biquad`]: struct.Biquad.html
+    pub fn high_pass(frequency: MathT, q: MathT, sample_rate: MathT) -> Self {
+        Self::new(BiquadKind::HighPass, frequency, q, 0.0, sample_rate)
+    }
+
+    /// Constructs a new band-pass [`Biquad`].
+    ///
+    /// [`Biquad`]: struct.Biquad.html
+    pub fn band_pass(frequency: MathT, q: MathT, sample_rate: MathT) -> Self {
+        Self::new(BiquadKind::BandPass, frequency, q, 0.0, sample_rate)
+    }
+
+    /// Constructs a new low-shelf [`Biquad`].
+    ///
+    /// [`Biquad`]: struct.Biquad.html
+    pub fn low_shelf(frequency: MathT, q: MathT, gain_db: MathT, sample_rate: MathT) -> Self {
+        Self::new(BiquadKind::LowShelf, frequency, q, gain_db, sample_rate)
+    }
+
+    /// Constructs a new peaking [`Biquad`].
+    ///
+    /// [`Biquad`]: struct.Biquad.html
+    pub fn peak(frequency: MathT, q: MathT, gain_db: MathT, sample_rate: MathT) -> Self {
+        Self::new(BiquadKind::Peak, frequency, q, gain_db, sample_rate)
+    }
+
+    /// Constructs a new high-shelf [`Biquad`].
+    ///
+    /// [`Biquad`]: struct.Biquad.html
+    pub fn high_shelf(frequency: MathT, q: MathT, gain_db: MathT, sample_rate: MathT) -> Self {
+        Self::new(BiquadKind::HighShelf, frequency, q, gain_db, sample_rate)
+    }
+
+    /// Returns the shape of the filter.
+    pub fn get_kind(&self) -> BiquadKind {
+        self.kind
+    }
+
+    /// Returns the cutoff, center, or corner frequency of the filter.
+    pub fn get_frequency(&self) -> MathT {
+        self.frequency
+    }
+
+    /// Sets the cutoff, center, or corner frequency of the filter, clamped to
+    /// the valid range. Returns `true` if the frequency had to be clamped.
+    pub fn set_frequency(&mut self, frequency: MathT) -> bool {
+        let clamped = Self::clamp_frequency(frequency, self.sample_rate);
+
+        self.frequency = clamped;
+        self.calculate_coefficients();
+
+        clamped != frequency
+    }
+
+    /// Returns the quality factor of the filter.
+    pub fn get_q(&self) -> MathT {
+        self.q
+    }
+
+    /// Sets the quality factor of the filter, clamped to be positive. Returns
+    /// `true` if the quality factor had to be clamped.
+    pub fn set_q(&mut self, q: MathT) -> bool {
+        let clamped = Self::clamp_q(q);
+
+        self.q = clamped;
+        self.calculate_coefficients();
+
+        clamped != q
+    }
+
+    /// Returns the boost or cut of the shelving and peaking shapes in decibels.
+    pub fn get_gain_db(&self) -> MathT {
+        self.gain_db
+    }
+
+    /// Sets the boost or cut of the shelving and peaking shapes in decibels.
+    pub fn set_gain_db(&mut self, gain_db: MathT) {
+        self.gain_db = gain_db;
+        self.calculate_coefficients();
+    }
+
+    fn clamp_frequency(frequency: MathT, sample_rate: MathT) -> MathT {
+        if frequency.is_nan() {
+            MIN_FREQUENCY
+        } else {
+            frequency
+                .max(MIN_FREQUENCY)
+                .min(MAX_NYQUIST_RATIO * sample_rate / 2.0)
+        }
+    }
+
+    fn clamp_q(q: MathT) -> MathT {
+        if q.is_nan() {
+            MIN_Q
+        } else {
+            q.max(MIN_Q)
+        }
+    }
+
+    fn calculate_coefficients(&mut self) {
+        let a = (10.0 as MathT).powf(self.gain_db / 40.0);
+        let w0 = 2.0 * PI * self.frequency / self.sample_rate;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2.0 * self.q);
+        let sqrt_a = 2.0 * a.sqrt() * alpha;
+
+        let (b0, b1, b2, a0, a1, a2) = match self.kind {
+            BiquadKind::LowPass => (
+                (1.0 - cos) / 2.0,
+                1.0 - cos,
+                (1.0 - cos) / 2.0,
+                1.0 + alpha,
+                -2.0 * cos,
+                1.0 - alpha,
+            ),
+            BiquadKind::HighPass => (
+                (1.0 + cos) / 2.0,
+                -(1.0 + cos),
+                (1.0 + cos) / 2.0,
+                1.0 + alpha,
+                -2.0 * cos,
+                1.0 - alpha,
+            ),
+            BiquadKind::BandPass => (alpha, 0.0, -alpha, 1.0 + alpha, -2.0 * cos, 1.0 - alpha),
+            BiquadKind::LowShelf => (
+                a * ((a + 1.0) - (a - 1.0) * cos + sqrt_a),
+                2.0 * a * ((a - 1.0) - (a + 1.0) * cos),
+                a * ((a + 1.0) - (a - 1.0) * cos - sqrt_a),
+                (a + 1.0) + (a - 1.0) * cos + sqrt_a,
+                -2.0 * ((a - 1.0) + (a + 1.0) * cos),
+                (a + 1.0) + (a - 1.0) * cos - sqrt_a,
+            ),
+            BiquadKind::Peak => (
+                1.0 + alpha * a,
+                -2.0 * cos,
+                1.0 - alpha * a,
+                1.0 + alpha / a,
+                -2.0 * cos,
+                1.0 - alpha / a,
+            ),
+            BiquadKind::HighShelf => (
+                a * ((a + 1.0) + (a - 1.0) * cos + sqrt_a),
+                -2.0 * a * ((a - 1.0) + (a + 1.0) * cos),
+                a * ((a + 1.0) + (a - 1.0) * cos - sqrt_a),
+                (a + 1.0) - (a - 1.0) * cos + sqrt_a,
+                2.0 * ((a - 1.0) - (a + 1.0) * cos),
+                (a + 1.0) - (a - 1.0) * cos - sqrt_a,
+            ),
+        };
+
+        self.b0 = b0 / a0;
+        self.b1 = b1 / a0;
+        self.b2 = b2 / a0;
+        self.a1 = a1 / a0;
+        self.a2 = a2 / a0;
+    }
+}
+
+impl Parameterized for Biquad {
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        vec![
+            ("frequency".to_owned(), self.frequency),
+            ("q".to_owned(), self.q),
+            ("gain_db".to_owned(), self.gain_db),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        match name {
+            "frequency" => {
+                self.set_frequency(value);
+            }
+            "q" => {
+                self.set_q(value);
+            }
+            "gain_db" => self.set_gain_db(value),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl Modifier for Biquad {
+    fn process(&mut self, x: SampleT) -> SampleT {
+        let x = x as MathT;
+        let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2
+            - self.a1 * self.y1
+            - self.a2 * self.y2;
+
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
+
+        y as SampleT
+    }
+}
@@ -0,0 +1,159 @@
+//! # Compressor
+//!
+//! A feed-forward dynamic range compressor [`Modifier`].
+//!
+//! [`Modifier`]: ../../modifiers/trait.Modifier.html
+
+use super::*;
+
+/// Struct implementing a peak-sensing compressor. Levels above the threshold
+/// are reduced by the given ratio, with the level detector smoothed by the
+/// attack and release times. The knee is hard by default; see [`set_knee`].
+///
+/// [`set_knee`]: struct.Compressor.html#method.set_knee
+#[derive(Copy, Clone)]
+pub struct Compressor {
+    threshold_db: MathT,
+    ratio: MathT,
+    knee_db: MathT,
+    attack: MathT,
+    release: MathT,
+    sample_rate: MathT,
+    envelope: MathT,
+    reduction_db: MathT,
+}
+
+impl Compressor {
+    /// Constructs a new [`Compressor`].
+    ///
+    /// # Parameters
+    ///
+    /// * `threshold_db` - The level in decibels above which gain reduction is
+    /// applied.
+    /// * `ratio` - The compression ratio, e.g. `4.0` for 4:1 compression.
+    /// * `attack` - The time taken for the level detector to rise.
+    /// * `release` - The time taken for the level detector to fall.
+    /// * `sample_rate` - The sample rate of the processed audio.
+    ///
+    /// [`Compressor`]: struct.Compressor.html
+    pub fn new(
+        threshold_db: MathT,
+        ratio: MathT,
+        attack: Duration,
+        release: Duration,
+        sample_rate: MathT,
+    ) -> Self {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        Compressor {
+            threshold_db,
+            ratio,
+            knee_db: 0.0,
+            attack: time_constant(attack, sample_rate),
+            release: time_constant(release, sample_rate),
+            sample_rate,
+            envelope: 0.0,
+            reduction_db: 0.0,
+        }
+    }
+
+    /// Returns the threshold in decibels.
+    pub fn get_threshold_db(&self) -> MathT {
+        self.threshold_db
+    }
+
+    /// Sets the threshold in decibels.
+    pub fn set_threshold_db(&mut self, threshold_db: MathT) {
+        self.threshold_db = threshold_db;
+    }
+
+    /// Returns the compression ratio.
+    pub fn get_ratio(&self) -> MathT {
+        self.ratio
+    }
+
+    /// Sets the compression ratio.
+    pub fn set_ratio(&mut self, ratio: MathT) {
+        self.ratio = ratio;
+    }
+
+    /// Returns the width of the knee in decibels.
+    pub fn get_knee(&self) -> MathT {
+        self.knee_db
+    }
+
+    /// Sets the width of the knee in decibels. Within `knee_db / 2` of the
+    /// threshold the gain reduction follows a quadratic curve, easing into the
+    /// full ratio instead of switching on at the threshold. A knee of `0.0`
+    /// gives a hard knee. Negative widths are treated as `0.0`.
+    pub fn set_knee(&mut self, knee_db: MathT) {
+        self.knee_db = knee_db.max(0.0);
+    }
+
+    /// Sets the attack time of the level detector.
+    pub fn set_attack(&mut self, d: Duration) {
+        self.attack = time_constant(d, self.sample_rate);
+    }
+
+    /// Sets the release time of the level detector.
+    pub fn set_release(&mut self, d: Duration) {
+        self.release = time_constant(d, self.sample_rate);
+    }
+
+    /// Returns the gain reduction in decibels applied to the most recently
+    /// processed sample.
+    pub fn get_gain_reduction_db(&self) -> MathT {
+        self.reduction_db
+    }
+
+    fn gain_reduction_db(&self, level_db: MathT) -> MathT {
+        let over = level_db - self.threshold_db;
+        let slope = 1.0 - 1.0 / self.ratio;
+        let half_knee = self.knee_db / 2.0;
+
+        if over <= -half_knee {
+            0.0
+        } else if over < half_knee {
+            slope * (over + half_knee).powi(2) / (2.0 * self.knee_db)
+        } else {
+            slope * over
+        }
+    }
+}
+
+impl Parameterized for Compressor {
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        vec![
+            ("threshold_db".to_owned(), self.get_threshold_db()),
+            ("ratio".to_owned(), self.get_ratio()),
+            ("knee_db".to_owned(), self.get_knee()),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        match name {
+            "threshold_db" => self.set_threshold_db(value),
+            "ratio" => self.set_ratio(value),
+            "knee_db" => self.set_knee(value),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl Modifier for Compressor {
+    fn process(&mut self, x: SampleT) -> SampleT {
+        let level = (x as MathT).abs();
+        let coeff = if level > self.envelope {
+            self.attack
+        } else {
+            self.release
+        };
+        self.envelope = coeff * self.envelope + (1.0 - coeff) * level;
+
+        let level_db = linear_to_db(self.envelope.max(1e-10));
+        self.reduction_db = self.gain_reduction_db(level_db);
+
+        (x as MathT * db_to_linear(-self.reduction_db)) as SampleT
+    }
+}
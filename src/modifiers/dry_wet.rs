@@ -0,0 +1,98 @@
+//! # Dry/Wet
+//!
+//! A [`Modifier`] wrapper blending the unprocessed input with the output of
+//! the wrapped [`Modifier`], as found on the mix control of most effects.
+//!
+//! [`Modifier`]: ../../modifiers/trait.Modifier.html
+
+use super::*;
+
+/// Struct wrapping a [`Modifier`], outputting `dry * (1 - mix) + wet * mix`,
+/// where `dry` is the input sample and `wet` the output of the wrapped
+/// [`Modifier`]. The wrapped [`Modifier`] is processed regardless of the mix,
+/// so raising the mix doesn't reveal stale state.
+///
+/// The mix is exposed as the `"mix"` parameter through [`Parameterized`], so
+/// it can be automated by name.
+///
+/// [`Modifier`]: ../../modifiers/trait.Modifier.html
+/// [`Parameterized`]: ../../trait.Parameterized.html
+#[derive(Clone)]
+pub struct DryWet<M>
+where
+    M: Modifier,
+{
+    inner: M,
+    mix: MathT,
+}
+
+impl<M> DryWet<M>
+where
+    M: Modifier,
+{
+    /// Constructs a new [`DryWet`] around the given [`Modifier`] with the
+    /// given mix, clamped to `[0, 1]`.
+    ///
+    /// [`DryWet`]: struct.DryWet.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn new(inner: M, mix: MathT) -> Self {
+        DryWet {
+            inner,
+            mix: mix.max(0.0).min(1.0),
+        }
+    }
+
+    /// Returns the mix, where `0` is fully dry and `1` fully wet.
+    pub fn get_mix(&self) -> MathT {
+        self.mix
+    }
+
+    /// Sets the mix, where `0` is fully dry and `1` fully wet, clamped to
+    /// `[0, 1]`.
+    pub fn set_mix(&mut self, mix: MathT) {
+        self.mix = mix.max(0.0).min(1.0);
+    }
+
+    /// Returns a reference to the wrapped [`Modifier`].
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn get_inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped [`Modifier`].
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn get_inner_mut(&mut self) -> &mut M {
+        &mut self.inner
+    }
+}
+
+impl<M> Parameterized for DryWet<M>
+where
+    M: Modifier,
+{
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        vec![("mix".to_owned(), self.mix)]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        match name {
+            "mix" => self.set_mix(value),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl<M> Modifier for DryWet<M>
+where
+    M: Modifier,
+{
+    fn process(&mut self, x: SampleT) -> SampleT {
+        let wet = self.inner.process(x) as MathT;
+
+        (x as MathT * (1.0 - self.mix) + wet * self.mix) as SampleT
+    }
+}
@@ -0,0 +1,221 @@
+//! # FDN Reverb
+//!
+//! A feedback delay network [`Modifier`] producing a dense algorithmic reverb
+//! tail.
+//!
+//! [`Modifier`]: ../../modifiers/trait.Modifier.html
+
+use super::*;
+
+/// Struct implementing a feedback delay network reverb. The outputs of a set of
+/// delay lines are attenuated, lowpass filtered, mixed through a lossless
+/// feedback matrix, and fed back into the lines along with the input. The
+/// output is the wet signal only.
+///
+/// Each line's feedback gain is derived from its length so that the tail
+/// decays by 60dB over the configured RT60. Damping additionally shortens the
+/// decay of high frequencies. Mutually prime line lengths give the densest
+/// tails.
+///
+/// The feedback matrix defaults to a normalized Hadamard matrix when the
+/// number of lines is a power of two, and to a Householder reflection
+/// otherwise.
+#[derive(Clone)]
+pub struct FdnReverb {
+    lines: Vec<Vec<MathT>>,
+    positions: Vec<usize>,
+    gains: Vec<MathT>,
+    filters: Vec<MathT>,
+    taps: Vec<MathT>,
+    matrix: Vec<Vec<MathT>>,
+    rt60: Duration,
+    damping: MathT,
+    sample_rate: MathT,
+}
+
+impl FdnReverb {
+    /// Constructs a new [`FdnReverb`].
+    ///
+    /// # Parameters
+    ///
+    /// * `lengths` - The length of each delay line in samples. Lengths of zero
+    /// are treated as one sample.
+    /// * `rt60` - The time taken for the tail to decay by 60dB.
+    /// * `damping` - The amount of high frequency damping within the loop,
+    /// from `0.0` for none to just below `1.0` for heavy damping.
+    /// * `sample_rate` - The sample rate of the processed audio.
+    ///
+    /// [`FdnReverb`]: struct.FdnReverb.html
+    pub fn new(lengths: Vec<usize>, rt60: Duration, damping: MathT, sample_rate: MathT) -> Self {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        let n = lengths.len();
+
+        let mut reverb = FdnReverb {
+            lines: lengths.iter().map(|l| vec![0.0; (*l).max(1)]).collect(),
+            positions: vec![0; n],
+            gains: vec![0.0; n],
+            filters: vec![0.0; n],
+            taps: vec![0.0; n],
+            matrix: Self::default_matrix(n),
+            rt60,
+            damping: 0.0,
+            sample_rate,
+        };
+        reverb.set_rt60(rt60);
+        reverb.set_damping(damping);
+
+        reverb
+    }
+
+    /// Returns the number of delay lines.
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns the time taken for the tail to decay by 60dB.
+    pub fn get_rt60(&self) -> Duration {
+        self.rt60
+    }
+
+    /// Sets the time taken for the tail to decay by 60dB.
+    pub fn set_rt60(&mut self, rt60: Duration) {
+        self.rt60 = rt60;
+
+        let samples = rt60.as_secs_f64() * self.sample_rate;
+        for (g, line) in self.gains.iter_mut().zip(&self.lines) {
+            *g = if samples > 0.0 {
+                (10.0 as MathT).powf(-3.0 * line.len() as MathT / samples)
+            } else {
+                0.0
+            };
+        }
+    }
+
+    /// Returns the amount of high frequency damping.
+    pub fn get_damping(&self) -> MathT {
+        self.damping
+    }
+
+    /// Sets the amount of high frequency damping, clamped to `[0.0, 0.99]`.
+    pub fn set_damping(&mut self, damping: MathT) {
+        self.damping = damping.max(0.0).min(0.99);
+    }
+
+    /// Sets the feedback matrix. To keep the network stable, the matrix must
+    /// be square with a side equal to [`line_count`] and orthogonal, so that it
+    /// neither adds nor removes energy. Returns `false` and keeps the current
+    /// matrix if these conditions aren't met.
+    ///
+    /// [`line_count`]: struct.FdnReverb.html#method.line_count
+    pub fn set_matrix(&mut self, matrix: Vec<Vec<MathT>>) -> bool {
+        let n = self.lines.len();
+
+        if matrix.len() != n || matrix.iter().any(|r| r.len() != n) {
+            return false;
+        }
+
+        for (i, a) in matrix.iter().enumerate() {
+            for (j, b) in matrix.iter().enumerate() {
+                let dot: MathT = a.iter().zip(b).map(|(x, y)| x * y).sum();
+                let expected = if i == j { 1.0 } else { 0.0 };
+
+                if (dot - expected).abs() > 1e-6 {
+                    return false;
+                }
+            }
+        }
+
+        self.matrix = matrix;
+        true
+    }
+
+    fn default_matrix(n: usize) -> Vec<Vec<MathT>> {
+        if n.is_power_of_two() {
+            let mut h = vec![vec![1.0]];
+            while h.len() < n {
+                let top = h.iter().map(|r| [&r[..], &r[..]].concat());
+                let bottom = h.iter().map(|r| {
+                    let neg: Vec<MathT> = r.iter().map(|x| -x).collect();
+                    [&r[..], &neg[..]].concat()
+                });
+                h = top.chain(bottom).collect();
+            }
+
+            let scale = 1.0 / (n as MathT).sqrt();
+            h.iter()
+                .map(|r| r.iter().map(|x| x * scale).collect())
+                .collect()
+        } else {
+            (0..n)
+                .map(|i| {
+                    (0..n)
+                        .map(|j| (if i == j { 1.0 } else { 0.0 }) - 2.0 / n as MathT)
+                        .collect()
+                })
+                .collect()
+        }
+    }
+}
+
+impl Parameterized for FdnReverb {
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        vec![
+            ("rt60".to_owned(), self.get_rt60().as_secs_f64()),
+            ("damping".to_owned(), self.get_damping()),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        match name {
+            "rt60" => self.set_rt60(Duration::from_secs_f64(value.max(0.0))),
+            "damping" => self.set_damping(value),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl CostHint for FdnReverb {
+    /// Estimates the cost from the multiply-adds of the feedback matrix plus
+    /// the gain and damping filter of each line, counting five multiply-adds
+    /// as one simple filter.
+    fn cost_hint(&self) -> u32 {
+        let n = self.lines.len() as u32;
+
+        ((n * n + 3 * n + 4) / 5).max(1)
+    }
+}
+
+impl Modifier for FdnReverb {
+    fn process(&mut self, x: SampleT) -> SampleT {
+        if self.lines.is_empty() {
+            return Default::default();
+        }
+
+        let mut y = 0.0;
+        for (i, line) in self.lines.iter().enumerate() {
+            let tap = line[self.positions[i]];
+            y += tap;
+
+            self.filters[i] =
+                (1.0 - self.damping) * tap * self.gains[i] + self.damping * self.filters[i];
+        }
+
+        for (t, row) in self.taps.iter_mut().zip(&self.matrix) {
+            *t = row.iter().zip(&self.filters).map(|(m, f)| m * f).sum();
+        }
+
+        for ((line, p), t) in self
+            .lines
+            .iter_mut()
+            .zip(&mut self.positions)
+            .zip(&self.taps)
+        {
+            line[*p] = x as MathT + t;
+            *p = (*p + 1) % line.len();
+        }
+
+        (y / self.lines.len() as MathT) as SampleT
+    }
+}
@@ -0,0 +1,84 @@
+//! # FIR
+//!
+//! A [`Modifier`] convolving its input with a user-supplied array of FIR
+//! coefficients, granting the ability to use filters designed externally.
+//!
+//! [`Modifier`]: ../../modifiers/trait.Modifier.html
+
+use super::*;
+
+/// Struct implementing a direct-form FIR filter. An empty list of taps passes
+/// the input through unchanged.
+#[derive(Clone)]
+pub struct Fir {
+    taps: Vec<SampleT>,
+    history: Vec<SampleT>,
+    position: usize,
+}
+
+impl Fir {
+    /// Constructs a new [`Fir`] from the given coefficients, where `taps[0]`
+    /// is applied to the most recent input sample.
+    ///
+    /// [`Fir`]: struct.Fir.html
+    pub fn new(taps: Vec<SampleT>) -> Self {
+        Fir {
+            history: vec![SampleT::default(); taps.len()],
+            taps,
+            position: 0,
+        }
+    }
+
+    /// Returns the coefficients of the filter.
+    pub fn get_taps(&self) -> &[SampleT] {
+        &self.taps
+    }
+
+    /// Returns the latency of the filter in samples, assuming a linear-phase
+    /// design. This is `(taps - 1) / 2`.
+    pub fn latency(&self) -> usize {
+        self.taps.len().saturating_sub(1) / 2
+    }
+
+    /// Pushes a sample into the history without computing an output, for
+    /// callers that discard it, such as a decimator.
+    pub(crate) fn push(&mut self, x: SampleT) {
+        let len = self.taps.len();
+
+        if len == 0 {
+            return;
+        }
+
+        self.history[self.position] = x;
+        self.position = (self.position + 1) % len;
+    }
+}
+
+impl CostHint for Fir {
+    /// Estimates the cost as one simple filter per five taps, as a biquad
+    /// takes about five multiply-adds per sample.
+    fn cost_hint(&self) -> u32 {
+        ((self.taps.len() as u32 + 4) / 5).max(1)
+    }
+}
+
+impl Modifier for Fir {
+    fn process(&mut self, x: SampleT) -> SampleT {
+        let len = self.taps.len();
+
+        if len == 0 {
+            return x;
+        }
+
+        self.history[self.position] = x;
+
+        let mut y = SampleT::default();
+        for (k, tap) in self.taps.iter().enumerate() {
+            y += tap * self.history[(self.position + len - k) % len];
+        }
+
+        self.position = (self.position + 1) % len;
+
+        y
+    }
+}
@@ -0,0 +1,135 @@
+//! # Frequency Shifter
+//!
+//! A single-sideband [`Modifier`] shifting every frequency component of its
+//! input by a fixed amount, producing inharmonic textures.
+//!
+//! [`Modifier`]: ../../modifiers/trait.Modifier.html
+
+use super::*;
+
+use crate::generators::{FreqControllable, QuadOsc};
+
+/// Allpass coefficients of the in-phase branch of the Hilbert transformer.
+const IN_PHASE_COEFFS: [MathT; 4] = [
+    0.6923878,
+    0.9360654322959,
+    0.9882295226860,
+    0.9987488452737,
+];
+
+/// Allpass coefficients of the quadrature branch of the Hilbert transformer.
+const QUADRATURE_COEFFS: [MathT; 4] = [
+    0.4021921162426,
+    0.8561710882420,
+    0.9722909545651,
+    0.9952884791278,
+];
+
+/// Struct implementing a frequency shifter. Unlike pitch shifting, every
+/// component is moved by the same number of hertz, so harmonic relationships
+/// are not preserved.
+///
+/// The input is split into an analytic pair by two chains of second-order
+/// allpass filters whose outputs are 90° apart across most of the audible
+/// range, and the pair is then mixed with a [`QuadOsc`] to keep only one
+/// sideband. Frequencies within a few tens of hertz of DC or Nyquist are not
+/// shifted cleanly.
+///
+/// [`QuadOsc`]: ../../generators/quad_osc/struct.QuadOsc.html
+#[derive(Clone)]
+pub struct FrequencyShifter {
+    osc: QuadOsc,
+    in_phase: HilbertBranch,
+    quadrature: HilbertBranch,
+    delayed: MathT,
+}
+
+impl FrequencyShifter {
+    /// Constructs a new [`FrequencyShifter`] moving all frequencies by
+    /// `shift` hertz. Negative shifts move frequencies down.
+    ///
+    /// [`FrequencyShifter`]: struct.FrequencyShifter.html
+    pub fn new(shift: MathT, sample_rate: MathT) -> Self {
+        FrequencyShifter {
+            osc: QuadOsc::new(shift, sample_rate),
+            in_phase: HilbertBranch::new(IN_PHASE_COEFFS),
+            quadrature: HilbertBranch::new(QUADRATURE_COEFFS),
+            delayed: 0.0,
+        }
+    }
+
+    /// Returns the shift in hertz.
+    pub fn get_shift(&self) -> MathT {
+        self.osc.get_frequency()
+    }
+
+    /// Sets the shift in hertz.
+    pub fn set_shift(&mut self, shift: MathT) {
+        self.osc.set_frequency(shift);
+    }
+}
+
+impl Parameterized for FrequencyShifter {
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        vec![("shift".to_owned(), self.get_shift())]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        match name {
+            "shift" => self.set_shift(value),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl Modifier for FrequencyShifter {
+    fn process(&mut self, x: SampleT) -> SampleT {
+        let x = x as MathT;
+
+        let i = self.delayed;
+        self.delayed = self.in_phase.process(x);
+        let q = self.quadrature.process(x);
+
+        let [sin, cos] = self.osc.process_frame();
+
+        (i * cos as MathT + q * sin as MathT) as SampleT
+    }
+}
+
+/// A chain of second-order allpass sections, `y[n] = a²(x[n] + y[n-2]) -
+/// x[n-2]`, forming one branch of the Hilbert transformer.
+#[derive(Copy, Clone)]
+struct HilbertBranch {
+    coeffs: [MathT; 4],
+    x: [[MathT; 2]; 4],
+    y: [[MathT; 2]; 4],
+}
+
+impl HilbertBranch {
+    fn new(coeffs: [MathT; 4]) -> Self {
+        let mut squared = coeffs;
+        for a in &mut squared {
+            *a *= *a;
+        }
+
+        HilbertBranch {
+            coeffs: squared,
+            x: [[0.0; 2]; 4],
+            y: [[0.0; 2]; 4],
+        }
+    }
+
+    fn process(&mut self, mut s: MathT) -> MathT {
+        for ((a, x), y) in self.coeffs.iter().zip(&mut self.x).zip(&mut self.y) {
+            let out = a * (s + y[1]) - x[1];
+
+            *x = [s, x[0]];
+            *y = [out, y[0]];
+            s = out;
+        }
+
+        s
+    }
+}
@@ -0,0 +1,109 @@
+//! # Linearity Probe
+//!
+//! A [`Modifier`] wrapper measuring how closely the wrapped [`Modifier`]
+//! behaves like a pure gain, useful for automated testing of processing
+//! chains.
+//!
+//! [`Modifier`]: ../../modifiers/trait.Modifier.html
+
+use super::*;
+
+use std::collections::VecDeque;
+
+/// Struct wrapping a [`Modifier`], passing its output through unchanged while
+/// recording the input and output samples over a sliding window. From the
+/// window, the best-fit linear gain between input and output and the energy of
+/// the output not explained by that gain are calculated.
+///
+/// [`Modifier`]: ../../modifiers/trait.Modifier.html
+#[derive(Clone)]
+pub struct LinearityProbe<M>
+where
+    M: Modifier,
+{
+    inner: M,
+    window: usize,
+    samples: VecDeque<(MathT, MathT)>,
+}
+
+impl<M> LinearityProbe<M>
+where
+    M: Modifier,
+{
+    /// Constructs a new [`LinearityProbe`] around the given [`Modifier`],
+    /// measuring over the most recent `window` samples.
+    ///
+    /// [`LinearityProbe`]: struct.LinearityProbe.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn new(inner: M, window: usize) -> Self {
+        LinearityProbe {
+            inner,
+            window,
+            samples: VecDeque::with_capacity(window),
+        }
+    }
+
+    /// Returns the least-squares gain relating the recorded input to the
+    /// recorded output. Returns `0.0` if the recorded input is silent.
+    pub fn measured_gain(&self) -> MathT {
+        let (xx, xy, _) = self.sums();
+
+        if xx > 0.0 {
+            xy / xx
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the fraction of the recorded output energy that is not
+    /// explained by [`measured_gain`], ranging from `0.0` for a perfectly
+    /// linear [`Modifier`] to `1.0` for output entirely uncorrelated with the
+    /// input.
+    ///
+    /// [`measured_gain`]: struct.LinearityProbe.html#method.measured_gain
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn residual_nonlinearity(&self) -> MathT {
+        let (xx, xy, yy) = self.sums();
+
+        if yy <= 0.0 {
+            0.0
+        } else if xx <= 0.0 {
+            1.0
+        } else {
+            ((yy - xy * xy / xx) / yy).max(0.0)
+        }
+    }
+
+    /// Returns a reference to the wrapped [`Modifier`].
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn get_inner(&self) -> &M {
+        &self.inner
+    }
+
+    fn sums(&self) -> (MathT, MathT, MathT) {
+        self.samples
+            .iter()
+            .fold((0.0, 0.0, 0.0), |(xx, xy, yy), (x, y)| {
+                (xx + x * x, xy + x * y, yy + y * y)
+            })
+    }
+}
+
+impl<M> Modifier for LinearityProbe<M>
+where
+    M: Modifier,
+{
+    fn process(&mut self, x: SampleT) -> SampleT {
+        let y = self.inner.process(x);
+
+        if self.window > 0 {
+            if self.samples.len() == self.window {
+                self.samples.pop_front();
+            }
+            self.samples.push_back((x as MathT, y as MathT));
+        }
+
+        y
+    }
+}
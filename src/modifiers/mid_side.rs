@@ -0,0 +1,73 @@
+//! # Mid/Side
+//!
+//! A stereo processor encoding left/right audio into mid/side, processing each
+//! with its own [`Modifier`], and decoding back to left/right.
+//!
+//! [`Modifier`]: ../../modifiers/trait.Modifier.html
+
+use super::*;
+
+/// Struct implementing mid/side processing. The mid channel is `(L + R) / 2`
+/// and the side channel is `(L - R) / 2`, so passing both through unchanged is
+/// transparent.
+///
+/// As [`Modifier`]s process a single channel, [`MidSide`] exposes its own
+/// stereo [`process`] instead of implementing [`Modifier`]. Mono input, with
+/// equal left and right samples, has no side content, so the side
+/// [`Modifier`] only ever sees silence and the output stays mono.
+///
+/// [`Modifier`]: ../../modifiers/trait.Modifier.html
+/// [`MidSide`]: struct.MidSide.html
+/// [`process`]: struct.MidSide.html#method.process
+#[derive(Clone)]
+pub struct MidSide {
+    mid: ModifierSP,
+    side: ModifierSP,
+}
+
+impl MidSide {
+    /// Constructs a new [`MidSide`] processing the mid channel with `mid` and
+    /// the side channel with `side`.
+    ///
+    /// [`MidSide`]: struct.MidSide.html
+    pub fn new(mid: ModifierSP, side: ModifierSP) -> Self {
+        MidSide { mid, side }
+    }
+
+    /// Returns a mutable reference to the [`Modifier`] processing the mid
+    /// channel.
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn get_mid_mut(&mut self) -> &mut ModifierSP {
+        &mut self.mid
+    }
+
+    /// Returns a mutable reference to the [`Modifier`] processing the side
+    /// channel.
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn get_side_mut(&mut self) -> &mut ModifierSP {
+        &mut self.side
+    }
+
+    /// Processes a single stereo frame, returning the processed left and right
+    /// samples.
+    ///
+    /// If the mutex of either [`Modifier`] is poisoned, that channel is passed
+    /// through unprocessed rather than dropped.
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn process(&mut self, left: SampleT, right: SampleT) -> (SampleT, SampleT) {
+        let mut m = (left + right) * 0.5;
+        let mut s = (left - right) * 0.5;
+
+        if let Ok(mut mid) = self.mid.lock() {
+            m = mid.process(m);
+        }
+        if let Ok(mut side) = self.side.lock() {
+            s = side.process(s);
+        }
+
+        (m + s, m - s)
+    }
+}
@@ -0,0 +1,53 @@
+//! # RMS Follower
+//!
+//! A [`Modifier`] outputting the running RMS level of its input, usable as a
+//! control signal for envelope-following routing.
+//!
+//! [`Modifier`]: ../../modifiers/trait.Modifier.html
+
+use super::*;
+
+/// Struct implementing a true RMS level detector over a sliding rectangular
+/// window. Unlike an envelope follower, the output is the exact RMS of the
+/// most recent window of input samples, treating samples before the first
+/// processed sample as silence.
+#[derive(Clone)]
+pub struct RmsFollower {
+    squares: Vec<MathT>,
+    sum: MathT,
+    position: usize,
+}
+
+impl RmsFollower {
+    /// Constructs a new [`RmsFollower`] averaging over the given window. The
+    /// window is at least one sample long.
+    ///
+    /// [`RmsFollower`]: struct.RmsFollower.html
+    pub fn new(window: Duration, sample_rate: MathT) -> Self {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        let len = ((window.as_secs_f64() * sample_rate) as usize).max(1);
+
+        RmsFollower {
+            squares: vec![0.0; len],
+            sum: 0.0,
+            position: 0,
+        }
+    }
+
+    /// Returns the length of the window in samples.
+    pub fn window_len(&self) -> usize {
+        self.squares.len()
+    }
+}
+
+impl Modifier for RmsFollower {
+    fn process(&mut self, x: SampleT) -> SampleT {
+        let sq = (x as MathT) * (x as MathT);
+
+        self.sum += sq - self.squares[self.position];
+        self.squares[self.position] = sq;
+        self.position = (self.position + 1) % self.squares.len();
+
+        (self.sum.max(0.0) / self.squares.len() as MathT).sqrt() as SampleT
+    }
+}
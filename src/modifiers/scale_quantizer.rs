@@ -0,0 +1,121 @@
+//! # Scale Quantizer
+//!
+//! A [`Modifier`] snapping a frequency control signal to the notes of a
+//! musical scale, for pitched randomness that stays in key.
+//!
+//! [`Modifier`]: ../../modifiers/trait.Modifier.html
+
+use super::*;
+
+/// Struct implementing a pitch quantizer. The input sample is interpreted as a
+/// frequency in Hz, such as the output of a random source, and the output is
+/// the frequency of the nearest note of the scale, measured in semitones. The
+/// output is intended to drive the frequency of an oscillator, e.g. through
+/// [`FreqControllable::set_frequency`].
+///
+/// The scale is given by the frequency of its root and the intervals of its
+/// notes in semitones above the root, repeating every octave. Non-positive
+/// input frequencies result in an output of `0`.
+///
+/// [`Modifier`]: ../../modifiers/trait.Modifier.html
+/// [`FreqControllable::set_frequency`]: ../../generators/trait.FreqControllable.html#tymethod.set_frequency
+#[derive(Clone)]
+pub struct ScaleQuantizer {
+    root: MathT,
+    intervals: Vec<MathT>,
+}
+
+impl ScaleQuantizer {
+    /// Constructs a new [`ScaleQuantizer`] for the given scale. See
+    /// [`set_scale`].
+    ///
+    /// [`ScaleQuantizer`]: struct.ScaleQuantizer.html
+    /// [`set_scale`]: struct.ScaleQuantizer.html#method.set_scale
+    pub fn new(root: MathT, intervals: Vec<MathT>) -> Self {
+        let mut q = ScaleQuantizer {
+            root,
+            intervals: Vec::new(),
+        };
+        q.set_scale(root, intervals);
+
+        q
+    }
+
+    /// Returns the frequency of the root of the scale.
+    pub fn get_root(&self) -> MathT {
+        self.root
+    }
+
+    /// Returns the intervals of the scale in semitones above the root, sorted
+    /// and reduced to a single octave.
+    pub fn get_intervals(&self) -> &[MathT] {
+        &self.intervals
+    }
+
+    /// Sets the scale to the given root frequency and intervals in semitones
+    /// above it, e.g. `[0, 2, 4, 5, 7, 9, 11]` for a major scale. Intervals
+    /// are reduced to a single octave, and an empty list of intervals results
+    /// in the root alone.
+    pub fn set_scale(&mut self, root: MathT, intervals: Vec<MathT>) {
+        let mut intervals: Vec<MathT> = intervals.iter().map(|i| i.rem_euclid(12.0)).collect();
+        if intervals.is_empty() {
+            intervals.push(0.0);
+        }
+
+        intervals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        intervals.dedup();
+
+        self.root = root;
+        self.intervals = intervals;
+    }
+
+    /// Returns the frequency of the note of the scale nearest to the given
+    /// frequency.
+    pub fn quantize(&self, frequency: MathT) -> MathT {
+        if !frequency.is_finite() || frequency <= 0.0 || self.root <= 0.0 {
+            return 0.0;
+        }
+
+        let semitones = 12.0 * (frequency / self.root).log2();
+        let octave = (semitones / 12.0).floor();
+        let within = semitones - 12.0 * octave;
+
+        let above = self.intervals[0] + 12.0;
+        let below = self.intervals[self.intervals.len() - 1] - 12.0;
+
+        let nearest =
+            self.intervals
+                .iter()
+                .chain(&[above, below])
+                .fold(MathT::INFINITY, |best, i| {
+                    if (i - within).abs() < (best - within).abs() {
+                        *i
+                    } else {
+                        best
+                    }
+                });
+
+        self.root * ((12.0 * octave + nearest) / 12.0).exp2()
+    }
+}
+
+impl Parameterized for ScaleQuantizer {
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        vec![("root".to_owned(), self.root)]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        match name {
+            "root" => self.root = value,
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl Modifier for ScaleQuantizer {
+    fn process(&mut self, x: SampleT) -> SampleT {
+        self.quantize(x as MathT) as SampleT
+    }
+}
@@ -0,0 +1,124 @@
+//! # Smoothed
+//!
+//! A [`Modifier`] wrapper smoothing changes to one parameter of the wrapped
+//! [`Modifier`], avoiding the zipper noise of abrupt changes.
+//!
+//! [`Modifier`]: ../../modifiers/trait.Modifier.html
+
+use super::*;
+
+/// Alias for the closure applying the smoothed value to the [`Modifier`]
+/// wrapped by a [`Smoothed`].
+///
+/// [`Modifier`]: ../../modifiers/trait.Modifier.html
+/// [`Smoothed`]: struct.Smoothed.html
+pub type SmoothedSetter<M> = Box<dyn FnMut(&mut M, SampleT) + Send>;
+
+/// Struct wrapping a [`Modifier`], moving one of its parameters towards a
+/// target value through a one-pole smoothing filter instead of jumping to it.
+///
+/// As parameter setters differ between [`Modifier`]s, the parameter is
+/// applied by a closure given the wrapped [`Modifier`] and the current
+/// smoothed value, which is called before each sample is processed.
+///
+/// [`Modifier`]: ../../modifiers/trait.Modifier.html
+pub struct Smoothed<M>
+where
+    M: Modifier,
+{
+    inner: M,
+    setter: SmoothedSetter<M>,
+    value: MathT,
+    target: MathT,
+    coeff: MathT,
+    sample_rate: MathT,
+}
+
+impl<M> Smoothed<M>
+where
+    M: Modifier,
+{
+    /// Constructs a new [`Smoothed`] around the given [`Modifier`].
+    ///
+    /// # Parameters
+    ///
+    /// * `inner` - The [`Modifier`] whose parameter is smoothed.
+    /// * `setter` - The closure applying the smoothed value to `inner`.
+    /// * `value` - The initial value of the parameter, which is applied
+    /// immediately and used as the initial target.
+    /// * `smoothing` - The time taken to move about 63% of the way to a
+    /// new target.
+    /// * `sample_rate` - The sample rate of the processed audio.
+    ///
+    /// [`Smoothed`]: struct.Smoothed.html
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn new<F>(
+        mut inner: M,
+        mut setter: F,
+        value: SampleT,
+        smoothing: Duration,
+        sample_rate: MathT,
+    ) -> Self
+    where
+        F: 'static + FnMut(&mut M, SampleT) + Send,
+    {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        setter(&mut inner, value);
+
+        Smoothed {
+            inner,
+            setter: Box::new(setter),
+            value: value as MathT,
+            target: value as MathT,
+            coeff: time_constant(smoothing, sample_rate),
+            sample_rate,
+        }
+    }
+
+    /// Returns the value the parameter is moving towards.
+    pub fn get_target(&self) -> SampleT {
+        self.target as SampleT
+    }
+
+    /// Sets the value the parameter moves towards.
+    pub fn set_target(&mut self, value: SampleT) {
+        self.target = value as MathT;
+    }
+
+    /// Returns the current smoothed value of the parameter.
+    pub fn get_value(&self) -> SampleT {
+        self.value as SampleT
+    }
+
+    /// Sets the time taken to move about 63% of the way to a new target. A
+    /// zero time constant applies new targets immediately.
+    pub fn set_time_constant(&mut self, d: Duration) {
+        self.coeff = time_constant(d, self.sample_rate);
+    }
+
+    /// Returns a reference to the wrapped [`Modifier`].
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn get_inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped [`Modifier`].
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    pub fn get_inner_mut(&mut self) -> &mut M {
+        &mut self.inner
+    }
+}
+
+impl<M> Modifier for Smoothed<M>
+where
+    M: Modifier,
+{
+    fn process(&mut self, x: SampleT) -> SampleT {
+        self.value = self.coeff * self.value + (1.0 - self.coeff) * self.target;
+        (self.setter)(&mut self.inner, self.value as SampleT);
+
+        self.inner.process(x)
+    }
+}
@@ -0,0 +1,159 @@
+//! # Transient Shaper
+//!
+//! A [`Modifier`] separately boosting or cutting the attack and sustain
+//! portions of its input, e.g. to add punch to drums.
+//!
+//! [`Modifier`]: ../../modifiers/trait.Modifier.html
+
+use super::*;
+
+/// Struct implementing a level-independent transient shaper.
+///
+/// Two envelope followers track the input: a fast one reacting within a
+/// millisecond and a slow one lagging behind it. While the fast envelope is
+/// above the slow one the input is considered a transient and the attack gain
+/// is applied; while it is below, during the decay, the sustain gain is
+/// applied. The gains blend with how far apart the envelopes are.
+///
+/// The input is delayed by a short lookahead, 1ms by default, so the attack
+/// gain is already applied by the time the transient reaches the output. See
+/// [`set_lookahead`].
+///
+/// [`set_lookahead`]: struct.TransientShaper.html#method.set_lookahead
+#[derive(Clone)]
+pub struct TransientShaper {
+    attack_db: MathT,
+    sustain_db: MathT,
+    fast: (MathT, MathT, MathT),
+    slow: (MathT, MathT, MathT),
+    sample_rate: MathT,
+    delay: Vec<SampleT>,
+    position: usize,
+}
+
+impl TransientShaper {
+    /// Constructs a new [`TransientShaper`].
+    ///
+    /// # Parameters
+    ///
+    /// * `attack_db` - The gain in decibels applied to transients. Positive
+    /// values add punch, negative values soften the attack.
+    /// * `sustain_db` - The gain in decibels applied to the decay following
+    /// each transient.
+    /// * `sample_rate` - The sample rate of the processed audio.
+    ///
+    /// [`TransientShaper`]: struct.TransientShaper.html
+    pub fn new(attack_db: MathT, sustain_db: MathT, sample_rate: MathT) -> Self {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        let mut ts = TransientShaper {
+            attack_db,
+            sustain_db,
+            fast: (
+                time_constant(Duration::from_millis(1), sample_rate),
+                time_constant(Duration::from_millis(20), sample_rate),
+                0.0,
+            ),
+            slow: (
+                time_constant(Duration::from_millis(20), sample_rate),
+                time_constant(Duration::from_millis(200), sample_rate),
+                0.0,
+            ),
+            sample_rate,
+            delay: Vec::new(),
+            position: 0,
+        };
+
+        ts.set_lookahead(Duration::from_millis(1));
+
+        ts
+    }
+
+    /// Returns the gain in decibels applied to transients.
+    pub fn get_attack_db(&self) -> MathT {
+        self.attack_db
+    }
+
+    /// Sets the gain in decibels applied to transients.
+    pub fn set_attack_db(&mut self, attack_db: MathT) {
+        self.attack_db = attack_db;
+    }
+
+    /// Returns the gain in decibels applied to the decay of each transient.
+    pub fn get_sustain_db(&self) -> MathT {
+        self.sustain_db
+    }
+
+    /// Sets the gain in decibels applied to the decay of each transient.
+    pub fn set_sustain_db(&mut self, sustain_db: MathT) {
+        self.sustain_db = sustain_db;
+    }
+
+    /// Returns the lookahead delay in samples, which is also the latency of
+    /// the shaper.
+    pub fn get_lookahead(&self) -> usize {
+        self.delay.len()
+    }
+
+    /// Sets the lookahead delay, truncated to a whole number of samples. A
+    /// zero lookahead disables the delay. Clears any delayed input.
+    pub fn set_lookahead(&mut self, d: Duration) {
+        let len = (d.as_secs_f64() * self.sample_rate) as usize;
+
+        self.delay = vec![SampleT::default(); len];
+        self.position = 0;
+    }
+
+    fn follow(env: &mut (MathT, MathT, MathT), level: MathT) -> MathT {
+        let coeff = if level > env.2 { env.0 } else { env.1 };
+        env.2 = coeff * env.2 + (1.0 - coeff) * level;
+
+        env.2
+    }
+}
+
+impl Parameterized for TransientShaper {
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        vec![
+            ("attack_db".to_owned(), self.get_attack_db()),
+            ("sustain_db".to_owned(), self.get_sustain_db()),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        match name {
+            "attack_db" => self.set_attack_db(value),
+            "sustain_db" => self.set_sustain_db(value),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl Modifier for TransientShaper {
+    fn process(&mut self, x: SampleT) -> SampleT {
+        let level = (x as MathT).abs();
+        let fast = Self::follow(&mut self.fast, level);
+        let slow = Self::follow(&mut self.slow, level);
+
+        let gain_db = if fast > slow {
+            self.attack_db * (fast - slow) / fast
+        } else if slow > fast {
+            self.sustain_db * (slow - fast) / slow
+        } else {
+            0.0
+        };
+
+        let y = if self.delay.is_empty() {
+            x
+        } else {
+            let y = self.delay[self.position];
+            self.delay[self.position] = x;
+            self.position = (self.position + 1) % self.delay.len();
+
+            y
+        };
+
+        (y as MathT * db_to_linear(gain_db)) as SampleT
+    }
+}
@@ -0,0 +1,214 @@
+//! # Oversampling
+//!
+//! Module containing a [`Block`] wrapper running another [`Block`] at an
+//! integer multiple of the sample rate, reducing the aliasing of nonlinear
+//! processing such as clipping and ring modulation.
+//!
+//! [`Block`]: ../trait.Block.html
+
+use super::*;
+
+use crate::modifiers::Fir;
+use bae_mod::Modifier;
+
+/// Number of FIR taps per unit of oversampling factor used by the
+/// interpolation and decimation filters of an [`Oversampled`] block.
+///
+/// [`Oversampled`]: struct.Oversampled.html
+const TAPS_PER_FACTOR: usize = 64;
+
+/// Struct implementing a [`Block`] that runs the inner [`Block`] oversampled
+/// by an integer factor.
+///
+/// Each input sample is zero-stuffed up to the higher rate and smoothed by a
+/// Blackman-windowed sinc lowpass filter, then processed by the inner
+/// [`Block`]. Its output is lowpassed again below the original Nyquist
+/// frequency and decimated back down, so harmonics the inner [`Block`]
+/// creates above the original Nyquist frequency are filtered out instead of
+/// folding back into the audible band.
+///
+/// The inner [`Block`] must be configured for the higher sample rate returned
+/// by [`get_oversampled_rate`]. The filters delay the signal by
+/// [`latency`] samples.
+///
+/// [`Block`]: ../trait.Block.html
+/// [`get_oversampled_rate`]: struct.Oversampled.html#method.get_oversampled_rate
+/// [`latency`]: struct.Oversampled.html#method.latency
+pub struct Oversampled<B>
+where
+    B: Block,
+{
+    inner: B,
+    factor: usize,
+    sample_rate: MathT,
+    up: Fir,
+    down: Fir,
+    input: SampleT,
+}
+
+impl<B> Oversampled<B>
+where
+    B: Block,
+{
+    /// Creates a new [`Oversampled`] block running `inner` at `factor` times
+    /// the given sample rate. A factor of `0` is treated as `1`, which runs
+    /// the inner [`Block`] directly without any filtering.
+    ///
+    /// Debug builds panic if `sample_rate` is not positive.
+    ///
+    /// [`Oversampled`]: struct.Oversampled.html
+    /// [`Block`]: ../trait.Block.html
+    pub fn new(inner: B, factor: usize, sample_rate: MathT) -> Self {
+        debug_assert!(sample_rate > 0.0, "sample rate must be positive");
+        let factor = factor.max(1);
+
+        Oversampled {
+            inner,
+            factor,
+            sample_rate,
+            up: Fir::new(Self::lowpass_taps(factor)),
+            down: Fir::new(Self::lowpass_taps(factor)),
+            input: SampleT::default(),
+        }
+    }
+
+    /// Returns the oversampling factor.
+    pub fn get_factor(&self) -> usize {
+        self.factor
+    }
+
+    /// Returns the sample rate the inner [`Block`] runs at.
+    ///
+    /// [`Block`]: ../trait.Block.html
+    pub fn get_oversampled_rate(&self) -> MathT {
+        self.sample_rate * self.factor as MathT
+    }
+
+    /// Returns the delay added by the interpolation and decimation filters,
+    /// in samples at the original sample rate. This is `0` for a factor of
+    /// `1`.
+    pub fn latency(&self) -> usize {
+        (self.up.latency() + self.down.latency()) / self.factor
+    }
+
+    /// Returns a reference to the inner [`Block`].
+    ///
+    /// [`Block`]: ../trait.Block.html
+    pub fn get_inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner [`Block`].
+    ///
+    /// [`Block`]: ../trait.Block.html
+    pub fn get_inner_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Designs the lowpass filter shared by interpolation and decimation,
+    /// cutting off just below the original Nyquist frequency. The taps are
+    /// normalized to unity gain at DC, and a factor of `1` needs no filter.
+    fn lowpass_taps(factor: usize) -> Vec<SampleT> {
+        if factor == 1 {
+            return Vec::new();
+        }
+
+        let len = TAPS_PER_FACTOR * factor + 1;
+        let center = (len - 1) as MathT / 2.0;
+        let cutoff = 0.45 / factor as MathT;
+        let pi = std::f64::consts::PI;
+
+        let taps: Vec<MathT> = (0..len)
+            .map(|n| {
+                let d = n as MathT - center;
+                let u = d / (center + 1.0);
+
+                let window = 0.42 + 0.5 * (pi * u).cos() + 0.08 * (2.0 * pi * u).cos();
+                let sinc = if d == 0.0 {
+                    2.0 * cutoff
+                } else {
+                    (2.0 * pi * cutoff * d).sin() / (pi * d)
+                };
+
+                sinc * window
+            })
+            .collect();
+        let sum: MathT = taps.iter().sum();
+
+        taps.iter().map(|t| (t / sum) as SampleT).collect()
+    }
+}
+
+impl<B> Block for Oversampled<B>
+where
+    B: Block,
+{
+    fn prime_input(&mut self, x: SampleT) {
+        self.input += x;
+    }
+
+    fn prime_control(&mut self, idx: usize, value: SampleT) {
+        self.inner.prime_control(idx, value);
+    }
+
+    fn process(&mut self) -> SampleT {
+        let x = self.input * self.factor as SampleT;
+        self.input = SampleT::default();
+
+        let mut y = SampleT::default();
+        for k in 0..self.factor {
+            let u = self.up.process(if k == 0 { x } else { 0.0 });
+
+            self.inner.prime_input(u);
+            let v = self.inner.process();
+
+            if k == 0 {
+                y = self.down.process(v);
+            } else {
+                self.down.push(v);
+            }
+        }
+
+        y
+    }
+
+    fn type_name(&self) -> &'static str {
+        self.inner.type_name()
+    }
+
+    /// Returns the cost of the inner [`Block`] and the interpolation filter,
+    /// which both run at the higher rate, scaled by the oversampling factor,
+    /// plus the cost of the decimation filter, which only computes one output
+    /// per sample.
+    ///
+    /// [`Block`]: ../trait.Block.html
+    fn cost_hint(&self) -> u32 {
+        if self.factor == 1 {
+            return self.inner.cost_hint();
+        }
+
+        (self.inner.cost_hint() + self.up.cost_hint()) * self.factor as u32 + self.down.cost_hint()
+    }
+
+    fn parameters(&self) -> Vec<(String, MathT)> {
+        self.inner.parameters()
+    }
+
+    fn set_parameter(&mut self, name: &str, value: MathT) -> bool {
+        self.inner.set_parameter(name, value)
+    }
+
+    fn reset_phase(&mut self) {
+        self.inner.reset_phase();
+    }
+
+    /// Resets the inner [`Block`] and clears the history of the filters.
+    ///
+    /// [`Block`]: ../trait.Block.html
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.up = Fir::new(Self::lowpass_taps(self.factor));
+        self.down = Fir::new(Self::lowpass_taps(self.factor));
+        self.input = SampleT::default();
+    }
+}
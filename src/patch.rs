@@ -0,0 +1,125 @@
+//! # Patches
+//!
+//! Module containing serializable descriptions of [`BaeSound`]s, allowing
+//! sounds to be tweaked and loaded from files, e.g. JSON, without
+//! recompiling. Only available with the `serde` feature enabled.
+//!
+//! [`BaeSound`]: ../bae_sound/struct.BaeSound.html
+
+use super::*;
+
+use bae_gen::*;
+use bae_mod::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Serializable description of a [`BaeSound`], constructed into a playable
+/// sound with [`BaeSound::from_patch`].
+///
+/// In its serialized form, [`modifiers`] and [`metadata`] may be omitted, in
+/// which case they are empty.
+///
+/// [`BaeSound`]: ../bae_sound/struct.BaeSound.html
+/// [`BaeSound::from_patch`]: ../bae_sound/struct.BaeSound.html#method.from_patch
+/// [`modifiers`]: struct.Patch.html#structfield.modifiers
+/// [`metadata`]: struct.Patch.html#structfield.metadata
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Patch {
+    /// The linear input gain of the sound.
+    pub input_gain: MathT,
+    /// The linear output gain of the sound.
+    pub output_gain: MathT,
+    /// The generator of the sound.
+    pub generator: GeneratorPatch,
+    /// The modifiers of the sound, in the order they are processed.
+    #[serde(default)]
+    pub modifiers: Vec<ModifierPatch>,
+    /// Metadata stored on the sound, as with [`BaeSound::set_meta`].
+    ///
+    /// [`BaeSound::set_meta`]: ../bae_sound/struct.BaeSound.html#method.set_meta
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+/// Serializable description of a [`Generator`] kind and its parameters. Each
+/// kind is tagged by name under the `kind` key when serialized.
+///
+/// [`Generator`]: ../../generators/trait.Generator.html
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum GeneratorPatch {
+    /// A [`Sine`] oscillator.
+    ///
+    /// [`Sine`]: ../../generators/sine/struct.Sine.html
+    Sine {
+        /// The frequency of the oscillator in Hz.
+        frequency: MathT,
+    },
+    /// A white [`Noise`] generator.
+    ///
+    /// [`Noise`]: ../../generators/noise/struct.Noise.html
+    Noise,
+}
+
+impl GeneratorPatch {
+    /// Constructs the described [`Generator`] wrapped in a [`BaeBlock`], as
+    /// with [`BaeBlock::from_generator`].
+    ///
+    /// [`Generator`]: ../../generators/trait.Generator.html
+    /// [`BaeBlock`]: ../bae_block/struct.BaeBlock.html
+    /// [`BaeBlock::from_generator`]: ../bae_block/struct.BaeBlock.html#method.from_generator
+    pub fn to_block(self, sample_rate: MathT) -> BlockSP {
+        match self {
+            GeneratorPatch::Sine { frequency } => Arc::new(Mutex::new(BaeBlock::from_generator(
+                Sine::new(frequency, sample_rate),
+            ))),
+            GeneratorPatch::Noise => Arc::new(Mutex::new(BaeBlock::from_generator(Noise::new()))),
+        }
+    }
+}
+
+/// Serializable description of a [`Modifier`] kind and its parameters. Each
+/// kind is tagged by name under the `kind` key when serialized.
+///
+/// [`Modifier`]: ../../modifiers/trait.Modifier.html
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum ModifierPatch {
+    /// A [`LowPass`] filter.
+    ///
+    /// [`LowPass`]: ../../modifiers/low_pass/struct.LowPass.html
+    LowPass {
+        /// The cutoff frequency of the filter in Hz.
+        cutoff: MathT,
+        /// The resonance of the filter.
+        q: MathT,
+    },
+    /// A [`HighPass`] filter.
+    ///
+    /// [`HighPass`]: ../../modifiers/high_pass/struct.HighPass.html
+    HighPass {
+        /// The cutoff frequency of the filter in Hz.
+        cutoff: MathT,
+        /// The resonance of the filter.
+        q: MathT,
+    },
+}
+
+impl ModifierPatch {
+    /// Constructs the described [`Modifier`] wrapped in a [`BaeBlock`], as
+    /// with [`BaeBlock::from_modifier`].
+    ///
+    /// [`Modifier`]: ../../modifiers/trait.Modifier.html
+    /// [`BaeBlock`]: ../bae_block/struct.BaeBlock.html
+    /// [`BaeBlock::from_modifier`]: ../bae_block/struct.BaeBlock.html#method.from_modifier
+    pub fn to_block(self, sample_rate: MathT) -> BlockSP {
+        match self {
+            ModifierPatch::LowPass { cutoff, q } => Arc::new(Mutex::new(BaeBlock::from_modifier(
+                LowPass::new(cutoff, q, sample_rate),
+            ))),
+            ModifierPatch::HighPass { cutoff, q } => Arc::new(Mutex::new(BaeBlock::from_modifier(
+                HighPass::new(cutoff, q, sample_rate),
+            ))),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! # Resampler
+//!
+//! Module containing the [`Resampler`] trait for converting tracks between
+//! sample rates, along with implementations trading quality for speed.
+//!
+//! [`Resampler`]: trait.Resampler.html
+
+use super::*;
+
+/// Trait defining the interface for converting a track of samples from one
+/// sample rate to another. Implement it to supply a custom algorithm.
+pub trait Resampler {
+    /// Resamples `input` from `from_rate` to `to_rate`. The returned track is
+    /// `input.len() * to_rate / from_rate` samples long, rounded to the
+    /// nearest sample.
+    fn process(&self, input: &[SampleT], from_rate: MathT, to_rate: MathT) -> SampleTrackT;
+}
+
+/// Returns the length of a track of `len` samples once resampled.
+fn resampled_len(len: usize, from_rate: MathT, to_rate: MathT) -> usize {
+    (len as MathT * to_rate / from_rate).round() as usize
+}
+
+/// Fast [`Resampler`] using linear interpolation between neighbouring input
+/// samples. No anti-aliasing filter is applied, so downsampling material with
+/// content above the new Nyquist frequency will alias.
+///
+/// [`Resampler`]: trait.Resampler.html
+#[derive(Copy, Clone, Default)]
+pub struct LinearResampler;
+
+impl LinearResampler {
+    /// Constructs a new [`LinearResampler`].
+    ///
+    /// [`LinearResampler`]: struct.LinearResampler.html
+    pub fn new() -> Self {
+        LinearResampler
+    }
+}
+
+impl Resampler for LinearResampler {
+    fn process(&self, input: &[SampleT], from_rate: MathT, to_rate: MathT) -> SampleTrackT {
+        let step = from_rate / to_rate;
+        let at = |i: usize| input.get(i).copied().unwrap_or_default() as MathT;
+
+        (0..resampled_len(input.len(), from_rate, to_rate))
+            .map(|n| {
+                let t = n as MathT * step;
+                let i = t as usize;
+                let frac = t - i as MathT;
+
+                (at(i) + (at(i + 1) - at(i)) * frac) as SampleT
+            })
+            .collect()
+    }
+}
+
+/// High quality [`Resampler`] using Blackman-windowed sinc interpolation. When
+/// downsampling, the kernel is widened to lowpass the input below the new
+/// Nyquist frequency, preventing aliasing.
+///
+/// [`Resampler`]: trait.Resampler.html
+#[derive(Copy, Clone)]
+pub struct SincResampler {
+    half_width: usize,
+}
+
+impl SincResampler {
+    /// Constructs a new [`SincResampler`] using the given number of sinc
+    /// zero-crossings on either side of each output sample. Larger values give
+    /// a sharper filter at a higher cost. The width is at least one.
+    ///
+    /// [`SincResampler`]: struct.SincResampler.html
+    pub fn new(half_width: usize) -> Self {
+        SincResampler {
+            half_width: half_width.max(1),
+        }
+    }
+
+    /// Returns the number of sinc zero-crossings on either side of each output
+    /// sample.
+    pub fn get_half_width(&self) -> usize {
+        self.half_width
+    }
+}
+
+impl Default for SincResampler {
+    fn default() -> Self {
+        SincResampler::new(16)
+    }
+}
+
+impl Resampler for SincResampler {
+    fn process(&self, input: &[SampleT], from_rate: MathT, to_rate: MathT) -> SampleTrackT {
+        let step = from_rate / to_rate;
+        let cutoff = (to_rate / from_rate).min(1.0);
+        let reach = self.half_width as MathT / cutoff;
+
+        (0..resampled_len(input.len(), from_rate, to_rate))
+            .map(|n| {
+                let t = n as MathT * step;
+                let first = ((t - reach).floor() + 1.0).max(0.0) as usize;
+                let last = ((t + reach).floor() as usize).min(input.len().saturating_sub(1));
+
+                let mut y = 0.0;
+                for (k, x) in input.iter().enumerate().take(last + 1).skip(first) {
+                    let d = t - k as MathT;
+                    let u = d / reach;
+                    if u.abs() >= 1.0 {
+                        continue;
+                    }
+
+                    let window = 0.42
+                        + 0.5 * (std::f64::consts::PI * u).cos()
+                        + 0.08 * (2.0 * std::f64::consts::PI * u).cos();
+                    let arg = std::f64::consts::PI * cutoff * d;
+                    let sinc = if arg == 0.0 { 1.0 } else { arg.sin() / arg };
+
+                    y += *x as MathT * cutoff * sinc * window;
+                }
+
+                y as SampleT
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,908 @@
+#[cfg(test)]
+mod tests {
+    use bae_sounds::*;
+
+    use bae_gen::{Generator, Sine, Zero};
+    use bae_mod::{LowPass, Modifier};
+    use bae_sf::*;
+    use bae_types::*;
+
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    const SAMPLE_RATE: usize = 48_000;
+
+    struct Dc(SampleT);
+
+    impl Generator for Dc {
+        fn process(&mut self) -> SampleT {
+            self.0
+        }
+    }
+
+    /// Returns a unity-gain [`BaeSound`] playing a sine at the given frequency.
+    fn sine(f: MathT) -> SoundSP {
+        Arc::new(Mutex::new(BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                f,
+                SAMPLE_RATE as MathT,
+            )))),
+        )))
+    }
+
+    #[test]
+    fn test_gain_db() {
+        let mut c = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        assert!(c.get_gain_db().abs() < 1e-9);
+
+        c.set_gain_db(-6.0);
+        assert!((c.get_gain_db() + 6.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_process_time() {
+        let mut c = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        c.process();
+        assert_eq!(c.get_output().len(), 480);
+
+        c.set_process_time(Duration::from_millis(10));
+        c.process();
+        assert_eq!(c.get_output().len(), 480);
+        c.process();
+        assert_eq!(c.get_output().len(), 480);
+
+        c.set_process_time(Duration::from_millis(20));
+        c.process();
+        assert_eq!(c.get_output().len(), 960);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "sample rate must be positive")]
+    fn test_zero_sample_rate() {
+        BaeChannel::<Mono>::new(1.0, 0.0);
+    }
+
+    #[test]
+    fn test_idle() {
+        let mut c = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        assert!(c.is_idle());
+
+        let len = c.get_output().len();
+        c.process();
+        assert_eq!(c.get_output().len(), len);
+        assert!(c.get_output().iter().all(|s| s.mono == 0.0));
+
+        let mut sound = BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Zero::new()))),
+        );
+        sound.toggle_pause();
+        let mut sound: SoundSP = Arc::new(Mutex::new(sound));
+        c.add_sound(&mut sound).unwrap();
+        assert!(c.is_idle());
+
+        /// Sound ignoring its own pause state, outputting `1.0` and counting
+        /// each call to `process`.
+        struct Counter {
+            calls: usize,
+            paused: bool,
+        }
+
+        impl Sound for Counter {
+            fn toggle_pause(&mut self) {
+                self.paused = !self.paused;
+            }
+
+            fn is_paused(&self) -> bool {
+                self.paused
+            }
+
+            fn toggle_mute(&mut self) {}
+
+            fn is_muted(&self) -> bool {
+                false
+            }
+
+            fn set_gain(&mut self, _: MathT) {}
+
+            fn get_gain(&self) -> MathT {
+                1.0
+            }
+
+            fn register(&mut self, _: usize) {}
+
+            fn unregister(&mut self) {}
+
+            fn process(&mut self, _: SampleT) -> SampleT {
+                self.calls += 1;
+                1.0
+            }
+
+            fn get_id(&self) -> Option<usize> {
+                None
+            }
+        }
+
+        let counter = Arc::new(Mutex::new(Counter {
+            calls: 0,
+            paused: true,
+        }));
+        let mut c = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        c.set_process_time(Duration::from_millis(1));
+        c.add_sound(&mut (counter.clone() as SoundSP)).unwrap();
+        assert!(c.is_idle());
+
+        c.process();
+        assert_eq!(counter.lock().unwrap().calls, 0);
+        assert_eq!(c.get_output().len(), 48);
+        assert!(c.get_output().iter().all(|s| s.mono == 0.0));
+
+        counter.lock().unwrap().toggle_pause();
+        assert!(!c.is_idle());
+        c.process();
+        assert_eq!(counter.lock().unwrap().calls, 48);
+        assert!(c.get_output().iter().all(|s| s.mono == 1.0));
+    }
+
+    #[test]
+    fn test_for_each_sound() {
+        let mut c = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        c.set_process_time(Duration::from_millis(10));
+
+        let sounds: Vec<SoundSP> = [220.0, 440.0]
+            .iter()
+            .map(|f| {
+                let mut sound = sine(*f);
+                c.add_sound(&mut sound).unwrap();
+                sound
+            })
+            .collect();
+        let (mut a, mut b) = (
+            Sine::new(220.0, SAMPLE_RATE as MathT),
+            Sine::new(440.0, SAMPLE_RATE as MathT),
+        );
+
+        c.process();
+        for y in c.get_output() {
+            assert!((y.mono - (a.process() + b.process())).abs() < 1e-6);
+        }
+
+        let mut count = 0;
+        c.for_each_sound(|s| {
+            s.set_gain(0.25);
+            count += 1;
+        });
+        assert_eq!(count, 2);
+        assert!(sounds.iter().all(|s| s.lock().unwrap().get_gain() == 0.25));
+
+        c.process();
+        for y in c.get_output() {
+            assert!((y.mono - 0.25 * (a.process() + b.process())).abs() < 1e-6);
+        }
+
+        c.for_each_sound(|s| s.toggle_mute());
+        assert!(sounds.iter().all(|s| s.lock().unwrap().is_muted()));
+
+        c.process();
+        assert!(c.get_output()[240..].iter().all(|y| y.mono == 0.0));
+    }
+
+    #[test]
+    fn test_block_size_independence() {
+        let build = || {
+            let mut c = BaeChannel::<Mono>::new(0.5, SAMPLE_RATE as MathT);
+
+            for f in &[220.0, 330.0, 440.0] {
+                let mut ss = BaeSound::new(
+                    1.0,
+                    0.5,
+                    Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                        *f,
+                        SAMPLE_RATE as MathT,
+                    )))),
+                );
+                ss.extend_modifiers(vec![Arc::new(Mutex::new(BaeBlock::from_modifier(
+                    LowPass::new(*f * 2.0, 1.0, SAMPLE_RATE as MathT),
+                )))]);
+
+                let mut sound: SoundSP = Arc::new(Mutex::new(ss));
+                c.add_sound(&mut sound).unwrap();
+            }
+
+            c
+        };
+
+        let render = |chunk: usize| {
+            let mut c = build();
+            c.set_process_time(Duration::from_secs_f64(
+                chunk as MathT / SAMPLE_RATE as MathT,
+            ));
+
+            let mut t = Vec::new();
+            for _ in 0..4096 / chunk {
+                c.process();
+                t.extend(c.get_output().iter().map(|s| s.mono));
+            }
+
+            t
+        };
+
+        let reference = render(4096);
+        for chunk in &[64, 128, 512] {
+            let t = render(*chunk);
+            assert_eq!(t.len(), reference.len());
+            assert_eq!(t, reference);
+        }
+    }
+
+    #[test]
+    fn test_inserts() {
+        let mut c = BaeChannel::<Mono>::new(0.5, SAMPLE_RATE as MathT);
+        let mut sound: SoundSP = Arc::new(Mutex::new(BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                4000.0,
+                SAMPLE_RATE as MathT,
+            )))),
+        )));
+        c.add_sound(&mut sound).unwrap();
+
+        c.add_insert(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+            200.0,
+            0.7,
+            SAMPLE_RATE as MathT,
+        )))));
+        assert_eq!(c.insert_count(), 1);
+
+        let mut s = Sine::new(4000.0, SAMPLE_RATE as MathT);
+        let mut lp = LowPass::new(200.0, 0.7, SAMPLE_RATE as MathT);
+
+        for _ in 0..10 {
+            c.process();
+            for y in c.get_output() {
+                let expected = lp.process(0.5 * s.process());
+                assert!((y.mono - expected).abs() < 1e-6);
+                assert!(y.mono.abs() < 0.05);
+            }
+        }
+
+        c.add_insert(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+            200.0,
+            0.7,
+            SAMPLE_RATE as MathT,
+        )))));
+        assert_eq!(c.insert_count(), 2);
+        let mut lp2 = LowPass::new(200.0, 0.7, SAMPLE_RATE as MathT);
+
+        for _ in 0..10 {
+            c.process();
+            for y in c.get_output() {
+                let expected = lp2.process(lp.process(0.5 * s.process()));
+                assert!((y.mono - expected).abs() < 1e-6);
+            }
+        }
+
+        assert!(c.remove_insert(2).is_none());
+        assert!(c.remove_insert(1).is_some());
+        assert!(c.remove_insert(0).is_some());
+        assert_eq!(c.insert_count(), 0);
+
+        c.process();
+        for y in c.get_output() {
+            assert!((y.mono - 0.5 * s.process()).abs() < 1e-6);
+        }
+        assert!(c.get_output().iter().any(|y| y.mono.abs() > 0.4));
+    }
+
+    #[test]
+    fn test_shared_sound() {
+        let mut a = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        let mut b = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+
+        let mut sound: SoundSP = Arc::new(Mutex::new(BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                440.0,
+                SAMPLE_RATE as MathT,
+            )))),
+        )));
+        let mut shared = sound.clone();
+        a.add_sound(&mut sound).unwrap();
+        b.add_sound(&mut shared).unwrap();
+
+        let mut s = Sine::new(440.0, SAMPLE_RATE as MathT);
+
+        for _ in 0..5 {
+            a.process();
+            b.process();
+
+            for y in a.get_output().iter().chain(b.get_output()) {
+                assert!((y.mono - s.process()).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_offline() {
+        let mut c = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        let mut sound: SoundSP = Arc::new(Mutex::new(BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                440.0,
+                SAMPLE_RATE as MathT,
+            )))),
+        )));
+        c.add_sound(&mut sound).unwrap();
+
+        let mut fractions = Vec::new();
+        let t = c.render_offline(10_000, |f| fractions.push(f));
+
+        assert_eq!(t.len(), 10_000);
+        assert_eq!(fractions.len(), 21);
+        assert!(fractions.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(*fractions.last().unwrap(), 1.0);
+
+        let mut s = Sine::new(440.0, SAMPLE_RATE as MathT);
+        for y in &t {
+            assert!((y.mono - s.process()).abs() < 1e-6);
+        }
+
+        c.process();
+        assert_eq!(c.get_output().len(), 480);
+
+        let mut fractions = Vec::new();
+        assert!(c.render_offline(0, |f| fractions.push(f)).is_empty());
+        assert_eq!(fractions, vec![1.0]);
+    }
+
+    #[test]
+    fn test_stereo_output() {
+        let mut c = BaeChannel::<Mono>::new(0.5, SAMPLE_RATE as MathT);
+        c.add_sound_panned(&mut sine(220.0), -1.0).unwrap();
+        c.add_sound_panned(&mut sine(330.0), 2.0).unwrap();
+        c.add_sound(&mut sine(440.0)).unwrap();
+
+        let (mut a, mut b, mut center) = (
+            Sine::new(220.0, SAMPLE_RATE as MathT),
+            Sine::new(330.0, SAMPLE_RATE as MathT),
+            Sine::new(440.0, SAMPLE_RATE as MathT),
+        );
+        let g = 0.5 * std::f64::consts::FRAC_1_SQRT_2 as SampleT;
+
+        for _ in 0..10 {
+            c.process();
+
+            let (left, right) = c.get_output_stereo();
+            assert_eq!(left.len(), c.get_output().len());
+            assert_eq!(right.len(), c.get_output().len());
+
+            for ((m, l), r) in c.get_output().iter().zip(left).zip(right) {
+                let (x, y, z) = (a.process(), b.process(), center.process());
+
+                assert!((m.mono - (0.5 * (x + y + z)).max(-1.0).min(1.0)).abs() < 1e-6);
+                assert!((l.mono - (0.5 * x + g * z)).abs() < 1e-6);
+                assert!((r.mono - (0.5 * y + g * z)).abs() < 1e-6);
+            }
+        }
+
+        let mut c = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        c.add_sound_panned(&mut sine(440.0), 0.0).unwrap();
+        c.remove_sound(0);
+        c.add_sound(&mut sine(440.0)).unwrap();
+        c.process();
+
+        let (left, right) = c.get_output_stereo();
+        assert!(left.iter().zip(right).all(|(l, r)| l.mono == r.mono));
+        assert!(left.iter().any(|l| l.mono != 0.0));
+    }
+
+    #[test]
+    fn test_meters() {
+        let mut c = BaeChannel::<Mono>::new(0.8, SAMPLE_RATE as MathT);
+        c.set_process_time(Duration::from_millis(100));
+        c.add_sound(
+            &mut (Arc::new(Mutex::new(BaeSound::new(
+                1.0,
+                1.0,
+                Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                    480.0,
+                    SAMPLE_RATE as MathT,
+                )))),
+            ))) as SoundSP),
+        )
+        .unwrap();
+
+        assert_eq!(c.get_peak(), 0.0);
+        assert_eq!(c.get_rms(), 0.0);
+
+        c.process();
+        assert!((c.get_peak() - 0.8).abs() < 1e-3);
+        assert!((c.get_rms() - 0.8 / SampleT::sqrt(2.0)).abs() < 1e-3);
+
+        c.set_gain(0.1);
+        c.process();
+        assert!((c.get_peak() - 0.8).abs() < 1e-3);
+        assert!((c.get_rms() - 0.1 / SampleT::sqrt(2.0)).abs() < 1e-3);
+
+        c.reset_meters();
+        assert_eq!(c.get_peak(), 0.0);
+        assert_eq!(c.get_rms(), 0.0);
+
+        c.process();
+        assert!((c.get_peak() - 0.1).abs() < 1e-3);
+    }
+
+    #[test]
+    #[cfg(feature = "realtime")]
+    fn test_realtime_channel() {
+        let mut c = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        c.add_sound(&mut sine(440.0)).unwrap();
+        let mut rt = RealtimeChannel::new(c, 256);
+        let mut s = Sine::new(440.0, SAMPLE_RATE as MathT);
+
+        let mut buffer = [0.0; 256];
+        for _ in 0..10 {
+            rt.fill(&mut buffer, 1);
+            for y in buffer.iter() {
+                assert!((y - s.process()).abs() < 1e-6);
+            }
+        }
+
+        let mut c = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        c.add_sound(&mut sine(440.0)).unwrap();
+        let mut rt = RealtimeChannel::new(c, 100);
+        let mut s = Sine::new(440.0, SAMPLE_RATE as MathT);
+        let g = std::f64::consts::FRAC_1_SQRT_2 as SampleT;
+
+        for _ in 0..10 {
+            rt.fill(&mut buffer, 3);
+            for frame in buffer.chunks_exact(3) {
+                let x = s.process();
+                assert!((frame[0] - g * x).abs() < 1e-6);
+                assert!((frame[1] - g * x).abs() < 1e-6);
+                assert_eq!(frame[2], 0.0);
+            }
+            assert_eq!(buffer[255], 0.0);
+        }
+    }
+
+    #[test]
+    fn test_sound_count() {
+        let sound = || -> SoundSP {
+            Arc::new(Mutex::new(BaeSound::new(
+                1.0,
+                1.0,
+                Arc::new(Mutex::new(BaeBlock::from_generator(Zero::new()))),
+            )))
+        };
+
+        let mut c = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        assert_eq!(c.sound_count(), 0);
+        assert_eq!(c.get_max_sounds(), None);
+
+        let mut first = sound();
+        let a = c.add_sound(&mut first).unwrap();
+        let b = c.add_sound_panned(&mut sound(), 0.5).unwrap();
+        let d = c.add_sound(&mut sound()).unwrap();
+        assert_eq!(first.lock().unwrap().get_id(), Some(a));
+        assert_eq!(c.sound_count(), 3);
+        assert_eq!(c.sound_ids(), vec![a, b, d]);
+
+        c.remove_sound(b);
+        assert_eq!(c.sound_ids(), vec![a, d]);
+
+        c.set_max_sounds(2);
+        assert_eq!(c.get_max_sounds(), Some(2));
+        assert_eq!(c.add_sound(&mut sound()), Err(ChannelError::ChannelFull));
+        assert_eq!(
+            c.add_sound_panned(&mut sound(), 0.0),
+            Err(ChannelError::ChannelFull)
+        );
+        assert_eq!(c.sound_count(), 2);
+
+        c.remove_sound(a);
+        let e = c.add_sound(&mut sound()).unwrap();
+        assert_eq!(c.sound_ids(), vec![d, e]);
+    }
+
+    #[test]
+    fn test_process_into() {
+        let build = || {
+            let mut c = BaeChannel::<Mono>::new(0.5, SAMPLE_RATE as MathT);
+            for f in &[220.0, 330.0] {
+                let mut sound: SoundSP = Arc::new(Mutex::new(BaeSound::new(
+                    1.0,
+                    1.0,
+                    Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                        *f,
+                        SAMPLE_RATE as MathT,
+                    )))),
+                )));
+                c.add_sound(&mut sound).unwrap();
+            }
+            c.add_insert(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+                1000.0,
+                0.7,
+                SAMPLE_RATE as MathT,
+            )))));
+
+            c
+        };
+
+        let mut a = build();
+        let mut b = build();
+        a.set_process_time(Duration::from_millis(10));
+        let mut out: Vec<Mono> = (0..SAMPLE_RATE / 100).map(|_| Mono::default()).collect();
+
+        for _ in 0..10 {
+            a.process();
+            b.process_into(&mut out);
+
+            assert_eq!(a.get_output().len(), out.len());
+            for (x, y) in a.get_output().iter().zip(&out) {
+                assert_eq!(x.mono, y.mono);
+            }
+            assert_eq!(a.get_peak(), b.get_peak());
+            assert_eq!(a.get_rms(), b.get_rms());
+        }
+
+        assert!(b.get_output().is_empty());
+    }
+
+    #[test]
+    fn test_voice_manager() {
+        let mut vm = VoiceManager::<Mono>::new(1.0, SAMPLE_RATE as MathT, 3, |f: MathT| {
+            Arc::new(Mutex::new(BaeSound::new(
+                1.0,
+                1.0,
+                Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                    f,
+                    SAMPLE_RATE as MathT,
+                )))),
+            ))) as SoundSP
+        });
+        assert_eq!(vm.get_max_voices(), 3);
+
+        let ids: Vec<usize> = [220.0, 330.0, 440.0]
+            .iter()
+            .map(|f| vm.note_on(*f).unwrap())
+            .collect();
+        vm.process();
+        assert_eq!(vm.voice_count(), 3);
+        assert_eq!(vm.get_channel().sound_ids(), ids);
+
+        vm.get_channel_mut().set_max_sounds(3);
+        let stealer = vm.note_on(550.0).unwrap();
+        assert_eq!(vm.voice_count(), 3);
+        assert_eq!(vm.active_frequencies(), vec![330.0, 440.0, 550.0]);
+        assert_eq!(vm.get_channel().sound_ids(), vec![ids[1], ids[2], stealer]);
+
+        assert!(vm.note_off(440.0));
+        assert!(!vm.note_off(1000.0));
+        assert_eq!(vm.active_frequencies(), vec![330.0, 550.0]);
+
+        vm.process();
+        assert_eq!(vm.get_channel().sound_ids(), vec![ids[1], stealer]);
+    }
+
+    #[test]
+    fn test_voice_manager_release() {
+        let created = Arc::new(Mutex::new(Vec::<SoundSP>::new()));
+        let factory = created.clone();
+        let mut vm = VoiceManager::<Mono>::new(1.0, SAMPLE_RATE as MathT, 3, move |f: MathT| {
+            let sound = sine(f);
+            factory.lock().unwrap().push(sound.clone());
+            sound
+        });
+        vm.get_channel_mut().set_max_sounds(2);
+        vm.set_release(Duration::from_secs(10));
+
+        let a = vm.note_on(220.0).unwrap();
+        let b = vm.note_on(330.0).unwrap();
+        assert!(vm.note_off(220.0));
+        vm.process();
+        assert_eq!(vm.get_channel().sound_ids(), vec![a, b]);
+
+        let c = vm.note_on(440.0).unwrap();
+        assert_eq!(vm.get_channel().sound_ids(), vec![b, c]);
+        assert_eq!(created.lock().unwrap()[0].lock().unwrap().get_id(), None);
+
+        assert_eq!(vm.note_on(550.0), Err(ChannelError::ChannelFull));
+        assert_eq!(vm.voice_count(), 2);
+    }
+
+    #[test]
+    fn test_output_clipping() {
+        let mut c = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        c.set_process_time(Duration::from_millis(1));
+        c.add_sound(
+            &mut (Arc::new(Mutex::new(BaeSound::new(
+                1.0,
+                1.0,
+                Arc::new(Mutex::new(BaeBlock::from_generator(Dc(1.5)))),
+            ))) as SoundSP),
+        )
+        .unwrap();
+
+        c.process();
+        assert!(c.get_output().iter().all(|y| y.mono == 1.0));
+        assert!((c.get_peak() - 1.5).abs() < 1e-6);
+
+        let (left, right) = c.get_output_stereo();
+        assert!(left.iter().chain(right).all(|y| y.mono <= 1.0));
+
+        c.set_gain(-1.0);
+        c.process();
+        assert!(c.get_output().iter().all(|y| y.mono == -1.0));
+
+        #[derive(Copy, Clone, Debug, Default, PartialEq)]
+        struct I16(i16);
+
+        impl SampleFormat for I16 {
+            fn from_sample(x: SampleT) -> Self {
+                I16((x * i16::MAX as SampleT) as i32 as i16)
+            }
+        }
+
+        assert_ne!(I16::from_sample(1.5), I16(i16::MAX));
+
+        let mut c = BaeChannel::<I16>::new(1.0, SAMPLE_RATE as MathT);
+        c.set_process_time(Duration::from_millis(1));
+        c.add_sound(
+            &mut (Arc::new(Mutex::new(BaeSound::new(
+                1.0,
+                1.0,
+                Arc::new(Mutex::new(BaeBlock::from_generator(Dc(1.5)))),
+            ))) as SoundSP),
+        )
+        .unwrap();
+
+        c.process();
+        assert_eq!(c.get_output().len(), 48);
+        assert!(c.get_output().iter().all(|y| *y == I16(i16::MAX)));
+
+        c.set_gain(-1.0);
+        c.process();
+        assert!(c.get_output().iter().all(|y| *y == I16(-i16::MAX)));
+    }
+
+    #[test]
+    fn test_solo() {
+        let mut c = BaeChannel::<Mono>::new(0.5, SAMPLE_RATE as MathT);
+        c.add_sound(&mut sine(220.0)).unwrap();
+        let soloed = c.add_sound(&mut sine(330.0)).unwrap();
+        c.add_sound(&mut sine(440.0)).unwrap();
+
+        c.set_solo(soloed, true);
+        c.set_solo(100, true);
+        assert!(c.is_soloed(soloed));
+        assert!(!c.is_soloed(100));
+
+        let mut alone = BaeChannel::<Mono>::new(0.5, SAMPLE_RATE as MathT);
+        alone.add_sound(&mut sine(330.0)).unwrap();
+
+        for _ in 0..10 {
+            c.process();
+            alone.process();
+
+            for (x, y) in c.get_output().iter().zip(alone.get_output()) {
+                assert_eq!(x.mono, y.mono);
+            }
+        }
+
+        c.set_solo(soloed, false);
+        assert!(!c.is_soloed(soloed));
+        c.process();
+        alone.process();
+        assert!(c
+            .get_output()
+            .iter()
+            .zip(alone.get_output())
+            .any(|(x, y)| x.mono != y.mono));
+    }
+
+    #[test]
+    fn test_clear_output() {
+        let mut c = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        c.add_sound(
+            &mut (Arc::new(Mutex::new(BaeSound::new(
+                1.0,
+                1.0,
+                Arc::new(Mutex::new(BaeBlock::from_generator(Dc(0.25)))),
+            ))) as SoundSP),
+        )
+        .unwrap();
+
+        c.process();
+        assert!(c.get_output().iter().all(|y| y.mono == 0.25));
+        c.process();
+        assert_eq!(c.get_output().len(), 480);
+        assert!(c.get_output().iter().all(|y| y.mono == 0.25));
+
+        let capacity = c.get_output().capacity();
+        c.clear_output();
+        assert!(c.get_output().is_empty());
+        assert!(c.get_output_stereo().0.is_empty());
+        assert!(c.get_output_stereo().1.is_empty());
+        assert_eq!(c.get_output().capacity(), capacity);
+
+        c.set_process_time(Duration::from_millis(5));
+        assert!(c.get_output().is_empty());
+        assert_eq!(c.get_output().capacity(), capacity);
+
+        c.process();
+        assert_eq!(c.get_output().len(), 240);
+        assert!(c.get_output().iter().all(|y| y.mono == 0.25));
+        assert_eq!(c.get_output().capacity(), capacity);
+    }
+
+    #[test]
+    fn test_master_bus() {
+        let channel = |gain: MathT, dc: SampleT| -> Box<dyn Channel<Mono>> {
+            let mut c = BaeChannel::<Mono>::new(gain, SAMPLE_RATE as MathT);
+            c.add_sound(
+                &mut (Arc::new(Mutex::new(BaeSound::new(
+                    1.0,
+                    1.0,
+                    Arc::new(Mutex::new(BaeBlock::from_generator(Dc(dc)))),
+                ))) as SoundSP),
+            )
+            .unwrap();
+
+            Box::new(c)
+        };
+
+        let mut bus = MasterBus::<Mono>::new(1.5);
+        assert_eq!(bus.process(), Ok(()));
+        assert!(bus.get_output().is_empty());
+
+        assert_eq!(bus.add_channel(channel(1.0, 0.25)), 0);
+        assert_eq!(bus.add_channel(channel(0.5, 0.5)), 1);
+        assert_eq!(bus.channel_count(), 2);
+
+        for _ in 0..3 {
+            assert_eq!(bus.process(), Ok(()));
+            assert_eq!(bus.get_output().len(), 480);
+            assert!(bus.get_output().iter().all(|y| y.mono == 0.75));
+        }
+
+        bus.set_gain(4.0);
+        assert_eq!(bus.get_gain(), 4.0);
+        bus.process().unwrap();
+        assert!(bus.get_output().iter().all(|y| y.mono == 1.0));
+
+        bus.get_channel_mut(1)
+            .unwrap()
+            .set_process_time(Duration::from_millis(5));
+        assert_eq!(bus.process(), Err(ChannelError::OutputLengthMismatch(1)));
+        assert!(bus.get_output().is_empty());
+
+        assert!(bus.remove_channel(1).is_some());
+        assert!(bus.remove_channel(1).is_none());
+        bus.set_gain(1.0);
+        assert_eq!(bus.process(), Ok(()));
+        assert!(bus.get_output().iter().all(|y| y.mono == 0.25));
+
+        let stereo = |gain: MathT, dc: SampleT| -> Box<dyn Channel<Stereo>> {
+            let mut c = BaeChannel::<Stereo>::new(gain, SAMPLE_RATE as MathT);
+            c.add_sound(
+                &mut (Arc::new(Mutex::new(BaeSound::new(
+                    1.0,
+                    1.0,
+                    Arc::new(Mutex::new(BaeBlock::from_generator(Dc(dc)))),
+                ))) as SoundSP),
+            )
+            .unwrap();
+
+            Box::new(c)
+        };
+
+        let mut bus = MasterBus::<Stereo>::new(1.5);
+        bus.add_channel(stereo(1.0, 0.25));
+        bus.add_channel(stereo(0.5, 0.5));
+        assert_eq!(bus.process(), Ok(()));
+        assert_eq!(bus.get_output().len(), 480);
+        assert!(bus
+            .get_output()
+            .iter()
+            .all(|y| y.left == 0.75 && y.right == 0.75));
+    }
+
+    #[test]
+    fn test_sound_gain() {
+        let mut quiet = sine(220.0);
+        let mut c = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        let a = c.add_sound(&mut quiet).unwrap();
+        let b = c.add_sound(&mut sine(330.0)).unwrap();
+
+        quiet.lock().unwrap().set_gain(0.25);
+        assert_eq!(c.get_sound_gain(a), Some(0.25));
+        assert!(c.set_sound_gain(b, 0.5));
+        assert_eq!(c.get_sound_gain(b), Some(0.5));
+        assert!(!c.set_sound_gain(100, 0.5));
+        assert_eq!(c.get_sound_gain(100), None);
+
+        let (mut x, mut y) = (
+            Sine::new(220.0, SAMPLE_RATE as MathT),
+            Sine::new(330.0, SAMPLE_RATE as MathT),
+        );
+
+        for _ in 0..10 {
+            c.process();
+
+            for m in c.get_output() {
+                let expected = 0.25 * x.process() + 0.5 * y.process();
+                assert!((m.mono - expected).abs() < 1e-6);
+            }
+        }
+
+        let mut cs = ComplexSound::new(1.0, 1.0);
+        let s = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+            440.0,
+            SAMPLE_RATE as MathT,
+        )))));
+        cs.add_connection(cs.get_input_gain(), s);
+        cs.add_connection(s, cs.get_output_gain());
+        cs.set_gain(0.5);
+        assert_eq!(cs.get_gain(), 0.5);
+
+        let mut z = Sine::new(440.0, SAMPLE_RATE as MathT);
+        for _ in 0..SAMPLE_RATE / 100 {
+            assert!((cs.process(0.0) - 0.5 * z.process()).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_finished_sounds() {
+        let mut one_shot = BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                440.0,
+                SAMPLE_RATE as MathT,
+            )))),
+        );
+        one_shot.set_duration(Duration::from_millis(5), SAMPLE_RATE as MathT);
+        let mut one_shot: SoundSP = Arc::new(Mutex::new(one_shot));
+        let mut held = sine(220.0);
+
+        let mut c = BaeChannel::<Mono>::new(1.0, SAMPLE_RATE as MathT);
+        c.set_process_time(Duration::from_millis(10));
+        let a = c.add_sound(&mut one_shot).unwrap();
+        let b = c.add_sound(&mut held).unwrap();
+        assert_eq!(one_shot.lock().unwrap().get_id(), Some(a));
+
+        let (mut x, mut y) = (
+            Sine::new(440.0, SAMPLE_RATE as MathT),
+            Sine::new(220.0, SAMPLE_RATE as MathT),
+        );
+
+        c.process();
+        for (i, m) in c.get_output().iter().enumerate() {
+            let expected = if i < SAMPLE_RATE / 200 {
+                x.process() + y.process()
+            } else {
+                y.process()
+            };
+            assert!((m.mono - expected).abs() < 1e-6);
+        }
+
+        assert!(one_shot.lock().unwrap().is_finished());
+        assert_eq!(one_shot.lock().unwrap().get_id(), None);
+        assert_eq!(c.sound_count(), 1);
+        assert_eq!(held.lock().unwrap().get_id(), Some(b));
+
+        c.process();
+        for m in c.get_output() {
+            assert!((m.mono - y.process()).abs() < 1e-6);
+        }
+        assert_eq!(c.sound_count(), 1);
+    }
+}
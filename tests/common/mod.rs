@@ -0,0 +1,21 @@
+//! Helpers shared by the integration tests.
+
+use bae_types::*;
+
+const SAMPLE_RATE: usize = 48_000;
+
+/// Returns the magnitude of the given frequency within the track using the
+/// Goertzel algorithm.
+pub fn magnitude(t: &[SampleT], freq: MathT) -> MathT {
+    let w = 2.0 * std::f64::consts::PI * freq / SAMPLE_RATE as MathT;
+    let coeff = 2.0 * w.cos();
+
+    let (mut s1, mut s2) = (0.0, 0.0);
+    for x in t {
+        let s0 = *x as MathT + coeff * s1 - s2;
+        s2 = s1;
+        s1 = s0;
+    }
+
+    (s1 * s1 + s2 * s2 - coeff * s1 * s2).max(0.0).sqrt() / t.len() as MathT
+}
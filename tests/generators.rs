@@ -0,0 +1,328 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use bae_sounds::generators::*;
+    use bae_sounds::*;
+
+    use bae_gen::*;
+    use bae_types::*;
+    use bae_utils::*;
+
+    use super::common::magnitude;
+
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    const SAMPLE_RATE: usize = 48_000;
+
+    struct TestSine {
+        phase: MathT,
+        freq: MathT,
+    }
+
+    impl TestSine {
+        fn new(freq: MathT) -> Self {
+            TestSine { phase: 0.0, freq }
+        }
+    }
+
+    impl Generator for TestSine {
+        fn process(&mut self) -> SampleT {
+            let y = (2.0 * std::f64::consts::PI * self.phase).sin();
+            self.phase = (self.phase + self.freq / SAMPLE_RATE as MathT).fract();
+            y as SampleT
+        }
+    }
+
+    impl FreqControllable for TestSine {
+        fn set_frequency(&mut self, f: MathT) {
+            self.freq = f;
+        }
+
+        fn get_frequency(&self) -> MathT {
+            self.freq
+        }
+    }
+
+    struct TestSaw {
+        phase: MathT,
+        freq: MathT,
+    }
+
+    impl Generator for TestSaw {
+        fn process(&mut self) -> SampleT {
+            let y = 2.0 * self.phase - 1.0;
+            self.phase = (self.phase + self.freq / SAMPLE_RATE as MathT).fract();
+            y as SampleT
+        }
+    }
+
+    /// Returns the sample index and the frequency measured between each pair
+    /// of consecutive upward zero-crossings.
+    fn zero_crossing_frequencies(t: &[SampleT]) -> Vec<(usize, MathT)> {
+        let mut crossings = Vec::new();
+        for i in 1..t.len() {
+            if t[i - 1] < 0.0 && t[i] >= 0.0 {
+                let frac = t[i - 1] as MathT / (t[i - 1] - t[i]) as MathT;
+                crossings.push((i, (i - 1) as MathT + frac));
+            }
+        }
+
+        crossings
+            .windows(2)
+            .map(|w| (w[1].0, SAMPLE_RATE as MathT / (w[1].1 - w[0].1)))
+            .collect()
+    }
+
+    #[test]
+    fn test_metronome() {
+        let mut b = BaeBlock::from_generator(Metronome::new(
+            120.0,
+            1000.0,
+            Duration::from_millis(10),
+            vec![1.0, 0.5, 0.5, 0.5],
+            SAMPLE_RATE as MathT,
+        ));
+
+        let beat = seconds_to_samples(Duration::from_secs_f64(0.5), SAMPLE_RATE as MathT);
+        let click = seconds_to_samples(Duration::from_millis(10), SAMPLE_RATE as MathT);
+
+        let mut t = SampleTrackT::new();
+        for _ in 0..4 * beat {
+            t.push(b.process());
+        }
+
+        let mut peaks = Vec::new();
+        for i in 0..4 {
+            let start = i * beat;
+
+            let peak = t[start..start + click]
+                .iter()
+                .fold(0.0 as SampleT, |p, s| p.max(s.abs()));
+            assert!(peak > 0.0);
+            peaks.push(peak);
+
+            assert!(t[start + click + 1..start + beat].iter().all(|s| *s == 0.0));
+        }
+
+        for p in &peaks[1..] {
+            assert!(peaks[0] > *p);
+        }
+    }
+
+    #[test]
+    fn test_glide() {
+        let glide_time = Duration::from_secs_f64(0.5);
+        let glide_samples = seconds_to_samples(glide_time, SAMPLE_RATE as MathT);
+
+        let mut g = Glide::new(TestSine::new(400.0), glide_time, SAMPLE_RATE as MathT);
+        g.set_target_frequency(800.0);
+
+        let mut t = SampleTrackT::new();
+        for _ in 0..glide_samples + SAMPLE_RATE / 10 {
+            t.push(g.process());
+        }
+
+        let freqs = zero_crossing_frequencies(&t);
+
+        let (_, first) = freqs[0];
+        assert!((first - 400.0).abs() < 10.0);
+
+        let mut last = 0.0;
+        for (i, f) in &freqs {
+            if *i <= glide_samples {
+                let expected = 400.0 + 400.0 * (*i as MathT / glide_samples as MathT);
+                assert!((f - expected).abs() < 10.0);
+                assert!(*f > last);
+                last = *f;
+            } else if *i > glide_samples + SAMPLE_RATE / 100 {
+                assert!((f - 800.0).abs() < 1.0);
+            }
+        }
+
+        let mut g = Glide::new(TestSine::new(400.0), Duration::from_secs(0), SAMPLE_RATE as MathT);
+        g.set_target_frequency(800.0);
+        assert_eq!(g.get_frequency(), 800.0);
+    }
+
+    #[test]
+    fn test_morph() {
+        let morph = |m: MathT| {
+            MorphGenerator::new(
+                Arc::new(Mutex::new(TestSine::new(440.0))),
+                Arc::new(Mutex::new(TestSaw {
+                    phase: 0.0,
+                    freq: 440.0,
+                })),
+                m,
+            )
+        };
+
+        let (mut sine, mut saw) = (
+            TestSine::new(440.0),
+            TestSaw {
+                phase: 0.0,
+                freq: 440.0,
+            },
+        );
+        let (mut zero, mut one, mut half) = (morph(0.0), morph(1.0), morph(0.5));
+        let mut equal_power = morph(0.5);
+        equal_power.set_equal_power(true);
+
+        for _ in 0..SAMPLE_RATE / 10 {
+            let (a, b) = (sine.process(), saw.process());
+
+            assert_eq!(zero.process(), a);
+            assert_eq!(one.process(), b);
+            assert!((half.process() - (a + b) * 0.5).abs() < 1e-6);
+            assert!((equal_power.process() - (a + b) * 0.5_f32.sqrt()).abs() < 1e-6);
+        }
+
+        let mut m = morph(2.0);
+        assert_eq!(m.get_morph(), 1.0);
+        m.set_morph(-1.0);
+        assert_eq!(m.get_morph(), 0.0);
+        assert!(m.set_parameter("morph", 0.25));
+        assert_eq!(m.get_morph(), 0.25);
+    }
+
+    #[test]
+    fn test_quad_osc() {
+        let mut q = QuadOsc::new(440.0, SAMPLE_RATE as MathT);
+        let mut s = q;
+
+        let mut t = SampleTrackT::new();
+        for i in 0..SAMPLE_RATE {
+            let [sin, cos] = q.process_frame();
+            assert!(((sin * sin + cos * cos) as MathT - 1.0).abs() < 1e-6);
+
+            let expected = 2.0 * std::f64::consts::PI * 440.0 * i as MathT / SAMPLE_RATE as MathT;
+            assert!((sin as MathT - expected.sin()).abs() < 1e-4);
+            assert!((cos as MathT - expected.cos()).abs() < 1e-4);
+
+            t.push(s.process());
+        }
+
+        assert!(zero_crossing_frequencies(&t)
+            .iter()
+            .all(|(_, f)| (f - 440.0).abs() < 1.0));
+    }
+
+    #[test]
+    fn test_sequencer() {
+        let beat = SAMPLE_RATE / 2;
+
+        let mut s = Sequencer::new(vec![(69.0, 1.0), (81.0, 1.0)], 120.0, SAMPLE_RATE as MathT);
+        assert_eq!(s.get_tempo(), 120.0);
+
+        let t: SampleTrackT = (0..4 * beat).map(|_| s.process()).collect();
+
+        for (i, f) in zero_crossing_frequencies(&t) {
+            let expected = if (i / beat) % 2 == 0 { 440.0 } else { 880.0 };
+            if i % beat > SAMPLE_RATE / 100 {
+                assert!((f - expected).abs() < 1.0);
+            }
+        }
+
+        s.set_steps(vec![(69.0, 1.0), (Sequencer::REST, 0.5)]);
+        assert_eq!(s.get_steps().len(), 2);
+
+        let t: SampleTrackT = (0..3 * beat).map(|_| s.process()).collect();
+        assert!(t[..beat].iter().any(|x| x.abs() > 0.5));
+        assert!(t[beat..beat + beat / 2].iter().all(|x| *x == 0.0));
+        assert!(t[beat + beat / 2..].iter().any(|x| x.abs() > 0.5));
+    }
+
+    #[test]
+    fn test_harmonics() {
+        let mut h = Harmonics::new(200.0, vec![1.0, 0.5, 0.25], SAMPLE_RATE as MathT);
+        let t: SampleTrackT = (0..SAMPLE_RATE).map(|_| h.process()).collect();
+
+        let fundamental = magnitude(&t, 200.0);
+        assert!(fundamental > 0.1);
+        assert!((magnitude(&t, 400.0) / fundamental - 0.5).abs() < 0.01);
+        assert!((magnitude(&t, 600.0) / fundamental - 0.25).abs() < 0.01);
+        assert!(magnitude(&t, 800.0) < 0.01 * fundamental);
+        assert!(magnitude(&t, 300.0) < 0.01 * fundamental);
+
+        h.set_harmonic(5, 0.5);
+        assert_eq!(h.get_harmonics(), &[1.0, 0.5, 0.25, 0.0, 0.5][..]);
+
+        let mut high = Harmonics::new(10_000.0, vec![1.0, 1.0, 1.0], SAMPLE_RATE as MathT);
+        let mut low = Harmonics::new(10_000.0, vec![1.0, 1.0], SAMPLE_RATE as MathT);
+        for _ in 0..SAMPLE_RATE / 10 {
+            assert_eq!(high.process(), low.process());
+        }
+    }
+
+    #[test]
+    fn test_formant() {
+        let render = |vowel| {
+            let mut f = Formant::new(100.0, vowel, SAMPLE_RATE as MathT);
+            assert_eq!(f.get_vowel(), vowel);
+
+            (0..SAMPLE_RATE).map(|_| f.process()).collect::<SampleTrackT>()
+        };
+
+        let a = render(Vowel::A);
+        let e = render(Vowel::E);
+        let i = render(Vowel::I);
+        let o = render(Vowel::O);
+
+        let strongest = (2..=10)
+            .map(|k| k as MathT * 100.0)
+            .max_by(|x, y| magnitude(&a, *x).partial_cmp(&magnitude(&a, *y)).unwrap())
+            .unwrap();
+        assert!((strongest - 600.0).abs() <= 100.0);
+
+        assert!(magnitude(&a, 600.0) > 2.0 * magnitude(&i, 600.0));
+        assert!(magnitude(&e, 1600.0) > 2.0 * magnitude(&o, 1600.0));
+
+        let mut f = Formant::new(100.0, Vowel::I, SAMPLE_RATE as MathT);
+        f.set_vowel(Vowel::A);
+        let switched = (0..SAMPLE_RATE).map(|_| f.process()).collect::<SampleTrackT>();
+        assert!(magnitude(&switched, 600.0) > 2.0 * magnitude(&i, 600.0));
+    }
+
+    #[test]
+    fn test_sweep() {
+        let duration = Duration::from_secs(1);
+        let mut b =
+            BaeBlock::from_generator(Sweep::new(100.0, 10_000.0, duration, SAMPLE_RATE as MathT));
+
+        let t = (0..SAMPLE_RATE + 100)
+            .map(|_| b.process())
+            .collect::<SampleTrackT>();
+        assert!(t[SAMPLE_RATE..].iter().all(|s| *s == 0.0));
+
+        let mid = SAMPLE_RATE / 2;
+        let (_, f) = zero_crossing_frequencies(&t[..SAMPLE_RATE])
+            .into_iter()
+            .find(|(i, _)| *i >= mid)
+            .unwrap();
+        assert!((f - 1000.0).abs() < 10.0);
+
+        let mut s = Sweep::new(100.0, 10_000.0, duration, SAMPLE_RATE as MathT);
+        assert_eq!(s.len(), SAMPLE_RATE);
+        for _ in 0..mid {
+            s.process();
+        }
+        assert!((s.get_frequency() - 1000.0).abs() < 1e-6);
+
+        s.reset_phase();
+        assert!((s.get_frequency() - 100.0).abs() < 1e-9);
+
+        let s = Sweep::new(
+            100.0,
+            10_000.0,
+            Duration::from_millis(50),
+            SAMPLE_RATE as MathT,
+        );
+        let mut g = s.clone();
+        let recording = (0..s.len()).map(|_| g.process()).collect::<SampleTrackT>();
+        let ir = s.deconvolve(&recording);
+        assert!((ir[0] - 1.0).abs() < 1e-3);
+        assert!(ir[SAMPLE_RATE / 100..].iter().all(|x| x.abs() < 0.1));
+    }
+}
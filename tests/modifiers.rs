@@ -0,0 +1,403 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use bae_sounds::modifiers::*;
+    use bae_sounds::ModifierSP;
+
+    use bae_gen::{Generator, Sine};
+    use bae_mod::{Gain, Modifier, Passthrough};
+    use bae_types::*;
+
+    use super::common::magnitude;
+
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    const SAMPLE_RATE: usize = 48_000;
+
+    struct Clip(SampleT);
+
+    impl Modifier for Clip {
+        fn process(&mut self, x: SampleT) -> SampleT {
+            x.max(-self.0).min(self.0)
+        }
+    }
+
+    #[test]
+    fn test_biquad_clamping() {
+        let nyquist = SAMPLE_RATE as MathT / 2.0;
+        let mut f = Biquad::low_pass(1000.0, 0.7, SAMPLE_RATE as MathT);
+
+        assert!(!f.set_frequency(2000.0));
+        assert_eq!(f.get_frequency(), 2000.0);
+        assert!(!f.set_q(2.0));
+
+        assert!(f.set_frequency(SAMPLE_RATE as MathT));
+        assert!(f.get_frequency() < nyquist);
+        assert!(f.get_frequency() > 0.99 * nyquist);
+
+        assert!(f.set_q(-1.0));
+        assert!(f.get_q() > 0.0);
+
+        assert!(f.set_frequency(-10.0));
+        assert!(f.get_frequency() > 0.0);
+        assert!(f.set_frequency(nyquist));
+
+        let mut s = Sine::new(440.0, SAMPLE_RATE as MathT);
+        for _ in 0..SAMPLE_RATE / 10 {
+            let y = f.process(s.process());
+            assert!(y.is_finite());
+            assert!(y.abs() < 100.0);
+        }
+
+        let f = Biquad::high_pass(-1.0, 0.0, SAMPLE_RATE as MathT);
+        assert!(f.get_frequency() > 0.0);
+        assert!(f.get_q() > 0.0);
+    }
+
+    #[test]
+    fn test_dry_wet() {
+        use bae_sounds::{BaeBlock, Block};
+
+        let mut b = BaeBlock::from_parameterized_modifier(DryWet::new(Gain::new(-1.0), 0.0));
+        assert_eq!(b.parameters(), vec![("mix".to_owned(), 0.0)]);
+
+        let len = SAMPLE_RATE / 10;
+        let mut s = Sine::new(440.0, SAMPLE_RATE as MathT);
+        let mut t = Vec::new();
+        let mut dry = Vec::new();
+
+        for i in 0..len {
+            assert!(b.set_parameter("mix", i as MathT / (len - 1) as MathT));
+
+            let x = s.process();
+            b.prime_input(x);
+            t.push(b.process());
+            dry.push(x);
+        }
+
+        assert_eq!(b.parameters(), vec![("mix".to_owned(), 1.0)]);
+        assert!(!b.set_parameter("wet", 0.5));
+
+        for (i, (y, x)) in t.iter().zip(&dry).enumerate() {
+            let mix = i as SampleT / (len - 1) as SampleT;
+            assert!((y - x * (1.0 - 2.0 * mix)).abs() < 1e-6);
+        }
+        assert_eq!(t[0], dry[0]);
+        assert_eq!(t[len - 1], -dry[len - 1]);
+    }
+
+    #[test]
+    fn test_smoothed() {
+        let mut s = Smoothed::new(
+            DryWet::new(Gain::new(-1.0), 0.0),
+            |m: &mut DryWet<Gain>, mix: SampleT| m.set_mix(mix as MathT),
+            0.0,
+            Duration::from_millis(10),
+            SAMPLE_RATE as MathT,
+        );
+        assert_eq!(s.process(1.0), 1.0);
+
+        s.set_target(1.0);
+        assert_eq!(s.get_target(), 1.0);
+
+        let mut last = 1.0;
+        for _ in 0..SAMPLE_RATE / 5 {
+            let y = s.process(1.0);
+            assert!((y - last).abs() < 0.01);
+            last = y;
+        }
+        assert!((last + 1.0).abs() < 1e-3);
+        assert!((s.get_value() - 1.0).abs() < 1e-3);
+
+        s.set_time_constant(Duration::from_secs(0));
+        s.set_target(0.0);
+        assert_eq!(s.process(1.0), 1.0);
+        assert_eq!(s.get_inner().get_mix(), 0.0);
+    }
+
+    #[test]
+    fn test_fdn_reverb() {
+        let lengths = vec![1031, 1327, 1523, 1871, 2053, 2311, 2539, 2803];
+        let mut r = FdnReverb::new(lengths, Duration::from_secs(1), 0.0, SAMPLE_RATE as MathT);
+        assert_eq!(r.line_count(), 8);
+
+        assert!(!r.set_matrix(vec![vec![0.5; 8]; 8]));
+        assert!(!r.set_matrix(vec![vec![1.0]]));
+
+        let len = 3 * SAMPLE_RATE / 2;
+        let t: Vec<SampleT> = (0..len)
+            .map(|i| r.process(if i == 0 { 1.0 } else { 0.0 }))
+            .collect();
+
+        let window = &t[3 * SAMPLE_RATE / 10..SAMPLE_RATE / 2];
+        let dense = window.iter().filter(|x| x.abs() > 1e-9).count();
+        assert!(dense as MathT > 0.9 * window.len() as MathT);
+
+        let energy = |a: usize, b: usize| t[a..b].iter().map(|x| (x * x) as MathT).sum::<MathT>();
+        assert!(
+            energy(SAMPLE_RATE * 8 / 10, SAMPLE_RATE)
+                < energy(SAMPLE_RATE / 5, SAMPLE_RATE * 2 / 5)
+        );
+
+        let mut edc: Vec<MathT> = t
+            .iter()
+            .rev()
+            .scan(0.0, |acc, x| {
+                *acc += (x * x) as MathT;
+                Some(*acc)
+            })
+            .collect();
+        edc.reverse();
+
+        let below = |db: MathT| {
+            edc.iter()
+                .position(|e| 10.0 * (e / edc[0]).log10() < db)
+                .unwrap()
+        };
+        let rt60 = 2.0 * (below(-35.0) - below(-5.0)) as MathT / SAMPLE_RATE as MathT;
+        assert!((rt60 - 1.0).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_fir() {
+        let mut f = Fir::new(vec![1.0 / 3.0; 3]);
+        assert_eq!(f.latency(), 1);
+
+        let input: Vec<SampleT> = (0..32).map(|i| ((i * 7) % 5) as SampleT - 2.0).collect();
+
+        for (i, x) in input.iter().enumerate() {
+            let start = if i >= 2 { i - 2 } else { 0 };
+            let expected = input[start..=i].iter().sum::<SampleT>() / 3.0;
+            assert!((f.process(*x) - expected).abs() < 1e-6);
+        }
+
+        let mut f = Fir::new(Vec::new());
+        assert_eq!(f.process(0.5), 0.5);
+
+        let mut f = Fir::new(vec![1.0]);
+        assert_eq!(f.process(0.25), 0.25);
+        assert_eq!(f.process(-0.75), -0.75);
+    }
+
+    #[test]
+    fn test_linearity_probe() {
+        let mut s = Sine::new(440.0, SAMPLE_RATE as MathT);
+        let mut gain = LinearityProbe::new(Gain::new(0.5), 4800);
+        let mut clip = LinearityProbe::new(Clip(0.25), 4800);
+
+        for _ in 0..SAMPLE_RATE / 10 {
+            let x = s.process();
+            assert!((gain.process(x) - x * 0.5).abs() < 1e-7);
+            clip.process(x);
+        }
+
+        assert!((gain.measured_gain() - 0.5).abs() < 1e-6);
+        assert!(gain.residual_nonlinearity() < 1e-9);
+
+        assert!(clip.residual_nonlinearity() > 0.01);
+    }
+
+    #[test]
+    fn test_rms_follower() {
+        let mut s = Sine::new(440.0, SAMPLE_RATE as MathT);
+        let mut rms = RmsFollower::new(Duration::from_millis(50), SAMPLE_RATE as MathT);
+
+        let mut y = 0.0;
+        for _ in 0..SAMPLE_RATE / 5 {
+            y = rms.process(0.8 * s.process());
+        }
+
+        assert!((y - 0.8 / (2.0 as SampleT).sqrt()).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_scale_quantizer() {
+        let minor = vec![0.0, 2.0, 3.0, 5.0, 7.0, 8.0, 10.0];
+        let mut q = ScaleQuantizer::new(220.0, minor.clone());
+
+        let notes: Vec<MathT> = (-2..4)
+            .flat_map(|octave| {
+                minor
+                    .iter()
+                    .map(move |i| 220.0 * ((12.0 * octave as MathT + i) / 12.0).exp2())
+            })
+            .collect();
+
+        let mut outputs: Vec<SampleT> = Vec::new();
+        for i in 0..SAMPLE_RATE {
+            let f = 100.0 + 1900.0 * i as SampleT / SAMPLE_RATE as SampleT;
+            let y = q.process(f);
+
+            assert!(notes.iter().any(|n| (y as MathT / n - 1.0).abs() < 1e-6));
+            if outputs.last() != Some(&y) {
+                outputs.push(y);
+            }
+        }
+
+        let in_range = notes.iter().filter(|n| **n > 100.0 && **n < 2000.0).count();
+        assert!(outputs.windows(2).all(|w| w[0] < w[1]));
+        assert!(outputs.len() >= in_range);
+
+        assert!((q.quantize(450.0) - 440.0).abs() < 1e-9);
+        assert!((q.quantize(227.0) - 220.0).abs() < 1e-9);
+        assert_eq!(q.quantize(0.0), 0.0);
+
+        q.set_scale(440.0, vec![12.0, 19.0, 16.0, 7.0]);
+        assert_eq!(q.get_intervals(), &[0.0, 4.0, 7.0][..]);
+    }
+
+    #[test]
+    fn test_transient_shaper() {
+        let drum: SampleTrackT = (0..SAMPLE_RATE / 4)
+            .map(|n| {
+                let t = n as MathT / SAMPLE_RATE as MathT;
+                ((-t / 0.03).exp() * (std::f64::consts::PI * 300.0 * t).sin()) as SampleT
+            })
+            .collect();
+        let peak_ratio = |ts: &mut TransientShaper| {
+            let out: SampleTrackT = drum.iter().map(|x| ts.process(*x)).collect();
+            let peak = |w: &[SampleT]| w.iter().fold(0.0 as SampleT, |p, y| p.max(y.abs()));
+
+            peak(&out[..SAMPLE_RATE / 100]) / peak(&out[SAMPLE_RATE / 10..SAMPLE_RATE / 5])
+        };
+
+        let mut neutral = TransientShaper::new(0.0, 0.0, SAMPLE_RATE as MathT);
+        let latency = neutral.get_lookahead();
+        assert_eq!(latency, SAMPLE_RATE / 1000);
+        for (n, x) in drum.iter().enumerate() {
+            let y = neutral.process(*x);
+            if n >= latency {
+                assert!((y - drum[n - latency]).abs() < 1e-6);
+            } else {
+                assert_eq!(y, 0.0);
+            }
+        }
+
+        let neutral = peak_ratio(&mut TransientShaper::new(0.0, 0.0, SAMPLE_RATE as MathT));
+        let punchy = peak_ratio(&mut TransientShaper::new(12.0, 0.0, SAMPLE_RATE as MathT));
+        let soft = peak_ratio(&mut TransientShaper::new(-12.0, 0.0, SAMPLE_RATE as MathT));
+
+        assert!(punchy > neutral * 1.5);
+        assert!(soft < neutral);
+    }
+
+    #[test]
+    fn test_compressor_knee() {
+        let reduction = |c: &mut Compressor, db: MathT| {
+            c.process(10.0_f64.powf(db / 20.0) as SampleT);
+            c.get_gain_reduction_db()
+        };
+
+        let mut hard = Compressor::new(
+            -12.0,
+            4.0,
+            Duration::from_secs(0),
+            Duration::from_secs(0),
+            SAMPLE_RATE as MathT,
+        );
+        let mut soft = hard;
+        soft.set_knee(12.0);
+        assert_eq!(soft.get_knee(), 12.0);
+
+        assert_eq!(reduction(&mut hard, -15.0), 0.0);
+        assert!(reduction(&mut soft, -15.0) > 0.0);
+        assert!(reduction(&mut soft, -19.0).abs() < 1e-9);
+
+        let mut previous = 0.0;
+        for step in 0..=24 {
+            let r = reduction(&mut soft, -18.0 + step as MathT * 0.5);
+            assert!(r >= previous);
+            assert!(r - previous < 0.4);
+            previous = r;
+        }
+
+        for db in &[-3.0, 0.0] {
+            let h = reduction(&mut hard, *db);
+            assert!((reduction(&mut soft, *db) - h).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_frequency_shifter() {
+        let mut s = Sine::new(1000.0, SAMPLE_RATE as MathT);
+        let mut up = FrequencyShifter::new(100.0, SAMPLE_RATE as MathT);
+        let mut down = FrequencyShifter::new(-100.0, SAMPLE_RATE as MathT);
+        assert_eq!(down.get_shift(), -100.0);
+
+        let (mut t_up, mut t_down) = (Vec::new(), Vec::new());
+        for _ in 0..SAMPLE_RATE {
+            let x = s.process();
+            t_up.push(up.process(x));
+            t_down.push(down.process(x));
+        }
+
+        let dominant = |t: &[SampleT]| {
+            (5..=15)
+                .map(|k| k as MathT * 100.0)
+                .max_by(|x, y| magnitude(t, *x).partial_cmp(&magnitude(t, *y)).unwrap())
+                .unwrap()
+        };
+
+        assert_eq!(dominant(&t_up), 1100.0);
+        assert_eq!(dominant(&t_down), 900.0);
+        assert!(magnitude(&t_up, 1100.0) > 100.0 * magnitude(&t_up, 900.0));
+    }
+
+    #[test]
+    fn test_mid_side() {
+        let mut transparent = MidSide::new(
+            Arc::new(Mutex::new(Passthrough::new())) as ModifierSP,
+            Arc::new(Mutex::new(Passthrough::new())) as ModifierSP,
+        );
+        let mut ms = MidSide::new(
+            Arc::new(Mutex::new(Passthrough::new())) as ModifierSP,
+            Arc::new(Mutex::new(Gain::new(0.5))) as ModifierSP,
+        );
+
+        let mut a = Sine::new(440.0, SAMPLE_RATE as MathT);
+        let mut b = Sine::new(660.0, SAMPLE_RATE as MathT);
+
+        for _ in 0..SAMPLE_RATE / 10 {
+            let mid = 0.5 * a.process();
+            let side = 0.25 * b.process();
+
+            let (l, r) = transparent.process(mid + side, mid - side);
+            assert!((l - (mid + side)).abs() < 1e-6);
+            assert!((r - (mid - side)).abs() < 1e-6);
+
+            let (l, r) = ms.process(mid + side, mid - side);
+            assert!(((l + r) * 0.5 - mid).abs() < 1e-6);
+            assert!(((l - r) * 0.5 - side * 0.5).abs() < 1e-6);
+        }
+
+        let mut mono = MidSide::new(
+            Arc::new(Mutex::new(Gain::new(0.5))) as ModifierSP,
+            Arc::new(Mutex::new(Gain::new(4.0))) as ModifierSP,
+        );
+        for _ in 0..SAMPLE_RATE / 10 {
+            let x = a.process();
+            let (l, r) = mono.process(x, x);
+            assert_eq!(l, r);
+            assert!((l - 0.5 * x).abs() < 1e-6);
+        }
+
+        let side = Arc::new(Mutex::new(Gain::new(0.0)));
+        let poisoned = side.clone();
+        let _ = std::thread::spawn(move || {
+            let _lock = poisoned.lock().unwrap();
+            panic!("poisoning the side modifier");
+        })
+        .join();
+        assert!(side.is_poisoned());
+
+        let mut ms = MidSide::new(
+            Arc::new(Mutex::new(Gain::new(0.5))) as ModifierSP,
+            side as ModifierSP,
+        );
+        let (l, r) = ms.process(1.0, 0.0);
+        assert!((l - 0.75).abs() < 1e-6);
+        assert!((r - -0.25).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use bae_sounds::*;
+
+    use bae_types::*;
+
+    const SAMPLE_RATE: usize = 48_000;
+
+    fn signal(t: MathT) -> MathT {
+        let w = 2.0 * std::f64::consts::PI * t;
+        0.5 * (1000.0 * w).sin() + 0.3 * (3000.0 * w).sin()
+    }
+
+    fn rms_error(r: &dyn Resampler, to_rate: MathT) -> MathT {
+        let input: SampleTrackT = (0..SAMPLE_RATE / 10)
+            .map(|i| signal(i as MathT / SAMPLE_RATE as MathT) as SampleT)
+            .collect();
+
+        let output = r.process(&input, SAMPLE_RATE as MathT, to_rate);
+        let expected_len = (input.len() as MathT * to_rate / SAMPLE_RATE as MathT).round();
+        assert_eq!(output.len(), expected_len as usize);
+
+        let inner = &output[100..output.len() - 100];
+        let sum: MathT = inner
+            .iter()
+            .enumerate()
+            .map(|(n, y)| (*y as MathT - signal((n + 100) as MathT / to_rate)).powi(2))
+            .sum();
+
+        (sum / inner.len() as MathT).sqrt()
+    }
+
+    #[test]
+    fn test_resamplers() {
+        let linear = LinearResampler::new();
+        let sinc = SincResampler::default();
+        assert_eq!(sinc.get_half_width(), 16);
+
+        for to_rate in &[44_100.0, 96_000.0] {
+            let linear_error = rms_error(&linear, *to_rate);
+            let sinc_error = rms_error(&sinc, *to_rate);
+
+            assert!(linear_error < 0.01);
+            assert!(sinc_error < 1e-4);
+            assert!(sinc_error < linear_error);
+        }
+
+        assert!(linear.process(&[], 48_000.0, 44_100.0).is_empty());
+        assert!(sinc.process(&[], 48_000.0, 44_100.0).is_empty());
+    }
+}
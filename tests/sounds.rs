@@ -8,11 +8,19 @@ mod tests {
     use bae_utils::*;
 
     use std::fs::File;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
     use std::time::Duration;
 
     const SAMPLE_RATE: usize = 48_000;
 
+    struct Dc(SampleT);
+
+    impl Generator for Dc {
+        fn process(&mut self) -> SampleT {
+            self.0
+        }
+    }
+
     #[test]
     fn test_blocks() {
         let mut b = BaeBlock::from_generator(Sine::new(440.0, SAMPLE_RATE as MathT));
@@ -43,24 +51,644 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn test_frame_blocks() {
+        let mut mono = BaeBlock::new(
+            Sine::new(440.0, SAMPLE_RATE as MathT),
+            LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT),
+            BaeBlock::default_interactor(),
+        );
+        let mut frame = BaeBlockN::<1>::new(
+            Sine::new(440.0, SAMPLE_RATE as MathT),
+            [Arc::new(Mutex::new(LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT))) as ModifierSP],
+            BaeBlockN::default_interactor(),
+        );
+        let mut n = Noise::new();
+
+        for _ in 0..SAMPLE_RATE / 10 {
+            let x = n.process();
+            mono.prime_frame(x);
+            frame.prime_frame([x]);
+            assert_eq!([mono.process_frame()], frame.process_frame());
+        }
+
+        let mut stereo = BaeBlockN::from_modifiers([
+            Arc::new(Mutex::new(Gain::new(0.25))) as ModifierSP,
+            Arc::new(Mutex::new(Gain::new(0.75))) as ModifierSP,
+        ]);
+        stereo.prime_frame([1.0, -1.0]);
+        assert_eq!(stereo.process_frame(), [0.25, -0.75]);
+        assert_eq!(stereo.process_frame(), [0.0, 0.0]);
+
+        let mut pan = BaeBlockN::<2>::from_generator(Sine::new(440.0, SAMPLE_RATE as MathT));
+        pan.set_inter(Arc::new(Mutex::new(|ge: SampleT, _: [SampleT; 2]| {
+            [ge * 0.3, ge * 0.7]
+        })));
+        let mut s = Sine::new(440.0, SAMPLE_RATE as MathT);
+
+        for _ in 0..SAMPLE_RATE / 10 {
+            let x = s.process();
+            let [l, r] = pan.process_frame();
+            assert!((l - x * 0.3).abs() < 1e-6);
+            assert!((r - x * 0.7).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_stereo_sound() {
+        let stereo = || {
+            let pan: FrameBlockSP<2> = Arc::new(Mutex::new(BaeBlockN::from_modifiers([
+                Arc::new(Mutex::new(Gain::new(0.25))) as ModifierSP,
+                Arc::new(Mutex::new(Gain::new(0.75))) as ModifierSP,
+            ])));
+            let mut sound = BaeSound::new(
+                1.0,
+                0.5,
+                Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                    440.0,
+                    SAMPLE_RATE as MathT,
+                )))),
+            );
+            sound.add_modifier(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+                880.0,
+                1.0,
+                SAMPLE_RATE as MathT,
+            )))));
+            sound.add_stereo_modifier(pan);
+            sound
+        };
+
+        let mut sound = stereo();
+        let mut mono = stereo();
+        mono.split_at(1);
+        assert_eq!(sound.stereo_modifier_count(), 1);
+        assert_eq!(mono.stereo_modifier_count(), 0);
+
+        let mut mixed = stereo();
+        for _ in 0..SAMPLE_RATE / 10 {
+            let y = mono.process(0.0) * 0.5;
+            let [l, r] = sound.process_stereo(0.0);
+            assert!((l - y * 0.25).abs() < 1e-6);
+            assert!((r - y * 0.75).abs() < 1e-6);
+            assert!((mixed.process(0.0) - (l + r) * 0.5).abs() < 1e-6);
+        }
+
+        sound.toggle_pause();
+        assert_eq!(sound.process_stereo(0.0), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_block_reset() {
+        let lp = || LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT);
+        let mut block = BaeBlock::from_modifier_fn(lp);
+        let mut n = Noise::new();
+
+        for _ in 0..SAMPLE_RATE / 10 {
+            block.prime_input(n.process());
+            block.process();
+        }
+        block.prime_input(1.0);
+        block.reset();
+
+        let mut fresh = BaeBlock::from_modifier(lp());
+        let input: SampleTrackT = (0..SAMPLE_RATE / 10).map(|_| n.process()).collect();
+
+        for x in input {
+            block.prime_input(x);
+            fresh.prime_input(x);
+            assert_eq!(block.process(), fresh.process());
+        }
+
+        let mut g = BaeBlock::from_modifier(Gain::new(1.0));
+        g.prime_input(1.0);
+        g.reset();
+        assert_eq!(g.process(), 0.0);
+    }
+
+    #[test]
+    fn test_composed_block() {
+        use bae_sounds::generators::QuadOsc;
+
+        let osc = Arc::new(Mutex::new(QuadOsc::new(5.0, SAMPLE_RATE as MathT)));
+        let mut block = BaeBlockBuilder::new()
+            .shared_generator(osc.clone())
+            .parameters(osc.clone())
+            .phase_resettable(osc)
+            .control_rate(true)
+            .build();
+        assert!(block.is_control_rate());
+        assert!(block.type_name().ends_with("QuadOsc"));
+
+        assert!(block.set_parameter("frequency", 10.0));
+        assert_eq!(block.parameters(), vec![("frequency".to_owned(), 10.0)]);
+
+        for _ in 0..SAMPLE_RATE / 7 {
+            block.process();
+        }
+        block.reset_phase();
+
+        let mut fresh = QuadOsc::new(10.0, SAMPLE_RATE as MathT);
+        for _ in 0..SAMPLE_RATE / 10 {
+            assert_eq!(block.process(), fresh.process());
+        }
+    }
+
+    #[test]
+    fn test_delay_line() {
+        let mut dl = DelayLine::new(Duration::from_millis(10), SAMPLE_RATE as MathT, 0.5);
+        let delay = SAMPLE_RATE / 100;
+        assert_eq!(dl.get_delay_samples(), delay);
+
+        dl.prime_input(1.0);
+        let t: SampleTrackT = dl.samples().take(4 * delay + 1).collect();
+        for (i, y) in t.iter().enumerate() {
+            let expected = if i > 0 && i % delay == 0 {
+                (0.5 as SampleT).powi(i as i32 / delay as i32 - 1)
+            } else {
+                0.0
+            };
+            assert!((y - expected).abs() < 1e-7);
+        }
+
+        dl.set_feedback(0.0);
+        dl.set_delay(Duration::from_millis(1));
+        assert_eq!(dl.get_feedback(), 0.0);
+        assert_eq!(dl.get_delay_samples(), SAMPLE_RATE / 1000);
+
+        dl.prime_input(1.0);
+        let t: SampleTrackT = dl.samples().take(3 * SAMPLE_RATE / 1000).collect();
+        assert_eq!(t.iter().filter(|y| **y != 0.0).count(), 1);
+        assert_eq!(t[SAMPLE_RATE / 1000], 1.0);
+
+        dl.prime_input(1.0);
+        dl.process();
+        dl.reset();
+        assert!(dl.samples().take(SAMPLE_RATE / 100).all(|y| y == 0.0));
+    }
+
+    #[test]
+    fn test_block_bypass() {
+        let mut lp = BaeBlock::from_modifier(LowPass::new(440.0, 1.0, SAMPLE_RATE as MathT));
+        let mut input = Sine::new(1000.0, SAMPLE_RATE as MathT);
+        assert!(!lp.is_bypassed());
+
+        lp.set_bypass(true);
+        assert!(lp.is_bypassed());
+        for _ in 0..SAMPLE_RATE / 10 {
+            let x = input.process();
+            lp.prime_input(x);
+            assert_eq!(lp.process(), x);
+        }
+
+        lp.set_bypass(false);
+        let filtered = (0..SAMPLE_RATE / 10)
+            .map(|_| {
+                let x = input.process();
+                lp.prime_input(x);
+                (lp.process() - x).abs()
+            })
+            .fold(0.0, SampleT::max);
+        assert!(filtered > 0.1);
+
+        let mut a = BaeBlock::new(
+            Sine::new(440.0, SAMPLE_RATE as MathT),
+            LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT),
+            BaeBlock::default_interactor(),
+        );
+        let mut reference = Sine::new(440.0, SAMPLE_RATE as MathT);
+        a.set_bypass(true);
+        for _ in 0..SAMPLE_RATE / 10 {
+            a.prime_input(1.0);
+            assert_eq!(a.process(), reference.process());
+        }
+
+        let mut custom = BaeBlock::from_modifier(Gain::new(0.5));
+        custom.set_inter(BaeBlock::default_interactor());
+        assert_eq!(custom.get_inter_kind(), None);
+        custom.set_bypass(true);
+        custom.prime_input(0.25);
+        assert_eq!(custom.process(), 0.25);
+
+        let mut out = [0.0; 4];
+        custom.prime_input(0.25);
+        custom.process_buffer(&mut out);
+        assert_eq!(out, [0.25, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_process_buffer() {
+        let mut looped = BaeBlock::from_generator(Sine::new(440.0, SAMPLE_RATE as MathT));
+        let expected: SampleTrackT = (0..4096).map(|_| looped.process()).collect();
+
+        let mut buffered = BaeBlock::from_generator(Sine::new(440.0, SAMPLE_RATE as MathT));
+        let mut out = vec![0.0; 4096];
+        buffered.process_buffer(&mut out);
+        assert_eq!(out, expected);
+
+        let dynamic: BlockSP = Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+            440.0,
+            SAMPLE_RATE as MathT,
+        ))));
+        let mut out = vec![0.0; 4096];
+        dynamic.lock().unwrap().process_buffer(&mut out);
+        assert_eq!(out, expected);
+
+        let mut looped = BaeBlock::from_modifier(LowPass::new(440.0, 1.0, SAMPLE_RATE as MathT));
+        let mut buffered = BaeBlock::from_modifier(LowPass::new(440.0, 1.0, SAMPLE_RATE as MathT));
+        looped.prime_input(1.0);
+        buffered.prime_input(1.0);
+
+        let expected: SampleTrackT = (0..4096).map(|_| looped.process()).collect();
+        let mut out = vec![0.0; 4096];
+        buffered.process_buffer(&mut out);
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_prime_control() {
+        use bae_sounds::modifiers::Biquad;
+
+        let window_peaks = |lp: &mut BaeBlock, modulate: bool| -> Vec<SampleT> {
+            let mut lfo = Sine::new(2.0, SAMPLE_RATE as MathT);
+            let mut input = Sine::new(2000.0, SAMPLE_RATE as MathT);
+
+            (0..100)
+                .map(|_| {
+                    (0..SAMPLE_RATE / 100)
+                        .map(|_| {
+                            if modulate {
+                                lp.prime_control(0, lfo.process());
+                            }
+                            lp.prime_input(input.process());
+                            lp.process().abs()
+                        })
+                        .fold(0.0, SampleT::max)
+                })
+                .skip(5)
+                .collect()
+        };
+        let spread = |peaks: &[SampleT]| {
+            peaks.iter().copied().fold(0.0, SampleT::max)
+                / peaks.iter().copied().fold(SampleT::MAX, SampleT::min)
+        };
+
+        let controls: Vec<ControlMapping<Biquad>> = vec![Box::new(|f: &mut Biquad, x: SampleT| {
+            f.set_frequency(2100.0 + 1900.0 * x as MathT);
+        })];
+        let mut modulated = BaeBlock::from_controlled_modifier(
+            Biquad::low_pass(4000.0, 0.707, SAMPLE_RATE as MathT),
+            controls,
+        );
+        assert!(modulated.type_name().ends_with("Biquad"));
+        assert!(spread(&window_peaks(&mut modulated, true)) > 4.0);
+
+        let mut fixed = BaeBlock::from_controlled_modifier(
+            Biquad::low_pass(4000.0, 0.707, SAMPLE_RATE as MathT),
+            Vec::new(),
+        );
+        fixed.prime_control(0, -1.0);
+        assert!(spread(&window_peaks(&mut fixed, false)) < 1.05);
+
+        let mut plain =
+            BaeBlock::from_modifier(Biquad::low_pass(4000.0, 0.707, SAMPLE_RATE as MathT));
+        assert!(spread(&window_peaks(&mut plain, true)) < 1.05);
+
+        let bq = Arc::new(Mutex::new(Biquad::low_pass(
+            4000.0,
+            0.707,
+            SAMPLE_RATE as MathT,
+        )));
+        let target = bq.clone();
+        let mut composed = BaeBlockBuilder::new()
+            .shared_modifier(bq.clone())
+            .parameters(bq)
+            .control(move |x: SampleT| {
+                if let Ok(mut f) = target.lock() {
+                    f.set_frequency(2100.0 + 1900.0 * x as MathT);
+                }
+            })
+            .build();
+        assert!(composed.set_parameter("q", 0.707));
+        assert!(spread(&window_peaks(&mut composed, true)) > 4.0);
+    }
+
+    #[test]
+    fn test_block_iter() {
+        let mut block = BaeBlock::from_generator(Sine::new(440.0, SAMPLE_RATE as MathT));
+        let mut s = Sine::new(440.0, SAMPLE_RATE as MathT);
+
+        let t: SampleTrackT = block.samples().take(SAMPLE_RATE / 10).collect();
+        assert_eq!(t.len(), SAMPLE_RATE / 10);
+        for y in t {
+            assert_eq!(y, s.process());
+        }
+
+        let mut lp = BaeBlock::from_modifier(LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT));
+        let mut expected = LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT);
+        let mut n = Noise::new();
+        let input: SampleTrackT = (0..SAMPLE_RATE / 10).map(|_| n.process()).collect();
+
+        let t: SampleTrackT = lp.samples().with_input(input.iter().copied()).collect();
+        assert_eq!(t.len(), input.len());
+        for (x, y) in input.iter().zip(t) {
+            assert_eq!(y, expected.process(*x));
+        }
+
+        let shared: BlockSP = Arc::new(Mutex::new(BaeBlock::from_modifier(Gain::new(0.5))));
+        let mut b = shared.lock().unwrap();
+        let t: SampleTrackT = BlockIter::new(&mut *b)
+            .with_input(vec![1.0, -1.0])
+            .collect();
+        assert_eq!(t, vec![0.5, -0.5]);
+    }
+
+    #[test]
+    fn test_inter_kinds() {
+        let kinds = vec![
+            InterKind::Multiply,
+            InterKind::Add,
+            InterKind::GeneratorOnly,
+            InterKind::ModifierOnly,
+            InterKind::Weighted { g: 0.25, m: 0.75 },
+        ];
+
+        for kind in kinds {
+            let inter: Inter = match kind {
+                InterKind::Multiply => Arc::new(Mutex::new(|ge: SampleT, mo: SampleT| ge * mo)),
+                InterKind::Add => Arc::new(Mutex::new(|ge: SampleT, mo: SampleT| ge + mo)),
+                InterKind::GeneratorOnly => Arc::new(Mutex::new(|ge: SampleT, _: SampleT| ge)),
+                InterKind::ModifierOnly => Arc::new(Mutex::new(|_: SampleT, mo: SampleT| mo)),
+                InterKind::Weighted { .. } => {
+                    Arc::new(Mutex::new(|ge: SampleT, mo: SampleT| ge * 0.25 + mo * 0.75))
+                }
+            };
+
+            let mut a = BaeBlock::new(
+                Sine::new(440.0, SAMPLE_RATE as MathT),
+                LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT),
+                BaeBlock::default_interactor(),
+            );
+            a.set_inter_kind(kind);
+            assert_eq!(a.get_inter_kind(), Some(kind));
+
+            let mut b = BaeBlock::new(
+                Sine::new(440.0, SAMPLE_RATE as MathT),
+                LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT),
+                inter,
+            );
+            assert_eq!(b.get_inter_kind(), None);
+
+            let mut input = Sine::new(220.0, SAMPLE_RATE as MathT);
+
+            for _ in 0..seconds_to_samples(Duration::from_secs_f64(0.1), SAMPLE_RATE as MathT) {
+                let x = input.process();
+                a.prime_input(x);
+                b.prime_input(x);
+                assert!((a.process() - b.process()).abs() < 1e-15);
+            }
+        }
+    }
+
+    #[test]
+    fn test_interactor_bank() {
+        let cases: Vec<(Inter, Vec<(SampleT, SampleT, SampleT)>)> = vec![
+            (
+                BaeBlock::min_interactor(),
+                vec![(0.5, -0.25, -0.25), (-1.0, 1.0, -1.0), (0.3, 0.3, 0.3)],
+            ),
+            (
+                BaeBlock::max_interactor(),
+                vec![(0.5, -0.25, 0.5), (-1.0, 1.0, 1.0), (0.3, 0.3, 0.3)],
+            ),
+            (
+                BaeBlock::ring_mod_interactor(),
+                vec![(0.5, -0.5, -0.25), (-1.0, -1.0, 1.0), (0.0, 0.7, 0.0)],
+            ),
+            (
+                BaeBlock::clip_interactor(0.5),
+                vec![(0.5, 0.5, 0.25), (1.0, 1.0, 0.5), (-1.0, 0.8, -0.5)],
+            ),
+            (
+                BaeBlock::clip_interactor(-0.5),
+                vec![(1.0, 1.0, 0.5), (-1.0, 1.0, -0.5)],
+            ),
+        ];
+
+        for (inter, pairs) in cases {
+            let mut i = inter.lock().unwrap();
+            for (ge, mo, expected) in pairs {
+                assert!(((*i)(ge, mo) - expected).abs() < 1e-7);
+            }
+        }
+    }
+
+    #[test]
+    fn test_crossfade_interactor() {
+        let pairs = vec![
+            (
+                BaeBlock::crossfade_interactor(0.0),
+                BaeBlock::generator_passthrough(),
+            ),
+            (
+                BaeBlock::crossfade_interactor(1.0),
+                BaeBlock::modifier_passthrough(),
+            ),
+            (
+                BaeBlock::crossfade_interactor(-1.0),
+                BaeBlock::generator_passthrough(),
+            ),
+            (
+                BaeBlock::crossfade_interactor(2.0),
+                BaeBlock::modifier_passthrough(),
+            ),
+            (
+                BaeBlock::crossfade_interactor(0.5),
+                InterKind::Weighted { g: 0.5, m: 0.5 }.to_inter(),
+            ),
+            (
+                BaeBlock::sum_interactor(),
+                InterKind::Weighted { g: 1.0, m: 1.0 }.to_inter(),
+            ),
+        ];
+
+        for (crossfade, reference) in pairs {
+            let mut a = BaeBlock::new(
+                Sine::new(440.0, SAMPLE_RATE as MathT),
+                LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT),
+                crossfade,
+            );
+            let mut b = BaeBlock::new(
+                Sine::new(440.0, SAMPLE_RATE as MathT),
+                LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT),
+                reference,
+            );
+
+            let mut input = Sine::new(220.0, SAMPLE_RATE as MathT);
+
+            for _ in 0..SAMPLE_RATE / 10 {
+                let x = input.process();
+                a.prime_input(x);
+                b.prime_input(x);
+                assert!((a.process() - b.process()).abs() < 1e-7);
+            }
+        }
+    }
+
+    #[test]
+    fn test_block_builder() {
+        use bae_sounds::modifiers::Biquad;
+
+        let mut a = BaeBlockBuilder::new()
+            .generator(Sine::new(440.0, SAMPLE_RATE as MathT))
+            .modifier(LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT))
+            .interactor(|ge, mo| 0.25 * ge + mo)
+            .build();
+        let mut b = BaeBlock::new(
+            Sine::new(440.0, SAMPLE_RATE as MathT),
+            LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT),
+            Arc::new(Mutex::new(|ge: SampleT, mo: SampleT| 0.25 * ge + mo)),
+        );
+        assert_eq!(a.get_inter_kind(), None);
+        assert_eq!(a.type_name(), b.type_name());
+
+        let mut input = Sine::new(220.0, SAMPLE_RATE as MathT);
+
+        for _ in 0..SAMPLE_RATE / 10 {
+            let x = input.process();
+            a.prime_input(x);
+            b.prime_input(x);
+            assert_eq!(a.process(), b.process());
+        }
+
+        let mut g = BaeBlockBuilder::new()
+            .generator(Sine::new(440.0, SAMPLE_RATE as MathT))
+            .build();
+        let mut m = BaeBlockBuilder::new()
+            .modifier(LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT))
+            .build();
+        let mut g_ref = BaeBlock::from_generator(Sine::new(440.0, SAMPLE_RATE as MathT));
+        let mut m_ref = BaeBlock::from_modifier(LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT));
+        assert_eq!(g.get_inter_kind(), Some(InterKind::GeneratorOnly));
+        assert_eq!(m.get_inter_kind(), Some(InterKind::ModifierOnly));
+
+        for _ in 0..SAMPLE_RATE / 10 {
+            let x = input.process();
+            g.prime_input(x);
+            g_ref.prime_input(x);
+            m.prime_input(x);
+            m_ref.prime_input(x);
+            assert_eq!(g.process(), g_ref.process());
+            assert_eq!(m.process(), m_ref.process());
+        }
+
+        let both = BaeBlockBuilder::new()
+            .generator(Sine::new(440.0, SAMPLE_RATE as MathT))
+            .modifier(LowPass::new(880.0, 1.0, SAMPLE_RATE as MathT))
+            .build();
+        assert_eq!(both.get_inter_kind(), Some(InterKind::Multiply));
+
+        let bq = Arc::new(Mutex::new(Biquad::low_pass(
+            880.0,
+            0.707,
+            SAMPLE_RATE as MathT,
+        )));
+        let mut p = BaeBlockBuilder::new()
+            .shared_modifier(bq.clone())
+            .parameters(bq)
+            .build();
+        let mut p_ref = BaeBlock::from_parameterized_modifier(Biquad::low_pass(
+            880.0,
+            0.707,
+            SAMPLE_RATE as MathT,
+        ));
+        assert_eq!(p.parameters(), p_ref.parameters());
+        assert_eq!(p.get_inter_kind(), p_ref.get_inter_kind());
+        assert!(p.set_parameter("frequency", 440.0));
+        assert!(p_ref.set_parameter("frequency", 440.0));
+
+        for _ in 0..SAMPLE_RATE / 10 {
+            let x = input.process();
+            p.prime_input(x);
+            p_ref.prime_input(x);
+            assert_eq!(p.process(), p_ref.process());
+        }
+    }
+
+    #[test]
+    fn test_oversampled() {
+        let clipper = || {
+            BaeBlockBuilder::new()
+                .modifier(Passthrough::new())
+                .interactor(|_, mo| mo.max(-0.3).min(0.3))
+                .build()
+        };
+        let render = |block: &mut dyn Block| -> SampleTrackT {
+            let mut input = Sine::new(1730.0, SAMPLE_RATE as MathT);
+
+            (0..SAMPLE_RATE / 10 + 200)
+                .map(|_| {
+                    block.prime_input(input.process());
+                    block.process()
+                })
+                .skip(200)
+                .collect()
+        };
+        let alias_energy = |t: &[SampleT]| -> MathT {
+            let total: MathT = t.iter().map(|y| (*y as MathT).powi(2)).sum();
+            let harmonics: MathT = (1..)
+                .map(|h| h as MathT * 1730.0)
+                .take_while(|f| *f < SAMPLE_RATE as MathT / 2.0)
+                .map(|f| {
+                    let w = 2.0 * std::f64::consts::PI * f / SAMPLE_RATE as MathT;
+                    let (c, s) = t.iter().enumerate().fold((0.0, 0.0), |(c, s), (i, y)| {
+                        let y = *y as MathT;
+                        (
+                            c + y * (w * i as MathT).cos(),
+                            s + y * (w * i as MathT).sin(),
+                        )
+                    });
+
+                    2.0 * (c * c + s * s) / t.len() as MathT
+                })
+                .sum();
+
+            total - harmonics
+        };
+
+        let mut os = Oversampled::new(clipper(), 4, SAMPLE_RATE as MathT);
+        assert_eq!(os.get_factor(), 4);
+        assert_eq!(os.get_oversampled_rate(), 4.0 * SAMPLE_RATE as MathT);
+        assert_eq!(os.latency(), 64);
+
+        let plain = alias_energy(&render(&mut clipper()));
+        let oversampled = alias_energy(&render(&mut os));
+        assert!(plain > 0.0);
+        assert!(oversampled < 0.1 * plain);
+
+        let mut direct = Oversampled::new(clipper(), 0, SAMPLE_RATE as MathT);
+        assert_eq!(direct.get_factor(), 1);
+        assert_eq!(direct.latency(), 0);
+        assert_eq!(render(&mut direct), render(&mut clipper()));
+    }
+
     #[test]
     fn test_bae_sounds() {
         let mut ss = BaeSound::new(
             1.0,
             0.5,
-            Arc::new(BaeBlock::from_generator(Noise::new())),
+            Arc::new(Mutex::new(BaeBlock::from_generator(Noise::new()))),
         );
         ss.extend_modifiers(vec![
-            Arc::new(BaeBlock::from_modifier(LowPass::new(
+            Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
                 440.0,
                 1.0,
                 SAMPLE_RATE as MathT,
-            ))),
-            Arc::new(BaeBlock::from_modifier(HighPass::new(
+            )))),
+            Arc::new(Mutex::new(BaeBlock::from_modifier(HighPass::new(
                 220.0,
                 1.0,
                 SAMPLE_RATE as MathT,
-            ))),
+            )))),
         ]);
 
         let mut t = SampleTrackT::new();
@@ -78,38 +706,1378 @@ mod tests {
     }
 
     #[test]
-    fn test_complex_sounds() {
-        let mut cs = ComplexSound::new(1.0, 1.0);
-
-        let n = cs.add_block(Arc::new(BaeBlock::from_generator(Noise::new())));
-        let lp = cs.add_block(Arc::new(BaeBlock::from_modifier(LowPass::new(
-            440.0,
+    fn test_dc_compensation() {
+        let mut ss = BaeSound::new(
             1.0,
-            SAMPLE_RATE as MathT,
+            0.5,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                440.0,
+                SAMPLE_RATE as MathT,
+            )))),
+        );
+        ss.add_modifier(Arc::new(Mutex::new(BaeBlock::new(
+            Dc(0.2),
+            Passthrough::new(),
+            BaeBlock::sum_interactor(),
         ))));
-        let hp = cs.add_block(Arc::new(BaeBlock::from_modifier(HighPass::new(
-            220.0,
+
+        let mean = |ss: &mut BaeSound| {
+            (0..SAMPLE_RATE)
+                .map(|_| ss.process(0.0) as MathT)
+                .sum::<MathT>()
+                / SAMPLE_RATE as MathT
+        };
+
+        assert!((mean(&mut ss) - 0.1).abs() < 1e-3);
+
+        ss.auto_dc_compensate(SAMPLE_RATE);
+        assert!((ss.get_dc_offset() - 0.2).abs() < 1e-3);
+        assert!(mean(&mut ss).abs() < 1e-3);
+
+        ss.auto_dc_compensate(0);
+        assert_eq!(ss.get_dc_offset(), 0.0);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_patch() {
+        let json = r#"{
+            "input_gain": 0.5,
+            "output_gain": 0.8,
+            "generator": { "kind": "Sine", "frequency": 440.0 },
+            "modifiers": [
+                { "kind": "LowPass", "cutoff": 880.0, "q": 1.0 },
+                { "kind": "HighPass", "cutoff": 110.0, "q": 0.7 }
+            ],
+            "metadata": { "name": "filtered sine" }
+        }"#;
+        let patch: Patch = serde_json::from_str(json).unwrap();
+        assert_eq!(patch.generator, GeneratorPatch::Sine { frequency: 440.0 });
+        assert_eq!(patch.modifiers.len(), 2);
+
+        let mut a = BaeSound::from_patch(&patch, SAMPLE_RATE as MathT);
+        let mut b = BaeSound::new(
+            0.5,
+            0.8,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                440.0,
+                SAMPLE_RATE as MathT,
+            )))),
+        );
+        b.add_modifier(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+            880.0,
             1.0,
             SAMPLE_RATE as MathT,
+        )))));
+        b.add_modifier(Arc::new(Mutex::new(BaeBlock::from_modifier(
+            HighPass::new(110.0, 0.7, SAMPLE_RATE as MathT),
         ))));
 
-        cs.add_connection(cs.get_input_gain(), n);
-        cs.add_connection(n, lp);
-        cs.add_connection(lp, hp);
-        cs.add_connection(hp, cs.get_output_gain());
-
-        let mut t = SampleTrackT::new();
+        assert_eq!(a.modifier_count(), b.modifier_count());
+        assert_eq!(a.get_meta("name"), Some("filtered sine"));
 
-        for _ in 0..seconds_to_samples(Duration::from_secs(4), SAMPLE_RATE as MathT) {
-            t.push(cs.process(0.0));
+        for _ in 0..SAMPLE_RATE / 10 {
+            assert_eq!(a.process(0.0), b.process(0.0));
         }
 
-        normalize_write(
-            -1.5,
-            t,
-            &mut File::create(".junk/sounds/complex_sounds.wav").unwrap(),
+        let round_trip: Patch =
+            serde_json::from_str(&serde_json::to_string(&patch).unwrap()).unwrap();
+        assert_eq!(round_trip, patch);
+
+        let minimal: Patch = serde_json::from_str(
+            r#"{ "input_gain": 1.0, "output_gain": 1.0, "generator": { "kind": "Noise" } }"#,
         )
         .unwrap();
+        assert_eq!(minimal.generator, GeneratorPatch::Noise);
+        assert!(minimal.modifiers.is_empty());
+        assert_eq!(
+            BaeSound::from_patch(&minimal, SAMPLE_RATE as MathT).modifier_count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_block_sound() {
+        let mut bs = BlockSound::new(Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+            440.0,
+            SAMPLE_RATE as MathT,
+        )))));
+        let mut reference = Sine::new(440.0, SAMPLE_RATE as MathT);
+
+        assert_eq!(bs.get_id(), None);
+        bs.register(3);
+        assert_eq!(bs.get_id(), Some(3));
+
+        for _ in 0..SAMPLE_RATE / 100 {
+            assert_eq!(bs.process(0.0), reference.process());
+        }
+
+        bs.toggle_pause();
+        assert!(bs.is_paused());
+        assert!((0..SAMPLE_RATE / 100).all(|_| bs.process(0.0) == 0.0));
+
+        bs.toggle_pause();
+        bs.toggle_mute();
+        assert_eq!(bs.get_mute_ramp(), 240);
+        for i in 0..SAMPLE_RATE / 100 {
+            let gain = (1.0 - (i + 1) as SampleT / 240.0).max(0.0);
+            assert!((bs.process(0.0) - reference.process() * gain).abs() < 1e-4);
+        }
+
+        bs.toggle_mute();
+        for i in 0..SAMPLE_RATE / 100 {
+            let gain = ((i + 1) as SampleT / 240.0).min(1.0);
+            assert!((bs.process(0.0) - reference.process() * gain).abs() < 1e-4);
+        }
+
+        bs.set_mute_ramp(Duration::from_secs(0), SAMPLE_RATE as MathT);
+        assert_eq!(bs.get_mute_ramp(), 0);
+        bs.toggle_mute();
+        assert_eq!(bs.process(0.0), 0.0);
+        bs.toggle_mute();
+        reference.process();
+        assert_eq!(bs.process(0.0), reference.process());
+
+        bs.unregister();
+        assert_eq!(bs.get_id(), None);
+
+        let mut passthrough = BlockSound::new(Arc::new(Mutex::new(BaeBlock::from_modifier(
+            Passthrough::new(),
+        ))));
+        assert_eq!(passthrough.process(0.5), 0.5);
+    }
+
+    #[test]
+    fn test_sound_gain_db() {
+        let mut ss = BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Zero::new()))),
+        );
+
+        ss.set_input_gain_db(-6.0);
+        ss.set_output_gain_db(-6.0);
+
+        assert!((ss.get_input_gain() - 0.5012).abs() < 1e-4);
+        assert!((ss.get_output_gain() - 0.5012).abs() < 1e-4);
+        assert!((ss.get_input_gain_db() + 6.0).abs() < 1e-4);
+        assert!((ss.get_output_gain_db() + 6.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_gain_envelopes() {
+        let mut ss = BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_modifier(Passthrough::new()))),
+        );
+        ss.set_input_gain_envelope(
+            vec![(Duration::from_secs(0), 0.0), (Duration::from_secs(1), 1.0)],
+            SAMPLE_RATE as MathT,
+        );
+
+        let t: SampleTrackT = (0..SAMPLE_RATE + 10).map(|_| ss.process(1.0)).collect();
+        assert_eq!(t[0], 0.0);
+        assert!((t[SAMPLE_RATE / 4] - 0.25).abs() < 1e-4);
+        assert!((t[SAMPLE_RATE / 2] - 0.5).abs() < 1e-4);
+        assert!(t[SAMPLE_RATE..].iter().all(|y| (y - 1.0).abs() < 1e-6));
+
+        ss.set_output_gain(0.5);
+        ss.set_output_gain_envelope(
+            vec![
+                (Duration::from_millis(10), 2.0),
+                (Duration::from_millis(5), 0.0),
+            ],
+            SAMPLE_RATE as MathT,
+        );
+        let t: SampleTrackT = (0..SAMPLE_RATE / 50).map(|_| ss.process(1.0)).collect();
+        assert_eq!(t[0], 0.0);
+        assert!((t[SAMPLE_RATE / 400 * 3] - 1.0).abs() < 1e-4);
+        assert!((t[SAMPLE_RATE / 50 - 1] - 2.0).abs() < 1e-6);
+
+        ss.set_input_gain_envelope(Vec::new(), SAMPLE_RATE as MathT);
+        ss.set_output_gain_envelope(Vec::new(), SAMPLE_RATE as MathT);
+        assert!((ss.process(1.0) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_mute_ramp() {
+        let mut ss = BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                440.0,
+                SAMPLE_RATE as MathT,
+            )))),
+        );
+        assert_eq!(ss.get_mute_ramp(), 240);
+
+        let mut last = 0.0;
+        for _ in 0..27 {
+            last = ss.process(0.0);
+        }
+        assert!(last > 0.9);
+
+        ss.toggle_mute();
+        let t: SampleTrackT = (0..480).map(|_| ss.process(0.0)).collect();
+        assert!((t[0] - last).abs() < 0.1);
+        assert!(t.windows(2).all(|w| (w[1] - w[0]).abs() < 0.1));
+        assert!(t[240..].iter().all(|y| *y == 0.0));
+
+        ss.toggle_mute();
+        let t: SampleTrackT = (0..480).map(|_| ss.process(0.0)).collect();
+        assert!(t[0].abs() < 0.01);
+        assert!(t.windows(2).all(|w| (w[1] - w[0]).abs() < 0.1));
+        assert!(t[240..].iter().any(|y| y.abs() > 0.9));
+
+        ss.set_mute_ramp(Duration::from_secs(0), SAMPLE_RATE as MathT);
+        assert_eq!(ss.get_mute_ramp(), 0);
+        ss.toggle_mute();
+        assert_eq!(ss.process(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_duration() {
+        let mut ss = BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                440.0,
+                SAMPLE_RATE as MathT,
+            )))),
+        );
+        ss.set_duration(Duration::from_secs_f64(0.5), SAMPLE_RATE as MathT);
+
+        ss.toggle_pause();
+        for _ in 0..SAMPLE_RATE {
+            ss.process(0.0);
+        }
+        assert!(!ss.is_finished());
+        ss.toggle_pause();
+
+        let mut s = Sine::new(440.0, SAMPLE_RATE as MathT);
+        for _ in 0..SAMPLE_RATE / 2 {
+            assert!(!ss.is_finished());
+            assert!((ss.process(0.0) - s.process()).abs() < 1e-6);
+        }
+        assert!(ss.is_finished());
+        assert!((0..100).all(|_| ss.process(0.0) == 0.0));
+
+        ss.clear_duration();
+        assert!(!ss.is_finished());
+        assert!((ss.process(0.0) - s.process()).abs() < 1e-6);
+
+        ss.set_duration(Duration::from_nanos(20_833), SAMPLE_RATE as MathT);
+        assert!(!ss.is_finished());
+        ss.process(0.0);
+        assert!(ss.is_finished());
+    }
+
+    #[test]
+    fn test_metadata() {
+        let mut b = BaeBlock::from_generator(Zero::new());
+        assert_eq!(b.get_meta("name"), None);
+        b.set_meta("name", "Silence");
+        b.set_meta("color", "#000000");
+        assert_eq!(b.get_meta("name"), Some("Silence"));
+        assert_eq!(b.get_meta("color"), Some("#000000"));
+
+        let mut ss = BaeSound::new(1.0, 1.0, Arc::new(Mutex::new(b)));
+        ss.set_meta("name", "Pad");
+        assert_eq!(ss.clone().get_meta("name"), Some("Pad"));
+
+        let mut cs = ComplexSound::new(1.0, 1.0);
+        cs.add_connection(cs.get_input_gain(), cs.get_output_gain());
+        cs.set_meta("name", "Lead");
+        assert_eq!(cs.get_meta("name"), Some("Lead"));
+
+        match cs.try_into_bae_sound() {
+            Ok(ss) => assert_eq!(ss.get_meta("name"), Some("Lead")),
+            Err(_) => panic!("linear graph was rejected"),
+        }
+    }
+
+    #[test]
+    fn test_parameters() {
+        use bae_sounds::modifiers::Biquad;
+
+        let mut ss = BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Noise::new()))),
+        );
+        ss.extend_modifiers(vec![
+            Arc::new(Mutex::new(BaeBlock::from_parameterized_modifier(
+                Biquad::low_pass(8000.0, 0.7, SAMPLE_RATE as MathT),
+            ))),
+            Arc::new(Mutex::new(BaeBlock::from_parameterized_modifier(
+                Biquad::high_pass(100.0, 0.7, SAMPLE_RATE as MathT),
+            ))),
+        ]);
+
+        let names: Vec<String> = ss.all_parameters().into_iter().map(|(n, _)| n).collect();
+        assert_eq!(
+            names,
+            vec![
+                "1.frequency",
+                "1.q",
+                "1.gain_db",
+                "2.frequency",
+                "2.q",
+                "2.gain_db"
+            ]
+        );
+        assert!(ss
+            .all_parameters()
+            .contains(&("2.frequency".to_owned(), 100.0)));
+
+        let rms = |ss: &mut BaeSound| {
+            let n = SAMPLE_RATE / 10;
+            let sum: MathT = (0..n).map(|_| (ss.process(0.0) as MathT).powi(2)).sum();
+            (sum / n as MathT).sqrt()
+        };
+
+        let open = rms(&mut ss);
+        assert!(ss.set_parameter("1.frequency", 200.0));
+        assert!(ss
+            .all_parameters()
+            .contains(&("1.frequency".to_owned(), 200.0)));
+        rms(&mut ss);
+        assert!(rms(&mut ss) < 0.5 * open);
+
+        assert!(!ss.set_parameter("1.cutoff", 200.0));
+        assert!(!ss.set_parameter("3.frequency", 200.0));
+        assert!(!ss.set_parameter("0.frequency", 200.0));
+        assert!(!ss.set_parameter("frequency", 200.0));
+    }
+
+    #[test]
+    fn test_mod_matrix() {
+        let mut ss = BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_modifier(Passthrough::new()))),
+        );
+
+        let m = ss.get_mod_matrix_mut();
+        let slow = m.add_source(Arc::new(Mutex::new(Sine::new(5.0, SAMPLE_RATE as MathT))));
+        let fast = m.add_source(Arc::new(Mutex::new(Sine::new(7.0, SAMPLE_RATE as MathT))));
+        m.add_route(slow, ModDestination::OutputGain, 0.5);
+        m.add_route(fast, ModDestination::InputGain, 0.25);
+        assert_eq!(m.route_count(), 2);
+
+        let mut slow = Sine::new(5.0, SAMPLE_RATE as MathT);
+        let mut fast = Sine::new(7.0, SAMPLE_RATE as MathT);
+
+        for _ in 0..SAMPLE_RATE {
+            let expected = (1.0 + 0.25 * fast.process()) * (1.0 + 0.5 * slow.process());
+            assert!((ss.process(1.0) - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_mod_matrix_parameters() {
+        use bae_sounds::modifiers::Biquad;
+
+        let mut ss = BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Noise::new()))),
+        );
+        ss.add_modifier(Arc::new(Mutex::new(BaeBlock::from_parameterized_modifier(
+            Biquad::low_pass(1000.0, 0.707, SAMPLE_RATE as MathT),
+        ))));
+
+        let m = ss.get_mod_matrix_mut();
+        let lfo = m.add_source(Arc::new(Mutex::new(Sine::new(5.0, SAMPLE_RATE as MathT))));
+        let cutoff = ModDestination::Parameter {
+            block: 1,
+            name: "frequency".to_owned(),
+        };
+        m.add_route(lfo, cutoff.clone(), 0.5);
+        m.add_route(lfo, ModDestination::OutputGain, 0.25);
+
+        let frequency = |ss: &BaeSound| {
+            ss.all_parameters()
+                .into_iter()
+                .find(|(n, _)| n == "1.frequency")
+                .map(|(_, v)| v)
+                .unwrap()
+        };
+
+        let mut reference = Sine::new(5.0, SAMPLE_RATE as MathT);
+        let (mut lowest, mut highest) = (MathT::MAX, 0.0 as MathT);
+        for _ in 0..SAMPLE_RATE / 5 {
+            ss.process(0.0);
+
+            let expected = 1000.0 * (1.0 + 0.5 * reference.process() as MathT);
+            let f = frequency(&ss);
+            assert!((f - expected).abs() < 1e-3);
+
+            lowest = lowest.min(f);
+            highest = highest.max(f);
+        }
+        assert!(lowest < 520.0 && highest > 1480.0);
+
+        assert!(ss.set_parameter("1.frequency", 2000.0));
+        ss.process(0.0);
+        let expected = 2000.0 * (1.0 + 0.5 * reference.process() as MathT);
+        assert!((frequency(&ss) - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_control_rate() {
+        let lfo = || {
+            let mut b = BaeBlock::from_generator(Sine::new(5.0, SAMPLE_RATE as MathT));
+            b.set_control_rate(true);
+            assert!(b.is_control_rate());
+            Arc::new(Mutex::new(b))
+        };
+
+        let mut control = BaeSound::new(1.0, 1.0, lfo());
+        control.set_control_rate_divisor(4);
+        assert_eq!(control.get_control_rate_divisor(), 4);
+
+        let mut reference = Sine::new(5.0, SAMPLE_RATE as MathT);
+        let mut held = 0.0;
+
+        for i in 0..SAMPLE_RATE / 10 {
+            if i % 4 == 0 {
+                held = reference.process();
+            }
+            assert!((control.process(0.0) - held).abs() < 1e-6);
+        }
+
+        let mut tremolo = BaeSound::new(1.0, 1.0, lfo());
+        tremolo.set_control_rate_divisor(4);
+        tremolo.extend_modifiers(vec![Arc::new(Mutex::new(BaeBlock::new(
+            Sine::new(440.0, SAMPLE_RATE as MathT),
+            Passthrough::new(),
+            BaeBlock::default_interactor(),
+        )))]);
+
+        let mut reference = Sine::new(5.0, SAMPLE_RATE as MathT);
+        let mut carrier = Sine::new(440.0, SAMPLE_RATE as MathT);
+
+        for i in 0..SAMPLE_RATE / 10 {
+            if i % 4 == 0 {
+                held = reference.process();
+            }
+            let expected = held * carrier.process();
+            assert!((tremolo.process(0.0) - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_sync_lfos() {
+        use bae_sounds::generators::QuadOsc;
+
+        let quarter = SAMPLE_RATE / 5 / 4;
+
+        let a: BlockSP = Arc::new(Mutex::new(BaeBlock::from_lfo(QuadOsc::new(
+            5.0,
+            SAMPLE_RATE as MathT,
+        ))));
+        let mut ring = BaeBlock::from_lfo(QuadOsc::new(5.0, SAMPLE_RATE as MathT));
+        ring.set_inter_kind(InterKind::Multiply);
+        let b: BlockSP = Arc::new(Mutex::new(ring));
+
+        let advance = |b: &BlockSP| {
+            for _ in 0..quarter {
+                b.lock().unwrap().process();
+            }
+        };
+
+        let mut ss = BaeSound::new(1.0, 1.0, a);
+        ss.add_modifier(b.clone());
+        advance(&b);
+
+        let t: SampleTrackT = (0..SAMPLE_RATE / 5).map(|_| ss.process(0.0)).collect();
+        assert!(t.iter().any(|y| *y < -0.4));
+
+        let in_phase = |ss: &mut BaeSound| {
+            let mut s = QuadOsc::new(5.0, SAMPLE_RATE as MathT);
+            for _ in 0..SAMPLE_RATE / 5 {
+                let x = s.process();
+                assert!((ss.process(0.0) - x * x).abs() < 1e-5);
+            }
+        };
+
+        ss.sync_lfos();
+        in_phase(&mut ss);
+
+        assert!(!ss.get_lfo_sync_on_trigger());
+        ss.set_lfo_sync_on_trigger(true);
+        advance(&b);
+        ss.toggle_pause();
+        ss.toggle_pause();
+        in_phase(&mut ss);
+    }
+
+    #[test]
+    fn test_parallel_modifiers() {
+        use bae_sounds::generators::QuadOsc;
+        use bae_sounds::modifiers::Biquad;
+
+        let mut ss = BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                1000.0,
+                SAMPLE_RATE as MathT,
+            )))),
+        );
+        ss.add_modifier(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+            2000.0,
+            0.7,
+            SAMPLE_RATE as MathT,
+        )))));
+        ss.add_parallel_modifier(
+            Arc::new(Mutex::new(BaeBlock::from_modifier(HighPass::new(
+                500.0,
+                0.7,
+                SAMPLE_RATE as MathT,
+            )))),
+            0.5,
+        );
+        assert_eq!(ss.modifier_count(), 1);
+        assert_eq!(ss.parallel_modifier_count(), 1);
+
+        let mut s = Sine::new(1000.0, SAMPLE_RATE as MathT);
+        let mut lp = LowPass::new(2000.0, 0.7, SAMPLE_RATE as MathT);
+        let mut hp = HighPass::new(500.0, 0.7, SAMPLE_RATE as MathT);
+
+        for _ in 0..SAMPLE_RATE / 10 {
+            let g = s.process();
+            let expected = lp.process(g + 0.5 * hp.process(g));
+            assert!((ss.process(0.0) - expected).abs() < 1e-6);
+        }
+
+        let mut send = BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                1000.0,
+                SAMPLE_RATE as MathT,
+            )))),
+        );
+        send.add_modifier(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+            2000.0,
+            0.7,
+            SAMPLE_RATE as MathT,
+        )))));
+        send.add_parallel_modifier(
+            Arc::new(Mutex::new(BaeBlock::from_parameterized_modifier(
+                Biquad::high_pass(800.0, 0.7, SAMPLE_RATE as MathT),
+            ))),
+            0.5,
+        );
+        assert_eq!(send.get_parallel_position(), ParallelPosition::BeforeChain);
+        send.set_parallel_position(ParallelPosition::AfterChain);
+
+        assert!(send
+            .all_parameters()
+            .contains(&("2.frequency".to_owned(), 800.0)));
+        assert!(send.set_parameter("2.frequency", 500.0));
+
+        let mut s = Sine::new(1000.0, SAMPLE_RATE as MathT);
+        let mut lp = LowPass::new(2000.0, 0.7, SAMPLE_RATE as MathT);
+        let mut hp = Biquad::high_pass(500.0, 0.7, SAMPLE_RATE as MathT);
+
+        for _ in 0..SAMPLE_RATE / 10 {
+            let dry = lp.process(s.process());
+            let expected = dry + 0.5 * hp.process(dry);
+            assert!((send.process(0.0) - expected).abs() < 1e-5);
+        }
+
+        let mut lfos = BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_modifier(Passthrough::new()))),
+        );
+        lfos.add_parallel_modifier(
+            Arc::new(Mutex::new(BaeBlock::from_lfo(QuadOsc::new(
+                5.0,
+                SAMPLE_RATE as MathT,
+            )))),
+            0.5,
+        );
+        for _ in 0..SAMPLE_RATE / 7 {
+            lfos.process(0.0);
+        }
+        lfos.sync_lfos();
+
+        let mut osc = QuadOsc::new(5.0, SAMPLE_RATE as MathT);
+        for _ in 0..SAMPLE_RATE / 10 {
+            assert!((lfos.process(0.0) - 0.5 * osc.process()).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_split_sound() {
+        let chain = || -> Vec<BlockSP> {
+            vec![
+                Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+                    880.0,
+                    1.0,
+                    SAMPLE_RATE as MathT,
+                )))),
+                Arc::new(Mutex::new(BaeBlock::from_modifier(HighPass::new(
+                    220.0,
+                    1.0,
+                    SAMPLE_RATE as MathT,
+                )))),
+                Arc::new(Mutex::new(BaeBlock::from_modifier(Gain::new(0.5)))),
+            ]
+        };
+        let source = || {
+            Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                440.0,
+                SAMPLE_RATE as MathT,
+            ))))
+        };
+
+        let mut original = BaeSound::new(0.75, 0.5, source());
+        original.extend_modifiers(chain());
+
+        let mut head = BaeSound::new(0.75, 0.5, source());
+        head.extend_modifiers(chain());
+        let mut tail = head.split_at(1);
+
+        assert_eq!(head.get_input_gain(), 0.75);
+        assert_eq!(head.get_output_gain(), 1.0);
+        assert_eq!(tail.get_input_gain(), 1.0);
+        assert_eq!(tail.get_output_gain(), 0.5);
+
+        for _ in 0..seconds_to_samples(Duration::from_secs_f64(0.1), SAMPLE_RATE as MathT) {
+            let expected = original.process(0.0);
+            let actual = tail.process(head.process(0.0));
+            assert!((expected - actual).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_split_sound_modulation() {
+        use bae_sounds::modifiers::Biquad;
+
+        let low_pass = |f| -> BlockSP {
+            Arc::new(Mutex::new(BaeBlock::from_parameterized_modifier(
+                Biquad::low_pass(f, 0.707, SAMPLE_RATE as MathT),
+            )))
+        };
+        let frequency = |block| ModDestination::Parameter {
+            block,
+            name: "frequency".to_owned(),
+        };
+        let parameter = |ss: &BaeSound, name: &str| {
+            ss.all_parameters()
+                .into_iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v)
+                .unwrap()
+        };
+
+        let mut head = BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Noise::new()))),
+        );
+        head.add_modifier(Arc::new(Mutex::new(BaeBlock::from_modifier(Gain::new(
+            0.5,
+        )))));
+        head.add_modifier(low_pass(1000.0));
+        head.add_parallel_modifier(low_pass(500.0), 0.5);
+
+        let m = head.get_mod_matrix_mut();
+        let dc = m.add_source(Arc::new(Mutex::new(Dc(0.5))));
+        m.add_route(dc, frequency(2), 1.0);
+        m.add_route(dc, frequency(3), 1.0);
+        m.add_route(dc, ModDestination::OutputGain, 0.5);
+
+        let mut tail = head.split_at(1);
+        assert_eq!(head.get_mod_matrix().route_count(), 2);
+        assert_eq!(tail.get_mod_matrix().route_count(), 0);
+
+        tail.process(head.process(0.0));
+        assert!((parameter(&head, "2.frequency") - 750.0).abs() < 1e-3);
+        assert_eq!(parameter(&tail, "1.frequency"), 1000.0);
+    }
+
+    #[test]
+    fn test_modifier_editing() {
+        let lp = || -> BlockSP {
+            Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+                880.0,
+                1.0,
+                SAMPLE_RATE as MathT,
+            ))))
+        };
+        let hp = || -> BlockSP {
+            Arc::new(Mutex::new(BaeBlock::from_modifier(HighPass::new(
+                220.0,
+                1.0,
+                SAMPLE_RATE as MathT,
+            ))))
+        };
+        let gain = || -> BlockSP { Arc::new(Mutex::new(BaeBlock::from_modifier(Gain::new(0.5)))) };
+        let source = || {
+            Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                440.0,
+                SAMPLE_RATE as MathT,
+            ))))
+        };
+
+        let mut edited = BaeSound::new(1.0, 1.0, source());
+        edited.extend_modifiers(vec![lp(), gain(), hp()]);
+        assert_eq!(edited.modifier_count(), 3);
+
+        assert!(edited.remove_modifier(3).is_none());
+        assert!(edited.remove_modifier(1).is_some());
+        assert_eq!(edited.modifier_count(), 2);
+
+        let mut fresh = BaeSound::new(1.0, 1.0, source());
+        fresh.extend_modifiers(vec![lp(), hp()]);
+
+        for _ in 0..SAMPLE_RATE / 10 {
+            assert_eq!(edited.process(0.0), fresh.process(0.0));
+        }
+
+        edited.insert_modifier(1, gain());
+        edited.insert_modifier(10, gain());
+        assert_eq!(edited.modifier_count(), 4);
+        assert!(edited.remove_modifier(3).is_some());
+
+        fresh.insert_modifier(1, gain());
+        for _ in 0..SAMPLE_RATE / 10 {
+            assert_eq!(edited.process(0.0), fresh.process(0.0));
+        }
+    }
+
+    #[test]
+    fn test_modifier_editing_keeps_modulation() {
+        use bae_sounds::modifiers::Biquad;
+
+        let gain = || -> BlockSP { Arc::new(Mutex::new(BaeBlock::from_modifier(Gain::new(0.5)))) };
+
+        let mut ss = BaeSound::new(
+            1.0,
+            1.0,
+            Arc::new(Mutex::new(BaeBlock::from_generator(Noise::new()))),
+        );
+        ss.add_modifier(gain());
+        ss.add_modifier(Arc::new(Mutex::new(BaeBlock::from_parameterized_modifier(
+            Biquad::low_pass(1000.0, 0.707, SAMPLE_RATE as MathT),
+        ))));
+
+        let m = ss.get_mod_matrix_mut();
+        let dc = m.add_source(Arc::new(Mutex::new(Dc(0.5))));
+        m.add_route(
+            dc,
+            ModDestination::Parameter {
+                block: 2,
+                name: "frequency".to_owned(),
+            },
+            1.0,
+        );
+        m.add_route(dc, ModDestination::OutputGain, 0.5);
+
+        let parameter = |ss: &BaeSound, name: &str| {
+            ss.all_parameters()
+                .into_iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v)
+        };
+
+        ss.process(0.0);
+        assert!((parameter(&ss, "2.frequency").unwrap() - 1500.0).abs() < 1e-3);
+
+        ss.insert_modifier(0, gain());
+        assert!(ss.set_parameter("3.frequency", 2000.0));
+        ss.process(0.0);
+        assert!((parameter(&ss, "3.frequency").unwrap() - 3000.0).abs() < 1e-3);
+
+        assert!(ss.remove_modifier(0).is_some());
+        assert!(ss.set_parameter("2.frequency", 1000.0));
+        ss.process(0.0);
+        assert!((parameter(&ss, "2.frequency").unwrap() - 1500.0).abs() < 1e-3);
+        assert_eq!(ss.get_mod_matrix().route_count(), 2);
+
+        assert!(ss.remove_modifier(1).is_some());
+        assert_eq!(ss.get_mod_matrix().route_count(), 1);
+        assert!(parameter(&ss, "2.frequency").is_none());
+    }
+
+    #[test]
+    fn test_complex_sounds() {
+        let mut cs = ComplexSound::new(1.0, 1.0);
+
+        let n = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_generator(Noise::new()))));
+        let lp = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+            440.0,
+            1.0,
+            SAMPLE_RATE as MathT,
+        )))));
+        let hp = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(
+            HighPass::new(220.0, 1.0, SAMPLE_RATE as MathT),
+        ))));
+
+        cs.add_connection(cs.get_input_gain(), n);
+        cs.add_connection(n, lp);
+        cs.add_connection(lp, hp);
+        cs.add_connection(hp, cs.get_output_gain());
+
+        let mut t = SampleTrackT::new();
+
+        for _ in 0..seconds_to_samples(Duration::from_secs(4), SAMPLE_RATE as MathT) {
+            t.push(cs.process(0.0));
+        }
+
+        normalize_write(
+            -1.5,
+            t,
+            &mut File::create(".junk/sounds/complex_sounds.wav").unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_complex_to_bae_sound() {
+        let linear = || {
+            let mut cs = ComplexSound::new(0.5, 0.75);
+
+            let s = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                440.0,
+                SAMPLE_RATE as MathT,
+            )))));
+            let lp = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+                440.0,
+                1.0,
+                SAMPLE_RATE as MathT,
+            )))));
+            let hp = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(
+                HighPass::new(220.0, 1.0, SAMPLE_RATE as MathT),
+            ))));
+
+            cs.add_connection(cs.get_input_gain(), s);
+            cs.add_connection(s, lp);
+            cs.add_connection(lp, hp);
+            cs.add_connection(hp, cs.get_output_gain());
+
+            cs
+        };
+
+        let mut cs = linear();
+        let mut ss = match linear().try_into_bae_sound() {
+            Ok(ss) => ss,
+            Err(_) => panic!("linear graph was rejected"),
+        };
+
+        for _ in 0..seconds_to_samples(Duration::from_secs_f64(0.1), SAMPLE_RATE as MathT) {
+            assert!((cs.process(0.0) - ss.process(0.0)).abs() < 1e-9);
+        }
+
+        let mut branching = ComplexSound::new(1.0, 1.0);
+        let n = branching.add_block(Arc::new(Mutex::new(BaeBlock::from_generator(Noise::new()))));
+        let lp = branching.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+            440.0,
+            1.0,
+            SAMPLE_RATE as MathT,
+        )))));
+        let hp = branching.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(
+            HighPass::new(220.0, 1.0, SAMPLE_RATE as MathT),
+        ))));
+
+        branching.add_connection(branching.get_input_gain(), n);
+        branching.add_connection(n, lp);
+        branching.add_connection(n, hp);
+        branching.add_connection(lp, branching.get_output_gain());
+        branching.add_connection(hp, branching.get_output_gain());
+
+        let mut branching = match branching.try_into_bae_sound() {
+            Ok(_) => panic!("branching graph was accepted"),
+            Err(cs) => cs,
+        };
+        assert_eq!(branching.blocks().count(), 5);
+        branching.process(0.0);
+    }
+
+    #[test]
+    fn test_complex_sound_block_processing() {
+        let build = |feedback: bool, guard: bool| {
+            let mut cs = ComplexSound::new(1.0, 0.5);
+            cs.set_stability_guard(guard);
+
+            let s = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                440.0,
+                SAMPLE_RATE as MathT,
+            )))));
+            let lp = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+                880.0,
+                1.0,
+                SAMPLE_RATE as MathT,
+            )))));
+            let hp = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(
+                HighPass::new(220.0, 1.0, SAMPLE_RATE as MathT),
+            ))));
+            let g = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(Gain::new(
+                0.5,
+            )))));
+
+            cs.add_connection(cs.get_input_gain(), s);
+            cs.add_connection(s, lp);
+            cs.add_connection(s, hp);
+            cs.add_connection(lp, g);
+            cs.add_connection(hp, g);
+            cs.add_connection(g, cs.get_output_gain());
+            if feedback {
+                cs.add_connection(g, lp);
+            }
+
+            cs
+        };
+
+        for (feedback, guard) in &[(false, false), (true, false), (false, true)] {
+            let mut sample = build(*feedback, *guard);
+            let mut block = build(*feedback, *guard);
+            block.set_block_size(64);
+            assert_eq!(block.get_block_size(), 64);
+
+            let mut out = vec![0.0; 1000];
+            block.process_block(&mut out);
+
+            for y in &out {
+                assert!((sample.process(0.0) - y).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_feedback_loop() {
+        let mut cs = ComplexSound::new(1.0, 1.0);
+        let a = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(Gain::new(
+            0.5,
+        )))));
+        let b = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(
+            Passthrough::new(),
+        ))));
+
+        cs.add_connection(cs.get_input_gain(), a);
+        cs.add_connection(a, b);
+        cs.add_connection(b, cs.get_output_gain());
+        assert!(!cs.has_cycle());
+
+        cs.add_connection(b, a);
+        assert!(cs.has_cycle());
+
+        let mut expected = 0.5;
+        for i in 0..10 {
+            let y = cs.process(if i == 0 { 1.0 } else { 0.0 });
+            assert!((y - expected).abs() < 1e-7);
+            expected *= 0.5;
+        }
+
+        assert!(cs.remove_connection(b, a));
+        assert!(!cs.remove_connection(b, a));
+        assert!(!cs.has_cycle());
+    }
+
+    #[test]
+    fn test_edge_ramps() {
+        let render = |ramp: Duration| {
+            let mut cs = ComplexSound::new(1.0, 1.0);
+            let s = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                440.0,
+                SAMPLE_RATE as MathT,
+            )))));
+            cs.add_connection(cs.get_input_gain(), s);
+            cs.add_connection(s, cs.get_output_gain());
+            cs.set_edge_ramp(ramp, SAMPLE_RATE as MathT);
+
+            let mut t = SampleTrackT::new();
+            while t.len() < 100 || t.last().unwrap().abs() < 0.9 {
+                t.push(cs.process(0.0));
+            }
+
+            assert!(cs.remove_connection(s, cs.get_output_gain()));
+            for _ in 0..SAMPLE_RATE / 100 {
+                t.push(cs.process(0.0));
+            }
+            assert_eq!(*t.last().unwrap(), 0.0);
+
+            cs.add_connection(s, cs.get_output_gain());
+            for _ in 0..SAMPLE_RATE / 100 {
+                t.push(cs.process(0.0));
+            }
+            assert!(t.last().unwrap().abs() > 0.0);
+
+            t.windows(2)
+                .map(|w| (w[1] - w[0]).abs())
+                .fold(0.0, SampleT::max)
+        };
+
+        let ramped = ComplexSound::new(1.0, 1.0);
+        assert_eq!(ramped.get_edge_ramp(), 240);
+
+        assert!(render(Duration::from_millis(5)) < 0.07);
+        assert!(render(Duration::from_secs(0)) > 0.5);
+    }
+
+    #[test]
+    fn test_stability_guard() {
+        let build = |guard: bool| {
+            let mut cs = ComplexSound::new(1.0, 1.0);
+            cs.set_stability_guard(guard);
+            assert_eq!(cs.get_stability_guard(), guard);
+
+            let s = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                440.0,
+                SAMPLE_RATE as MathT,
+            )))));
+            let g = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(Gain::new(
+                1.01,
+            )))));
+
+            cs.add_connection(cs.get_input_gain(), s);
+            cs.add_connection(s, g);
+            cs.add_connection(g, g);
+            cs.add_connection(g, cs.get_output_gain());
+
+            (cs, g)
+        };
+
+        let (mut unguarded, _) = build(false);
+        let peak = (0..SAMPLE_RATE / 10)
+            .map(|_| unguarded.process(0.0).abs())
+            .fold(0.0, |p: SampleT, y| {
+                if y.is_finite() {
+                    p.max(y)
+                } else {
+                    SampleT::MAX
+                }
+            });
+        assert!(peak > STABILITY_THRESHOLD);
+
+        let (mut guarded, g) = build(true);
+        for _ in 0..SAMPLE_RATE / 10 {
+            let y = guarded.process(0.0);
+            assert!(y.is_finite());
+            assert!(y.abs() <= STABILITY_THRESHOLD);
+        }
+        assert_eq!(guarded.stability_trips(), &[g][..]);
+
+        guarded.reset_stability_guard();
+        assert!(guarded.stability_trips().is_empty());
+
+        let (mut block, g) = build(true);
+        let mut out = vec![0.0; SAMPLE_RATE / 10];
+        block.process_block(&mut out);
+        assert!(out
+            .iter()
+            .all(|y| y.is_finite() && y.abs() <= STABILITY_THRESHOLD));
+        assert_eq!(block.stability_trips(), &[g][..]);
+
+        let mut runaway = ComplexSound::new(1.0, 1.0);
+        runaway.set_stability_guard(true);
+        let inf = runaway.add_block(Arc::new(Mutex::new(BaeBlock::from_generator(Dc(
+            SampleT::INFINITY,
+        )))));
+        runaway.add_connection(inf, runaway.get_output_gain());
+        for _ in 0..SAMPLE_RATE / 10 {
+            assert_eq!(runaway.process(0.0), 0.0);
+        }
+        assert_eq!(runaway.stability_trips(), &[inf][..]);
+
+        runaway.reset_stability_guard();
+        runaway.process(0.0);
+        assert_eq!(runaway.stability_trips(), &[inf][..]);
+    }
+
+    #[test]
+    fn test_complex_sound_introspection() {
+        let mut cs = ComplexSound::new(1.0, 1.0);
+
+        let n = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_generator(Noise::new()))));
+        let lp = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+            440.0,
+            1.0,
+            SAMPLE_RATE as MathT,
+        )))));
+        let hp = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(
+            HighPass::new(220.0, 1.0, SAMPLE_RATE as MathT),
+        ))));
+
+        cs.add_connection(cs.get_input_gain(), n);
+        cs.add_connection(n, lp);
+        cs.add_connection(lp, hp);
+        cs.add_connection(hp, cs.get_output_gain());
+
+        let blocks: Vec<(usize, &str)> = cs.blocks().collect();
+        assert_eq!(blocks.len(), 5);
+        assert!(blocks
+            .iter()
+            .any(|(i, t)| *i == n.index() && t.ends_with("Noise")));
+        assert!(blocks
+            .iter()
+            .any(|(i, t)| *i == lp.index() && t.ends_with("LowPass")));
+        assert!(blocks
+            .iter()
+            .any(|(i, t)| *i == hp.index() && t.ends_with("HighPass")));
+
+        let connections: Vec<(usize, usize)> = cs.connections().collect();
+        assert_eq!(connections.len(), 4);
+        for edge in &[
+            (cs.get_input_gain().index(), n.index()),
+            (n.index(), lp.index()),
+            (lp.index(), hp.index()),
+            (hp.index(), cs.get_output_gain().index()),
+        ] {
+            assert!(connections.contains(edge));
+        }
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut cs = ComplexSound::new(0.5, 2.0);
+
+        let n = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_generator(Noise::new()))));
+        let lp = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+            440.0,
+            1.0,
+            SAMPLE_RATE as MathT,
+        )))));
+        let hp = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(
+            HighPass::new(220.0, 1.0, SAMPLE_RATE as MathT),
+        ))));
+
+        cs.add_connection(cs.get_input_gain(), n);
+        cs.add_connection(n, lp);
+        cs.add_connection(lp, hp);
+        cs.add_connection(hp, cs.get_output_gain());
+
+        let nodes: Vec<usize> = cs.nodes().collect();
+        assert_eq!(nodes.len(), 5);
+        for node in &[cs.get_input_gain(), cs.get_output_gain(), n, lp, hp] {
+            assert!(nodes.contains(&node.index()));
+        }
+
+        let dot = cs.to_dot();
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.ends_with("}\n"));
+        for (from, to) in &[
+            (cs.get_input_gain(), n),
+            (n, lp),
+            (lp, hp),
+            (hp, cs.get_output_gain()),
+        ] {
+            assert!(dot.contains(&format!("    {} -> {};\n", from.index(), to.index())));
+        }
+        assert_eq!(dot.matches(" -> ").count(), 4);
+        assert!(dot.contains(&format!(
+            "    {} [label=\"input gain (0.5)\", style=filled",
+            cs.get_input_gain().index()
+        )));
+        assert!(dot.contains(&format!(
+            "    {} [label=\"output gain (2)\", style=filled",
+            cs.get_output_gain().index()
+        )));
+        assert_eq!(dot.matches("style=filled").count(), 2);
+        assert!(dot.contains("LowPass\"];"));
+    }
+
+    #[test]
+    fn test_graph_string() {
+        use bae_sounds::modifiers::Biquad;
+
+        let registry = |name: &str| -> Option<BlockSP> {
+            if name.ends_with("Noise") {
+                Some(Arc::new(Mutex::new(BaeBlock::from_generator(Noise::new()))))
+            } else if name.ends_with("Sine") {
+                Some(Arc::new(Mutex::new(BaeBlock::from_generator(Sine::new(
+                    440.0,
+                    SAMPLE_RATE as MathT,
+                )))))
+            } else if name.ends_with("LowPass") {
+                Some(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+                    440.0,
+                    1.0,
+                    SAMPLE_RATE as MathT,
+                )))))
+            } else if name.ends_with("HighPass") {
+                Some(Arc::new(Mutex::new(BaeBlock::from_modifier(
+                    HighPass::new(220.0, 1.0, SAMPLE_RATE as MathT),
+                ))))
+            } else if name.ends_with("Biquad") {
+                Some(Arc::new(Mutex::new(BaeBlock::from_parameterized_modifier(
+                    Biquad::low_pass(1000.0, 0.7, SAMPLE_RATE as MathT),
+                ))))
+            } else {
+                None
+            }
+        };
+
+        let mut cs = ComplexSound::new(0.5, 0.8);
+        let n = cs.add_block(registry("Noise").unwrap());
+        let lp = cs.add_block(registry("LowPass").unwrap());
+        let hp = cs.add_block(registry("HighPass").unwrap());
+
+        cs.add_connection(cs.get_input_gain(), n);
+        cs.add_connection(n, lp);
+        cs.add_connection(lp, hp);
+        cs.add_connection(hp, cs.get_output_gain());
+
+        let text = cs.to_graph_string();
+        assert_eq!(text.lines().count(), 9);
+
+        let parsed = ComplexSound::from_graph_string(&text, registry).unwrap();
+        assert_eq!(parsed.to_graph_string(), text);
+        assert_eq!(parsed.get_input_gain(), cs.get_input_gain());
+        assert_eq!(parsed.get_output_gain(), cs.get_output_gain());
+        assert_eq!(
+            parsed.connections().collect::<Vec<_>>(),
+            cs.connections().collect::<Vec<_>>()
+        );
+
+        let mut cs = ComplexSound::new(0.5, 0.8);
+        let s = cs.add_block(registry("Sine").unwrap());
+        let bq = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_parameterized_modifier(
+            Biquad::low_pass(4000.0, 2.0, SAMPLE_RATE as MathT),
+        ))));
+        let hp = cs.add_block(registry("HighPass").unwrap());
+
+        cs.add_connection(cs.get_input_gain(), s);
+        cs.add_connection(s, bq);
+        cs.add_connection(bq, hp);
+        cs.add_connection(hp, cs.get_output_gain());
+
+        let mut parsed = ComplexSound::from_graph_string(&cs.to_graph_string(), registry).unwrap();
+        for _ in 0..SAMPLE_RATE / 10 {
+            assert!((parsed.process(0.0) - cs.process(0.0)).abs() < 1e-6);
+        }
+
+        let mut cs = ComplexSound::new(1.0, 1.0);
+        let s = cs.add_block(registry("Sine").unwrap());
+        let lp = cs.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+            4000.0,
+            1.0,
+            SAMPLE_RATE as MathT,
+        )))));
+        cs.add_connection(cs.get_input_gain(), s);
+        cs.add_connection(s, lp);
+        cs.add_connection(lp, cs.get_output_gain());
+
+        let text = cs.to_graph_string();
+        let node = text
+            .lines()
+            .find(|l| l.starts_with(&format!("node\t{}\t", lp.index())))
+            .unwrap();
+        assert_eq!(node.split('\t').count(), 3);
+
+        let mut parsed = ComplexSound::from_graph_string(&text, registry).unwrap();
+        let (a, b): (SampleTrackT, SampleTrackT) = (0..SAMPLE_RATE / 10)
+            .map(|_| (cs.process(0.0), parsed.process(0.0)))
+            .unzip();
+        assert!(a.iter().zip(&b).any(|(a, b)| (a - b).abs() > 0.1));
+
+        assert_eq!(
+            ComplexSound::from_graph_string("input\t0\t1\nnode\t2\tSquare", registry).err(),
+            Some(ParseError::UnknownBlock(2, "Square".to_owned()))
+        );
+        assert_eq!(
+            ComplexSound::from_graph_string("# comment\n\nbogus", registry).err(),
+            Some(ParseError::InvalidLine(3))
+        );
+        assert_eq!(
+            ComplexSound::from_graph_string("edge\t0\t9", registry).err(),
+            Some(ParseError::InvalidNode(1))
+        );
+    }
+
+    #[test]
+    fn test_estimated_cost() {
+        use bae_sounds::modifiers::{FdnReverb, Fir};
+
+        let mut simple = ComplexSound::new(1.0, 1.0);
+        let lp = simple.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(LowPass::new(
+            440.0,
+            1.0,
+            SAMPLE_RATE as MathT,
+        )))));
+        let hp = simple.add_block(Arc::new(Mutex::new(BaeBlock::from_modifier(
+            HighPass::new(220.0, 1.0, SAMPLE_RATE as MathT),
+        ))));
+        simple.add_connection(simple.get_input_gain(), lp);
+        simple.add_connection(lp, hp);
+        simple.add_connection(hp, simple.get_output_gain());
+
+        assert_eq!(simple.estimated_cost(), 4);
+
+        let fir = BaeBlock::from_costly_modifier(Fir::new(vec![1.0 / 64.0; 64]));
+        assert_eq!(fir.cost_hint(), 13);
+        assert_eq!(
+            BaeBlock::from_costly_modifier(Fir::new(vec![1.0])).cost_hint(),
+            1
+        );
+
+        let reverb = FdnReverb::new(
+            vec![1031, 1327, 1523, 1871, 2053, 2311, 2539, 2801],
+            Duration::from_secs(2),
+            0.3,
+            SAMPLE_RATE as MathT,
+        );
+        let reverb = BaeBlock::from_costly_modifier(reverb);
+        assert_eq!(reverb.cost_hint(), 18);
+
+        let oversampled = Oversampled::new(
+            BaeBlock::from_costly_modifier(Fir::new(vec![1.0 / 64.0; 64])),
+            4,
+            SAMPLE_RATE as MathT,
+        );
+        assert_eq!(oversampled.cost_hint(), (13 + 52) * 4 + 52);
+        assert_eq!(
+            Oversampled::new(
+                BaeBlock::from_costly_modifier(Fir::new(vec![1.0 / 64.0; 64])),
+                1,
+                SAMPLE_RATE as MathT,
+            )
+            .cost_hint(),
+            13
+        );
+
+        let mut expensive = ComplexSound::new(1.0, 1.0);
+        let f = expensive.add_block(Arc::new(Mutex::new(fir)));
+        let r = expensive.add_block(Arc::new(Mutex::new(reverb)));
+        let o = expensive.add_block(Arc::new(Mutex::new(oversampled)));
+        expensive.add_connection(expensive.get_input_gain(), f);
+        expensive.add_connection(f, r);
+        expensive.add_connection(r, o);
+        expensive.add_connection(o, expensive.get_output_gain());
+
+        assert_eq!(expensive.estimated_cost(), 2 + 13 + 18 + (13 + 52) * 4 + 52);
+        assert!(expensive.estimated_cost() > simple.estimated_cost());
+    }
+
+    #[test]
+    fn test_channel_strip() {
+        let mut strip = ChannelStrip::new(SAMPLE_RATE as MathT);
+        strip.set_input_trim(0.5);
+        strip.set_output_trim(0.25);
+
+        for section in &[
+            StripSection::LowShelf,
+            StripSection::Peak,
+            StripSection::HighShelf,
+            StripSection::Compressor,
+        ] {
+            strip.set_enabled(*section, false);
+            assert!(!strip.is_enabled(*section));
+        }
+
+        let mut s = Sine::new(440.0, SAMPLE_RATE as MathT);
+
+        for _ in 0..seconds_to_samples(Duration::from_secs_f64(0.1), SAMPLE_RATE as MathT) {
+            let x = s.process();
+            assert!((strip.process(x) - x * 0.5 * 0.25).abs() < 1e-7);
+        }
     }
 
     fn normalize_write(